@@ -13,6 +13,30 @@ pub const ASSET_SEED: &str = "asset";
 pub const VAULT_SEED: &str = "vault";
 #[constant]
 pub const BET_SEED: &str = "bet";
+#[constant]
+pub const USER_ROUND_POSITION_SEED: &str = "user_round_position";
+#[constant]
+pub const JACKPOT_SEED: &str = "jackpot";
+#[constant]
+pub const JACKPOT_VAULT_SEED: &str = "jackpot_vault";
+#[constant]
+pub const KEEPER_REWARDS_SEED: &str = "keeper_rewards";
+#[constant]
+pub const KEEPER_REWARD_VAULT_SEED: &str = "keeper_reward_vault";
+#[constant]
+pub const KEEPER_STATS_SEED: &str = "keeper_stats";
+#[constant]
+pub const REFERRER_SEED: &str = "referrer";
+#[constant]
+pub const REFERRAL_VAULT_SEED: &str = "referral_vault";
+#[constant]
+pub const STAKE_VAULT_SEED: &str = "stake_vault";
+#[constant]
+pub const STAKE_ACCOUNT_SEED: &str = "stake_account";
+#[constant]
+pub const PROMO_VAULT_SEED: &str = "promo_vault";
+#[constant]
+pub const VOUCHER_SEED: &str = "voucher";
 
 /// Enum for program status flags
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
@@ -30,6 +54,7 @@ pub enum RoundStatus {
     Cancelling,        // Ongoing cancellation
     PendingSettlement, // Ended but settlement failed, needs retry
     Ended,             // Successfully settled
+    Closed,            // Claim window expired and unclaimed rewards were swept
 }
 
 /// Enum for market types
@@ -39,6 +64,28 @@ pub enum MarketType {
     SingleAsset,
     /// Group-battle market; prices captured per-asset and aggregated by groups
     GroupBattle,
+    /// Asset-battle market; assets captured like GroupBattle, bettors pick the single best asset
+    AssetBattle,
+}
+
+/// Enum for how a group's growth is aggregated from its assets (per round)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum GroupAggregation {
+    /// Mean of asset growths (weighted by asset weight_bps when set)
+    Mean,
+    /// Median of asset growths; robust to a single outlier
+    Median,
+    /// Index return from summed start and final prices
+    IndexReturn,
+}
+
+/// Enum for how an exact tie in growth is resolved when ranking (per round)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum TieBreakRule {
+    /// All tied groups/assets win (or lose) together
+    Split,
+    /// Only the lowest group id (AssetBattle: lowest asset address) is kept
+    LowestId,
 }
 
 /// Enum for bet types
@@ -47,6 +94,29 @@ pub enum BetDirection {
     Up,
     Down,
     PercentageChangeBps(i16), // e.g., 10 for 0.1%, -25 for -0.25%
+    Underperform,             // GroupBattle only: the chosen group ends with the lowest avg growth
+}
+
+/// Running stake totals per direction bucket (see DIRECTION_BUCKETS for the order)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, PartialEq, Eq, InitSpace)]
+pub struct DirectionTotals {
+    pub amounts: [u64; DIRECTION_BUCKETS],
+    pub weights: [u64; DIRECTION_BUCKETS],
+}
+
+/// How the remaining fees are split between treasury, keeper pool and burn, in bps (sums to 10000)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct FeeSchedule {
+    pub treasury_bps: u16,
+    pub keeper_pool_bps: u16,
+    pub burn_bps: u16,
+}
+
+/// A staking tier: bettors with at least `min_stake` GRT staked get `discount_bps` of their fee rebated
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct FeeDiscountTier {
+    pub min_stake: u64,
+    pub discount_bps: u16,
 }
 
 /// Enum for bet status
@@ -56,6 +126,7 @@ pub enum BetStatus {
     Won,
     Lost,
     Draw,
+    CashedOut,
 }
 
 /// Spaces
@@ -64,12 +135,17 @@ pub const DISRIMINATOR_SIZE: u8 = 8;
 /// Maths
 pub const HUNDRED_PERCENT_BPS: u16 = 10_000;
 pub const BPS_SCALING_FACTOR: u16 = 100;
+pub const GROWTH_RATE_PRECISION: i64 = 100_000_000; // growth rates are in 1e-8 units (1e8 = 100%)
 
 /// Limits
 pub const MAX_KEEPER_AUTHORITIES: usize = 5;
 pub const MAX_REMAINING_ACCOUNTS: usize = 20;
 pub const MAX_ASSETS_IN_GROUP: usize = 10;
 pub const MAX_WINNER_GROUP_IDS: usize = 10;
+pub const MAX_LOSER_GROUP_IDS: usize = 10;
+pub const MAX_WINNER_ASSETS: usize = 10;
+pub const MAX_FEE_DISCOUNT_TIERS: usize = 5;
+pub const DIRECTION_BUCKETS: usize = 6; // Up, Down, PercentageUp, PercentageDown, PercentageFlat, Underperform
 
 /// Price
 pub const ASSET_PRICE_DECIMALS: i32 = 6;
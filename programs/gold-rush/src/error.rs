@@ -85,6 +85,75 @@ pub enum GoldRushError {
     #[msg("New bet cutoff window seconds must be greater than 0")]
     InvalidNewBetCutoffWindowSecs = 0x2020,
 
+    #[msg("New draw band bps must be less than 10000")]
+    InvalidNewDrawBandBps = 0x2021,
+
+    #[msg("New early exit fee bps must be less than 10000")]
+    InvalidNewEarlyExitFeeBps = 0x2022,
+
+    #[msg("New cash out haircut bps must be less than 10000")]
+    InvalidNewCashOutHaircutBps = 0x2023,
+
+    #[msg("New max bet amount must be 0 or at least the min bet amount")]
+    InvalidNewMaxBetAmount = 0x2024,
+
+    #[msg("New claim window seconds must not be negative")]
+    InvalidNewClaimWindowSecs = 0x2025,
+
+    #[msg("New jackpot fee bps plus referral fee bps must not exceed 10000")]
+    InvalidNewJackpotFeeBps = 0x2026,
+
+    #[msg("Jackpot has already been initialized")]
+    JackpotAlreadyInitialized = 0x2027,
+
+    #[msg("Jackpot has not been initialized")]
+    JackpotNotInitialized = 0x2028,
+
+    #[msg("New fee schedule must total 10000 bps")]
+    InvalidNewFeeSchedule = 0x2029,
+
+    #[msg("Keeper rewards have already been initialized")]
+    KeeperRewardsAlreadyInitialized = 0x202A,
+
+    #[msg("Referrals have already been initialized")]
+    ReferralsAlreadyInitialized = 0x202B,
+
+    #[msg("Referrals have not been initialized")]
+    ReferralsNotInitialized = 0x202C,
+
+    #[msg("New referral fee bps plus jackpot fee bps must not exceed 10000")]
+    InvalidNewReferralFeeBps = 0x202D,
+
+    #[msg("New fee discount tiers must ascend by min stake with discounts not above 10000 bps")]
+    InvalidNewFeeDiscountTiers = 0x202E,
+
+    #[msg("New unstake cooldown seconds must not be negative")]
+    InvalidNewUnstakeCooldownSecs = 0x202F,
+
+    #[msg("Staking has already been initialized")]
+    StakingAlreadyInitialized = 0x2030,
+
+    #[msg("Staking has not been initialized")]
+    StakingNotInitialized = 0x2031,
+
+    #[msg("New loss rebate bps must not exceed 10000")]
+    InvalidNewLossRebateBps = 0x2032,
+
+    #[msg("Promotions have already been initialized")]
+    PromotionsAlreadyInitialized = 0x2033,
+
+    #[msg("Promotions have not been initialized")]
+    PromotionsNotInitialized = 0x2034,
+
+    #[msg("New max user stake per round must be 0 or at least the min bet amount")]
+    InvalidNewMaxUserStakePerRound = 0x2035,
+
+    #[msg("New max total pool must be above 0 and at least the max bet amount")]
+    InvalidNewMaxTotalPool = 0x2036,
+
+    #[msg("Keeper reward pool must be set before the fee schedule has a keeper pool leg")]
+    KeeperRewardPoolNotSet = 0x2037,
+
     // Round Management Errors (0x3000 - 0x3999)
     #[msg("Invalid timestamps: start_time must be less than end_time and in the future")]
     InvalidTimestamps = 0x3000,
@@ -128,6 +197,21 @@ pub enum GoldRushError {
     #[msg("Round has not captured all start price of the assets in the group")]
     RoundNotCapturedStartPrice = 0x3013,
 
+    #[msg("Round has not ranked all of its assets")]
+    RoundAssetsNotRanked = 0x3014,
+
+    #[msg("Asset weights in a group must not exceed 10000 bps")]
+    InvalidAssetWeight = 0x3015,
+
+    #[msg("Weighted group asset must have asset weights totaling 10000 bps")]
+    GroupAssetWeightsIncomplete = 0x3016,
+
+    #[msg("Invalid round account provided")]
+    InvalidRoundAccount = 0x3017,
+
+    #[msg("Round does not have auto-distribution enabled")]
+    AutoDistributeDisabled = 0x3018,
+
     // Betting Errors (0x4000 - 0x4999)
     #[msg("Bet amount is below minimum required")]
     BetBelowMinimum = 0x4000,
@@ -144,6 +228,60 @@ pub enum GoldRushError {
     #[msg("No bets have been placed in this round")]
     NoBetsPlaced = 0x4004,
 
+    #[msg("Bet direction is not supported for this market type")]
+    InvalidBetDirection = 0x4005,
+
+    #[msg("Bet amount must be greater than 0")]
+    InvalidBetAmount = 0x4006,
+
+    #[msg("Bet is out of the money and cannot be cashed out")]
+    CashOutNotAvailable = 0x4007,
+
+    #[msg("Cash-out is only available between bet cutoff and round end")]
+    CashOutWindowClosed = 0x4008,
+
+    #[msg("Cash-out is disabled")]
+    CashOutDisabled = 0x4009,
+
+    #[msg("Bet weight is below the requested minimum")]
+    BetWeightBelowMinimum = 0x400A,
+
+    #[msg("Bet deadline has passed")]
+    BetDeadlineExceeded = 0x400B,
+
+    #[msg("Bet amount is above the maximum")]
+    BetAboveMaximum = 0x400C,
+
+    #[msg("Bettor stake in this round would exceed the cap")]
+    UserStakeCapExceeded = 0x400D,
+
+    #[msg("Round pool would exceed the cap")]
+    RoundPoolCapExceeded = 0x400E,
+
+    #[msg("No listed bet has a reward to claim")]
+    NothingToClaim = 0x400F,
+
+    #[msg("Bettor cannot refer their own bet")]
+    SelfReferral = 0x4010,
+
+    #[msg("Invalid referrer account provided")]
+    InvalidReferrerAccount = 0x4011,
+
+    #[msg("Invalid stake account provided")]
+    InvalidStakeAccount = 0x4012,
+
+    #[msg("Voucher does not belong to the bettor")]
+    InvalidVoucher = 0x4013,
+
+    #[msg("Voucher has already been redeemed")]
+    VoucherAlreadyRedeemed = 0x4014,
+
+    #[msg("Voucher has expired")]
+    VoucherExpired = 0x4015,
+
+    #[msg("Promotional bets cannot be increased or cashed out")]
+    PromotionalBetNotAllowed = 0x4016,
+
     // Settlement & Claim Errors (0x5000 - 0x5999)
     #[msg("Error retrieving price from oracle")]
     OracleError = 0x5000,
@@ -208,6 +346,39 @@ pub enum GoldRushError {
     #[msg("Max winner group ids exceeded")]
     MaxWinnerGroupIdsExceeded = 0x5020,
 
+    #[msg("Max loser group ids exceeded")]
+    MaxLoserGroupIdsExceeded = 0x5021,
+
+    #[msg("Max winner assets exceeded")]
+    MaxWinnerAssetsExceeded = 0x5022,
+
+    #[msg("Asset has not been finalized end price")]
+    AssetNotFinalizedEndPrice = 0x5023,
+
+    #[msg("Asset has already been ranked")]
+    AssetAlreadyRanked = 0x5024,
+
+    #[msg("Round has already ranked all of its assets")]
+    RoundAlreadyRankedAssets = 0x5025,
+
+    #[msg("Claim window for this round has expired")]
+    ClaimExpired = 0x5026,
+
+    #[msg("Claim window for this round has not expired yet")]
+    ClaimWindowNotExpired = 0x5027,
+
+    #[msg("Jackpot accounts are required to settle this round")]
+    MissingJackpotAccount = 0x5028,
+
+    #[msg("Referral accounts are required to settle this round")]
+    MissingReferralAccount = 0x5029,
+
+    #[msg("Promo vault is required to return a promotional bet's stake")]
+    MissingPromoVault = 0x502A,
+
+    #[msg("Treasury token account is required to collect the early-exit fee")]
+    MissingTreasuryTokenAccount = 0x502B,
+
     // Account & Token Errors (0x6000 - 0x6999)
     #[msg("Invalid token account")]
     InvalidTokenAccount = 0x6000,
@@ -221,6 +392,30 @@ pub enum GoldRushError {
     #[msg("Token transfer operation failed")]
     TokenTransferFailed = 0x6003,
 
+    #[msg("Keeper reward pool does not match program configuration")]
+    InvalidKeeperRewardPool = 0x6004,
+
+    #[msg("Keeper reward vault is empty")]
+    KeeperRewardVaultEmpty = 0x6005,
+
+    #[msg("Stake amount must be greater than 0")]
+    InvalidStakeAmount = 0x6006,
+
+    #[msg("Unstake amount exceeds the staked amount")]
+    InsufficientStake = 0x6007,
+
+    #[msg("Unstaked GRT is still in its cooldown")]
+    UnstakeCooldownActive = 0x6008,
+
+    #[msg("No unstaked GRT to withdraw")]
+    NothingToWithdraw = 0x6009,
+
+    #[msg("Voucher amount must be greater than 0")]
+    InvalidVoucherAmount = 0x600A,
+
+    #[msg("Voucher expiry must be in the future")]
+    InvalidVoucherExpiry = 0x600B,
+
     // Math Errors (0x7000 - 0x7999)
     #[msg("Mathematical overflow detected.")]
     Overflow = 0x7000,
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FeeBurned {
+    pub round: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
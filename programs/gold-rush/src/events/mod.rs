@@ -1,9 +1,11 @@
 pub mod emergency_paused;
 pub mod emergency_unpaused;
+pub mod fee_burned;
 pub mod program_paused;
 pub mod program_unpaused;
 
 pub use emergency_paused::*;
 pub use emergency_unpaused::*;
+pub use fee_burned::*;
 pub use program_paused::*;
 pub use program_unpaused::*;
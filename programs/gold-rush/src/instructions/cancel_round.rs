@@ -1,4 +1,4 @@
-use crate::{constants::*, error::GoldRushError, state::*};
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_program;
 use anchor_spl::token::{close_account, transfer, CloseAccount, Token, TokenAccount, Transfer};
@@ -28,7 +28,8 @@ pub struct CancelRound<'info> {
     )]
     pub round_vault: Account<'info, TokenAccount>,
 
-    /// CHECK: Treasury pubkey from config
+    /// CHECK: Treasury pubkey from config; receives the rent of the closed accounts
+    #[account(mut)]
     pub treasury: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
@@ -67,20 +68,27 @@ impl<'info> CancelRound<'info> {
     }
 }
 
-pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelRound<'info>>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CancelRound<'info>>) -> Result<()> {
     // validate
     ctx.accounts.validate()?;
 
     let round_id = ctx.accounts.round.id;
     let round_bump = ctx.accounts.round.bump;
 
+    // GroupBattle bets also carry their GroupAsset so its totals stay in sync
+    let stride = if matches!(ctx.accounts.round.market_type, MarketType::GroupBattle) {
+        3
+    } else {
+        2
+    };
+
     // validate remaining account
     require!(
         ctx.remaining_accounts.len() <= MAX_REMAINING_ACCOUNTS,
         GoldRushError::InvalidRemainingAccountsLength
     );
     require!(
-        ctx.remaining_accounts.len() % 2 == 0,
+        ctx.remaining_accounts.len() % stride == 0,
         GoldRushError::InvalidRemainingAccountsLength
     );
 
@@ -119,10 +127,10 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelRound<'info>>) -> Re
         round_mut.status = RoundStatus::Cancelling;
     }
 
-    // Process bet refunds in pairs [Bet PDA, Bettor ATA]
-    for i in (0..ctx.remaining_accounts.len()).step_by(2) {
+    // Process bet refunds in pairs [Bet PDA, Bettor ATA] (triples with the GroupAsset for GroupBattle)
+    for i in (0..ctx.remaining_accounts.len()).step_by(stride) {
         // 1) Read + validate bet (isolated)
-        let bet_amount = {
+        let (bet_amount, bet_weight, bet_direction, bet_group, refund_amount) = {
             let bet_ai = &ctx.remaining_accounts[i];
             require_keys_eq!(
                 *bet_ai.owner,
@@ -143,11 +151,37 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelRound<'info>>) -> Re
             )
             .0;
             require_keys_eq!(*bet_ai.key, expected_pda, GoldRushError::InvalidBetAccount);
-            bet.amount
+
+            // Promotional stakes are refunded to the promo vault
+            if bet.is_promotional {
+                let promo_vault_pda =
+                    Pubkey::find_program_address(&[PROMO_VAULT_SEED.as_bytes()], ctx.program_id).0;
+                require_keys_eq!(
+                    ctx.remaining_accounts[i + 1].key(),
+                    promo_vault_pda,
+                    GoldRushError::MissingPromoVault
+                );
+            }
+
+            // Cashed-out bets were already paid; others share cash-out gains/losses pro-rata
+            if bet.status == BetStatus::CashedOut {
+                (0, 0, bet.direction, bet.group, 0)
+            } else {
+                let round = &ctx.accounts.round;
+                let refund_amount =
+                    calculate_refund_amount(bet.amount, round.total_pool, round.total_stake)?;
+                (
+                    bet.amount,
+                    bet.weight,
+                    bet.direction,
+                    bet.group,
+                    refund_amount,
+                )
+            }
         };
 
         // 2) Refund (isolated CPI scope)
-        if bet_amount > 0 {
+        if refund_amount > 0 {
             let token_program_info = ctx.accounts.token_program.to_account_info();
             let from_info = ctx.accounts.round_vault.to_account_info();
             let auth_info = ctx.accounts.round.to_account_info();
@@ -166,7 +200,7 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelRound<'info>>) -> Re
             let signer = &[&round_seeds[..]];
             let transfer_ctx =
                 CpiContext::new_with_signer(token_program_info, transfer_accounts, signer);
-            transfer(transfer_ctx, bet_amount)?;
+            transfer(transfer_ctx, refund_amount)?;
         }
 
         // 3) Update totals + close bet (isolated)
@@ -175,8 +209,35 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelRound<'info>>) -> Re
             let round_mut = &mut ctx.accounts.round;
             round_mut.total_pool = round_mut
                 .total_pool
+                .checked_sub(refund_amount)
+                .ok_or(GoldRushError::Underflow)?;
+            round_mut.total_stake = round_mut
+                .total_stake
                 .checked_sub(bet_amount)
                 .ok_or(GoldRushError::Underflow)?;
+            sub_from_direction_totals(
+                &mut round_mut.direction_totals,
+                &bet_direction,
+                bet_amount,
+                bet_weight,
+            )?;
+        }
+        if stride == 3 {
+            // update the bet's group totals
+            let group_ai = &ctx.remaining_accounts[i + 2];
+            require!(
+                Some(group_ai.key()) == bet_group,
+                GoldRushError::InvalidGroupAssetAccount
+            );
+            let mut group_asset: Account<GroupAsset> = Account::try_from(group_ai)
+                .map_err(|_| GoldRushError::InvalidGroupAssetAccountData)?;
+            sub_from_direction_totals(
+                &mut group_asset.direction_totals,
+                &bet_direction,
+                bet_amount,
+                bet_weight,
+            )?;
+            group_asset.exit(ctx.program_id)?;
         }
         {
             // close bet account by manual lamports move + reassign
@@ -189,6 +250,8 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelRound<'info>>) -> Re
                 .checked_add(bet_lamports)
                 .ok_or(GoldRushError::Overflow)?;
             **bet_ai.lamports.borrow_mut() = 0;
+            // the runtime only lets a zeroed account change owner
+            bet_ai.try_borrow_mut_data()?.fill(0);
             bet_ai.assign(&system_program::ID);
         }
     }
@@ -198,7 +261,7 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelRound<'info>>) -> Re
         let round_mut = &mut ctx.accounts.round;
         round_mut.cancelled_bets = round_mut
             .cancelled_bets
-            .checked_add((ctx.remaining_accounts.len() / 2) as u64)
+            .checked_add((ctx.remaining_accounts.len() / stride) as u64)
             .ok_or(GoldRushError::Overflow)?;
     }
 
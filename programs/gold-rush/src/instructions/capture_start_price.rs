@@ -13,6 +13,15 @@ pub struct CaptureStartPrice<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + KeeperStats::INIT_SPACE,
+        seeds = [KEEPER_STATS_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub keeper_stats: Account<'info, KeeperStats>,
+
     #[account(
         mut,
         seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
@@ -50,7 +59,10 @@ impl<'info> CaptureStartPrice<'info> {
             GoldRushError::InvalidRoundStatus
         );
         require!(
-            self.round.market_type == MarketType::GroupBattle,
+            matches!(
+                self.round.market_type,
+                MarketType::GroupBattle | MarketType::AssetBattle
+            ),
             GoldRushError::InvalidRoundMarketType,
         );
         require!(
@@ -99,6 +111,8 @@ pub fn handler(ctx: Context<CaptureStartPrice>) -> Result<()> {
         ..Clock::get()?
     };
 
+    // Only assets whose price is captured here earn the keeper bounty
+    let mut captured_assets = 0u64;
     for pair in remaining_accounts.chunks(2) {
         let asset_ai = &pair[0];
         let pyth_ai = &pair[1];
@@ -163,6 +177,9 @@ pub fn handler(ctx: Context<CaptureStartPrice>) -> Result<()> {
         if asset.start_price.is_none() {
             require!(normalized > 0, GoldRushError::InvalidAssetPrice);
             asset.start_price = Some(normalized);
+            captured_assets = captured_assets
+                .checked_add(1)
+                .ok_or(GoldRushError::Overflow)?;
 
             // serialize back
             let serialized = asset
@@ -190,5 +207,14 @@ pub fn handler(ctx: Context<CaptureStartPrice>) -> Result<()> {
         .checked_add((ctx.remaining_accounts.len() / 2) as u64)
         .ok_or(GoldRushError::Overflow)?;
 
+    // accrue keeper bounty
+    accrue_keeper_bounty(
+        &mut ctx.accounts.keeper_stats,
+        ctx.accounts.signer.key(),
+        ctx.bumps.keeper_stats,
+        captured_assets,
+        ctx.accounts.config.keeper_bounty_per_unit,
+    )?;
+
     Ok(())
 }
@@ -0,0 +1,229 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+#[derive(Accounts)]
+pub struct CashOutBet<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [BET_SEED.as_bytes(), round.key().as_ref(), &bet.id.to_le_bytes()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        seeds = [USER_ROUND_POSITION_SEED.as_bytes(), round.key().as_ref(), signer.key().as_ref()],
+        bump = user_round_position.bump
+    )]
+    pub user_round_position: Account<'info, UserRoundPosition>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes(), round.key().as_ref()],
+        bump
+    )]
+    pub round_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+    )]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is the price feed account
+    pub price_update: Account<'info, PriceUpdateV2>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CashOutBet<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.config.status == ProgramStatus::Active,
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.config.cash_out_haircut_bps > 0,
+            GoldRushError::CashOutDisabled
+        );
+
+        require!(
+            self.round.status == RoundStatus::Active,
+            GoldRushError::RoundNotActive
+        );
+
+        // Oracle-marked exit only exists for the single-asset market
+        require!(
+            matches!(self.round.market_type, MarketType::SingleAsset),
+            GoldRushError::InvalidRoundMarketType
+        );
+
+        // Before cutoff the bettor can withdraw instead; after end_time the round settles
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= self.round.bet_cutoff_time && now < self.round.end_time,
+            GoldRushError::CashOutWindowClosed
+        );
+
+        require_keys_eq!(
+            self.bet.bettor,
+            self.signer.key(),
+            GoldRushError::Unauthorized
+        );
+
+        require_keys_eq!(
+            self.bet.round,
+            self.round.key(),
+            GoldRushError::InvalidBetAccount
+        );
+
+        // Promotional bets can only be withdrawn or settled
+        require!(
+            !self.bet.is_promotional,
+            GoldRushError::PromotionalBetNotAllowed
+        );
+
+        require!(
+            self.bet.status == BetStatus::Pending,
+            GoldRushError::InvalidBetStatus
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CashOutBet>) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let config = &ctx.accounts.config;
+    let round = &mut ctx.accounts.round;
+    let bet = &mut ctx.accounts.bet;
+    let user_round_position = &mut ctx.accounts.user_round_position;
+
+    // Read current price from oracle
+    let price = ctx
+        .accounts
+        .price_update
+        .get_price_no_older_than(
+            &Clock::get()?,
+            config.max_price_update_age_secs,
+            &config.single_asset_feed_id,
+        )
+        .map_err(|_| GoldRushError::PythError)?;
+    let current_price = normalize_price_to_u64(price.price, price.exponent)?;
+    require!(current_price > 0, GoldRushError::InvalidAssetPrice);
+
+    // Determine current move against the start price (same draw band as settlement)
+    let start_price = round.start_price.ok_or(GoldRushError::InvalidAssetPrice)?;
+    let price_change: i64 = (current_price as i64)
+        .checked_sub(start_price as i64)
+        .ok_or(GoldRushError::Overflow)?;
+    let price_change = apply_draw_band(price_change, start_price, round.draw_band_bps)?;
+    let is_winner = is_bet_winner(bet.direction.clone(), price_change);
+
+    // Mark by weight against the current pool composition net of the settlement fee,
+    // then apply the haircut
+    let winning_side_weight = calculate_side_weight(&round.direction_totals, price_change > 0)?;
+    let cash_out_amount = calculate_cash_out_amount(
+        bet.amount,
+        bet.weight,
+        round.total_pool,
+        winning_side_weight,
+        config.fee_single_asset_bps,
+        is_winner,
+        config.cash_out_haircut_bps,
+    )?;
+
+    // Transfer cash-out amount from round vault to bettor
+    if cash_out_amount > 0 {
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.round_vault.to_account_info(),
+            to: ctx.accounts.bettor_token_account.to_account_info(),
+            authority: round.to_account_info(),
+        };
+        let round_bump = round.bump;
+        let round_id = round.id;
+        let seeds = &[
+            ROUND_SEED.as_bytes(),
+            &round_id.to_le_bytes(),
+            &[round_bump],
+        ];
+        let signer = &[&seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            signer,
+        );
+        transfer(transfer_ctx, cash_out_amount)?;
+    }
+
+    // Remove the bet from the pool; the haircut stays for the remaining bettors
+    round.total_pool = round
+        .total_pool
+        .checked_sub(cash_out_amount)
+        .ok_or(GoldRushError::Underflow)?;
+    round.total_stake = round
+        .total_stake
+        .checked_sub(bet.amount)
+        .ok_or(GoldRushError::Underflow)?;
+    if bet.referrer.is_some() {
+        round.referred_stake = round
+            .referred_stake
+            .checked_sub(bet.amount)
+            .ok_or(GoldRushError::Underflow)?;
+    }
+    round.discounted_stake = round
+        .discounted_stake
+        .checked_sub(calculate_discounted_stake(
+            bet.amount,
+            bet.fee_discount_bps,
+        )?)
+        .ok_or(GoldRushError::Underflow)?;
+    sub_from_direction_totals(
+        &mut round.direction_totals,
+        &bet.direction,
+        bet.amount,
+        bet.weight,
+    )?;
+
+    // Update user round position
+    user_round_position.total_stake = user_round_position
+        .total_stake
+        .checked_sub(bet.amount)
+        .ok_or(GoldRushError::Underflow)?;
+    user_round_position.total_weight = user_round_position
+        .total_weight
+        .checked_sub(bet.weight)
+        .ok_or(GoldRushError::Underflow)?;
+
+    // set bet fields
+    bet.status = BetStatus::CashedOut;
+    bet.weight = 0;
+    bet.cash_out_amount = cash_out_amount;
+
+    Ok(())
+}
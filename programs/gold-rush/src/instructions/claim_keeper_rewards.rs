@@ -0,0 +1,126 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct ClaimKeeperRewards<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [KEEPER_REWARDS_SEED.as_bytes()],
+        bump = keeper_rewards.bump
+    )]
+    pub keeper_rewards: Account<'info, KeeperRewards>,
+
+    #[account(
+        mut,
+        seeds = [KEEPER_REWARD_VAULT_SEED.as_bytes()],
+        bump = keeper_rewards.vault_bump
+    )]
+    pub keeper_reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [KEEPER_STATS_SEED.as_bytes(), signer.key().as_ref()],
+        bump = keeper_stats.bump
+    )]
+    pub keeper_stats: Account<'info, KeeperStats>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+    )]
+    pub keeper_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimKeeperRewards<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            matches!(
+                self.config.status,
+                ProgramStatus::Active | ProgramStatus::EmergencyPaused
+            ),
+            GoldRushError::ProgramPaused
+        );
+
+        // Keepers removed from config can still claim what they earned
+        require_keys_eq!(
+            self.keeper_stats.keeper,
+            self.signer.key(),
+            GoldRushError::Unauthorized
+        );
+
+        require!(
+            self.keeper_stats.total_earned > self.keeper_stats.total_claimed,
+            GoldRushError::NothingToClaim
+        );
+
+        require!(
+            self.keeper_reward_vault.amount > 0,
+            GoldRushError::KeeperRewardVaultEmpty
+        );
+
+        require_keys_eq!(
+            self.keeper_reward_vault.mint,
+            self.mint.key(),
+            GoldRushError::InvalidMint
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ClaimKeeperRewards>) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let keeper_rewards = &mut ctx.accounts.keeper_rewards;
+    let keeper_stats = &mut ctx.accounts.keeper_stats;
+
+    // pay what the vault can cover; the rest stays claimable
+    let unclaimed_amount = keeper_stats
+        .total_earned
+        .checked_sub(keeper_stats.total_claimed)
+        .ok_or(GoldRushError::Underflow)?;
+    let claim_amount = unclaimed_amount.min(ctx.accounts.keeper_reward_vault.amount);
+
+    // transfer from keeper reward vault to signer
+    let transfer_accounts = Transfer {
+        from: ctx.accounts.keeper_reward_vault.to_account_info(),
+        to: ctx.accounts.keeper_token_account.to_account_info(),
+        authority: keeper_rewards.to_account_info(),
+    };
+    let seeds = &[KEEPER_REWARDS_SEED.as_bytes(), &[keeper_rewards.bump]];
+    let signer = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        signer,
+    );
+    transfer(transfer_ctx, claim_amount)?;
+
+    // set fields
+    keeper_stats.total_claimed = keeper_stats
+        .total_claimed
+        .checked_add(claim_amount)
+        .ok_or(GoldRushError::Overflow)?;
+    keeper_rewards.total_paid_out = keeper_rewards
+        .total_paid_out
+        .checked_add(claim_amount)
+        .ok_or(GoldRushError::Overflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct ClaimPositionRewards<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes(), round.key().as_ref()],
+        bump
+    )]
+    pub round_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [USER_ROUND_POSITION_SEED.as_bytes(), round.key().as_ref(), signer.key().as_ref()],
+        bump = user_round_position.bump
+    )]
+    pub user_round_position: Account<'info, UserRoundPosition>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+    )]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    // Optional: only required when the position holds promotional bets
+    #[account(
+        mut,
+        seeds = [PROMO_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub promo_vault: Option<Account<'info, TokenAccount>>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimPositionRewards<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            matches!(
+                self.config.status,
+                ProgramStatus::Active | ProgramStatus::EmergencyPaused
+            ),
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.round.status != RoundStatus::Closed,
+            GoldRushError::ClaimExpired
+        );
+        require!(
+            self.round.status == RoundStatus::Ended,
+            GoldRushError::RoundNotEnded
+        );
+        require!(
+            !is_claim_expired(
+                self.round.settled_at,
+                self.round.claim_window_secs,
+                Clock::get()?.unix_timestamp
+            )?,
+            GoldRushError::ClaimExpired
+        );
+
+        require_keys_eq!(
+            self.round_vault.mint,
+            self.mint.key(),
+            GoldRushError::InvalidMint
+        );
+        require_keys_eq!(
+            self.bettor_token_account.mint,
+            self.mint.key(),
+            GoldRushError::InvalidMint
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimPositionRewards<'info>>,
+) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    // Bets are passed in the same order as user_round_position.bet_ids
+    let bet_ids = &ctx.accounts.user_round_position.bet_ids;
+    require!(
+        ctx.remaining_accounts.len() == bet_ids.len(),
+        GoldRushError::InvalidRemainingAccountsLength
+    );
+
+    let round = &ctx.accounts.round;
+    let signer_key = ctx.accounts.signer.key();
+
+    let mut total_reward_amount = 0u64;
+    let mut total_promo_amount = 0u64;
+    for (acc_info, bet_id) in ctx.remaining_accounts.iter().zip(bet_ids.iter()) {
+        // Ownership must be our program (Bet PDA)
+        require_keys_eq!(
+            *acc_info.owner,
+            *ctx.program_id,
+            GoldRushError::InvalidBetAccount
+        );
+
+        // Borrow and deserialize Bet
+        let mut data = acc_info.try_borrow_mut_data()?;
+        let mut bet: Bet = Bet::try_deserialize(&mut &data[..])
+            .map_err(|_| GoldRushError::InvalidBetAccountData)?;
+
+        // Validate expected Bet PDA
+        let expected_pda = Pubkey::find_program_address(
+            &[
+                BET_SEED.as_bytes(),
+                round.key().as_ref(),
+                &bet_id.to_le_bytes(),
+            ],
+            ctx.program_id,
+        )
+        .0;
+        require_keys_eq!(
+            *acc_info.key,
+            expected_pda,
+            GoldRushError::InvalidBetAccount
+        );
+        require_keys_eq!(bet.bettor, signer_key, GoldRushError::Unauthorized);
+
+        // Only unclaimed Won/Draw bets and Lost bets with a loss or fee rebate pay out; the rest are skipped
+        if bet.claimed || !is_bet_claimable(&bet, round)? {
+            continue;
+        }
+
+        // same payout math as claim_reward
+        let payout = calculate_claim_amount_with_rebate(&bet, round)?;
+        let (reward_amount, promo_amount) =
+            split_promotional_payout(payout, bet.amount, bet.is_promotional)?;
+        total_reward_amount = total_reward_amount
+            .checked_add(reward_amount)
+            .ok_or(GoldRushError::Overflow)?;
+        total_promo_amount = total_promo_amount
+            .checked_add(promo_amount)
+            .ok_or(GoldRushError::Overflow)?;
+
+        // set bet fields and serialize back
+        bet.claimed = true;
+        let serialized = bet
+            .try_to_vec()
+            .map_err(|_| GoldRushError::SerializeError)?;
+        if serialized.len() > data[8..].len() {
+            return Err(GoldRushError::AccountDataTooSmall.into());
+        }
+        data[8..8 + serialized.len()].copy_from_slice(&serialized);
+    }
+
+    require!(
+        total_reward_amount > 0 || total_promo_amount > 0,
+        GoldRushError::NothingToClaim
+    );
+
+    let round_id = round.id;
+    let round_bump = round.bump;
+    let seeds = &[
+        ROUND_SEED.as_bytes(),
+        &round_id.to_le_bytes(),
+        &[round_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // single transfer from vault to signer
+    if total_reward_amount > 0 {
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.round_vault.to_account_info(),
+            to: ctx.accounts.bettor_token_account.to_account_info(),
+            authority: round.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            signer,
+        );
+        transfer(transfer_ctx, total_reward_amount)?;
+    }
+
+    // single transfer of the promotional stakes from vault to promo vault
+    if total_promo_amount > 0 {
+        let promo_vault = ctx
+            .accounts
+            .promo_vault
+            .as_ref()
+            .ok_or(GoldRushError::MissingPromoVault)?;
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.round_vault.to_account_info(),
+            to: promo_vault.to_account_info(),
+            authority: round.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            signer,
+        );
+        transfer(transfer_ctx, total_promo_amount)?;
+    }
+
+    Ok(())
+}
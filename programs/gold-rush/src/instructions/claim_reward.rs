@@ -1,4 +1,4 @@
-use crate::{constants::*, error::GoldRushError, state::*};
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
 
@@ -40,6 +40,14 @@ pub struct ClaimReward<'info> {
     )]
     pub bettor_token_account: Account<'info, TokenAccount>,
 
+    // Optional: only required for promotional bets, whose stake goes back to the promo vault
+    #[account(
+        mut,
+        seeds = [PROMO_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub promo_vault: Option<Account<'info, TokenAccount>>,
+
     pub mint: Account<'info, Mint>,
 
     pub token_program: Program<'info, Token>,
@@ -62,13 +70,25 @@ impl<'info> ClaimReward<'info> {
             GoldRushError::Unauthorized
         );
 
+        require!(
+            self.round.status != RoundStatus::Closed,
+            GoldRushError::ClaimExpired
+        );
         require!(
             self.round.status == RoundStatus::Ended,
             GoldRushError::RoundNotEnded
         );
+        require!(
+            !is_claim_expired(
+                self.round.settled_at,
+                self.round.claim_window_secs,
+                Clock::get()?.unix_timestamp
+            )?,
+            GoldRushError::ClaimExpired
+        );
 
         require!(
-            matches!(self.bet.status, BetStatus::Won | BetStatus::Draw),
+            is_bet_claimable(&self.bet, &self.round)?,
             GoldRushError::BetNotWonOrDraw
         );
 
@@ -92,6 +112,10 @@ impl<'info> ClaimReward<'info> {
             );
         }
 
+        if self.bet.is_promotional {
+            require!(self.promo_vault.is_some(), GoldRushError::MissingPromoVault);
+        }
+
         Ok(())
     }
 }
@@ -103,26 +127,12 @@ pub fn handler(ctx: Context<ClaimReward>) -> Result<()> {
     let bet = &mut ctx.accounts.bet;
     let round = &ctx.accounts.round;
 
-    // calculate reward
-    let reward_amount = match bet.status {
-        BetStatus::Won => (bet.weight as u128)
-            .checked_mul(round.total_reward_pool as u128)
-            .and_then(|intermediate_result| {
-                intermediate_result.checked_div(round.winners_weight as u128)
-            })
-            .map(|final_result| final_result as u64)
-            .ok_or(GoldRushError::Underflow)?,
-        BetStatus::Draw => bet.amount,
-        BetStatus::Pending => return Err(GoldRushError::ClaimPendingBet.into()),
-        BetStatus::Lost => return Err(GoldRushError::ClaimLosingBet.into()),
-    };
+    // calculate reward (the loss rebate for Lost bets), plus the staking fee rebate
+    let payout = calculate_claim_amount_with_rebate(bet, round)?;
+    // promotional bets return their stake to the promo vault
+    let (reward_amount, promo_amount) =
+        split_promotional_payout(payout, bet.amount, bet.is_promotional)?;
 
-    // transfer from vault to signer
-    let transfer_accounts = Transfer {
-        from: ctx.accounts.round_vault.to_account_info(),
-        to: ctx.accounts.bettor_token_account.to_account_info(),
-        authority: round.to_account_info(),
-    };
     let round_id = round.id;
     let round_bump = round.bump;
     let seeds = &[
@@ -131,6 +141,13 @@ pub fn handler(ctx: Context<ClaimReward>) -> Result<()> {
         &[round_bump],
     ];
     let signer = &[&seeds[..]];
+
+    // transfer from vault to signer
+    let transfer_accounts = Transfer {
+        from: ctx.accounts.round_vault.to_account_info(),
+        to: ctx.accounts.bettor_token_account.to_account_info(),
+        authority: round.to_account_info(),
+    };
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
         transfer_accounts,
@@ -138,6 +155,26 @@ pub fn handler(ctx: Context<ClaimReward>) -> Result<()> {
     );
     transfer(transfer_ctx, reward_amount)?;
 
+    // transfer the promotional stake from vault to promo vault
+    if promo_amount > 0 {
+        let promo_vault = ctx
+            .accounts
+            .promo_vault
+            .as_ref()
+            .ok_or(GoldRushError::MissingPromoVault)?;
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.round_vault.to_account_info(),
+            to: promo_vault.to_account_info(),
+            authority: round.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            signer,
+        );
+        transfer(transfer_ctx, promo_amount)?;
+    }
+
     // set bet fields
     bet.claimed = true;
 
@@ -0,0 +1,266 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct ClaimRewardsBatch<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+    )]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    // Optional: only required when a promotional bet is claimed
+    #[account(
+        mut,
+        seeds = [PROMO_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub promo_vault: Option<Account<'info, TokenAccount>>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimRewardsBatch<'info> {
+    pub fn validate(&self, remaining_accounts_len: usize) -> Result<()> {
+        require!(
+            matches!(
+                self.config.status,
+                ProgramStatus::Active | ProgramStatus::EmergencyPaused
+            ),
+            GoldRushError::ProgramPaused
+        );
+
+        require_keys_eq!(
+            self.bettor_token_account.mint,
+            self.mint.key(),
+            GoldRushError::InvalidMint
+        );
+
+        // [Round, Vault, Bet] triples
+        require!(
+            remaining_accounts_len > 0
+                && remaining_accounts_len.is_multiple_of(3)
+                && remaining_accounts_len <= MAX_REMAINING_ACCOUNTS,
+            GoldRushError::InvalidRemainingAccountsLength
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimRewardsBatch<'info>>) -> Result<()> {
+    // validate
+    ctx.accounts.validate(ctx.remaining_accounts.len())?;
+
+    let signer_key = ctx.accounts.signer.key();
+    let mint_key = ctx.accounts.mint.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    // Rewards are accumulated per round and paid with one transfer when the round changes
+    let mut pending: Option<(usize, u64, u8, u64)> = None; // (triple index, round id, round bump, amount)
+    for (i, triple) in ctx.remaining_accounts.chunks(3).enumerate() {
+        let (round_ai, vault_ai, bet_ai) = (&triple[0], &triple[1], &triple[2]);
+
+        // Round: program-owned PDA that has ended
+        let round: Account<Round> =
+            Account::try_from(round_ai).map_err(|_| GoldRushError::InvalidRoundAccount)?;
+        let expected_round_pda = Pubkey::find_program_address(
+            &[ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+            ctx.program_id,
+        )
+        .0;
+        require_keys_eq!(
+            round_ai.key(),
+            expected_round_pda,
+            GoldRushError::InvalidRoundAccount
+        );
+        require!(
+            round.status != RoundStatus::Closed,
+            GoldRushError::ClaimExpired
+        );
+        require!(
+            round.status == RoundStatus::Ended,
+            GoldRushError::RoundNotEnded
+        );
+        require!(
+            !is_claim_expired(round.settled_at, round.claim_window_secs, now)?,
+            GoldRushError::ClaimExpired
+        );
+
+        // Vault: the round's vault PDA holding the configured mint
+        let vault: Account<TokenAccount> =
+            Account::try_from(vault_ai).map_err(|_| GoldRushError::InvalidTokenAccount)?;
+        let expected_vault_pda = Pubkey::find_program_address(
+            &[VAULT_SEED.as_bytes(), round_ai.key().as_ref()],
+            ctx.program_id,
+        )
+        .0;
+        require_keys_eq!(
+            vault_ai.key(),
+            expected_vault_pda,
+            GoldRushError::InvalidTokenAccount
+        );
+        require_keys_eq!(vault.mint, mint_key, GoldRushError::InvalidMint);
+
+        // Bet: same checks as ClaimReward::validate
+        require_keys_eq!(
+            *bet_ai.owner,
+            *ctx.program_id,
+            GoldRushError::InvalidBetAccount
+        );
+        let mut data = bet_ai.try_borrow_mut_data()?;
+        let mut bet: Bet = Bet::try_deserialize(&mut &data[..])
+            .map_err(|_| GoldRushError::InvalidBetAccountData)?;
+        let expected_bet_pda = Pubkey::find_program_address(
+            &[
+                BET_SEED.as_bytes(),
+                round_ai.key().as_ref(),
+                &bet.id.to_le_bytes(),
+            ],
+            ctx.program_id,
+        )
+        .0;
+        require_keys_eq!(
+            bet_ai.key(),
+            expected_bet_pda,
+            GoldRushError::InvalidBetAccount
+        );
+        require_keys_eq!(bet.bettor, signer_key, GoldRushError::Unauthorized);
+        require!(
+            is_bet_claimable(&bet, &round)?,
+            GoldRushError::BetNotWonOrDraw
+        );
+        require!(!bet.claimed, GoldRushError::AlreadyClaimed);
+        if bet.status == BetStatus::Won {
+            require!(
+                round.winners_weight > 0,
+                GoldRushError::RewardCalculationError
+            );
+        }
+
+        // calculate reward (the loss rebate for Lost bets), plus the staking fee rebate
+        let payout = calculate_claim_amount_with_rebate(&bet, &round)?;
+        let (reward_amount, promo_amount) =
+            split_promotional_payout(payout, bet.amount, bet.is_promotional)?;
+
+        // set bet fields and serialize back
+        bet.claimed = true;
+        let serialized = bet
+            .try_to_vec()
+            .map_err(|_| GoldRushError::SerializeError)?;
+        if serialized.len() > data[8..].len() {
+            return Err(GoldRushError::AccountDataTooSmall.into());
+        }
+        data[8..8 + serialized.len()].copy_from_slice(&serialized);
+        drop(data);
+
+        // promotional bets return their stake to the promo vault right away
+        return_promotional_stake(&ctx, i, round.id, round.bump, promo_amount)?;
+
+        // Accumulate while the round stays the same, otherwise pay out the previous round
+        pending = match pending {
+            Some((index, round_id, round_bump, amount)) if round_id == round.id => Some((
+                index,
+                round_id,
+                round_bump,
+                amount
+                    .checked_add(reward_amount)
+                    .ok_or(GoldRushError::Overflow)?,
+            )),
+            previous => {
+                if let Some((index, round_id, round_bump, amount)) = previous {
+                    pay_round_rewards(&ctx, index, round_id, round_bump, amount)?;
+                }
+                Some((i, round.id, round.bump, reward_amount))
+            }
+        };
+    }
+
+    if let Some((index, round_id, round_bump, amount)) = pending {
+        pay_round_rewards(&ctx, index, round_id, round_bump, amount)?;
+    }
+
+    Ok(())
+}
+
+fn pay_round_rewards<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ClaimRewardsBatch<'info>>,
+    index: usize,
+    round_id: u64,
+    round_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    // transfer from round vault to signer
+    let transfer_accounts = Transfer {
+        from: ctx.remaining_accounts[index * 3 + 1].to_account_info(),
+        to: ctx.accounts.bettor_token_account.to_account_info(),
+        authority: ctx.remaining_accounts[index * 3].to_account_info(),
+    };
+    let seeds = &[
+        ROUND_SEED.as_bytes(),
+        &round_id.to_le_bytes(),
+        &[round_bump],
+    ];
+    let signer = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        signer,
+    );
+    transfer(transfer_ctx, amount)
+}
+
+fn return_promotional_stake<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ClaimRewardsBatch<'info>>,
+    index: usize,
+    round_id: u64,
+    round_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let promo_vault = ctx
+        .accounts
+        .promo_vault
+        .as_ref()
+        .ok_or(GoldRushError::MissingPromoVault)?;
+
+    // transfer from round vault to promo vault
+    let transfer_accounts = Transfer {
+        from: ctx.remaining_accounts[index * 3 + 1].to_account_info(),
+        to: promo_vault.to_account_info(),
+        authority: ctx.remaining_accounts[index * 3].to_account_info(),
+    };
+    let seeds = &[
+        ROUND_SEED.as_bytes(),
+        &round_id.to_le_bytes(),
+        &[round_bump],
+    ];
+    let signer = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        signer,
+    );
+    transfer(transfer_ctx, amount)
+}
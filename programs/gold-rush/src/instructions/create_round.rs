@@ -40,7 +40,7 @@ pub struct CreateRound<'info> {
 }
 
 impl<'info> CreateRound<'info> {
-    pub fn validate(&self, start_time: i64, end_time: i64) -> Result<()> {
+    pub fn validate(&self, start_time: i64, end_time: i64, is_jackpot_round: bool) -> Result<()> {
         require!(
             self.config.status == ProgramStatus::Active,
             GoldRushError::ProgramPaused
@@ -58,6 +58,13 @@ impl<'info> CreateRound<'info> {
             GoldRushError::InvalidTimestamps
         );
 
+        if is_jackpot_round {
+            require!(
+                self.config.jackpot_enabled,
+                GoldRushError::JackpotNotInitialized
+            );
+        }
+
         Ok(())
     }
 }
@@ -67,9 +74,13 @@ pub fn handler(
     market_type: MarketType,
     start_time: i64,
     end_time: i64,
+    aggregation: GroupAggregation,
+    auto_distribute: bool,
+    is_jackpot_round: bool,
 ) -> Result<()> {
     // validate
-    ctx.accounts.validate(start_time, end_time)?;
+    ctx.accounts
+        .validate(start_time, end_time, is_jackpot_round)?;
 
     let config = &mut ctx.accounts.config;
     let round = &mut ctx.accounts.round;
@@ -89,6 +100,14 @@ pub fn handler(
     round.vault = ctx.accounts.vault.key();
     round.vault_bump = ctx.bumps.vault;
     round.market_type = market_type;
+    round.aggregation = aggregation;
+    round.tie_break_rule = config.tie_break_rule.clone();
+    round.draw_band_bps = config.draw_band_bps;
+    round.max_total_pool = config.max_total_pool;
+    round.claim_window_secs = config.claim_window_secs;
+    round.loss_rebate_bps = config.loss_rebate_bps;
+    round.auto_distribute = auto_distribute;
+    round.is_jackpot_round = is_jackpot_round;
     round.status = RoundStatus::Scheduled;
     round.created_at = Clock::get()?.unix_timestamp;
     round.bump = ctx.bumps.round;
@@ -0,0 +1,220 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+
+#[derive(Accounts)]
+pub struct DistributeRewards<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes(), round.key().as_ref()],
+        bump
+    )]
+    pub round_vault: Account<'info, TokenAccount>,
+
+    // Optional: only required when a promotional bet is distributed
+    #[account(
+        mut,
+        seeds = [PROMO_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub promo_vault: Option<Account<'info, TokenAccount>>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DistributeRewards<'info> {
+    pub fn validate(&self, remaining_accounts_len: usize) -> Result<()> {
+        require!(
+            self.config.status == ProgramStatus::Active,
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.config.keeper_authorities.contains(&self.signer.key()),
+            GoldRushError::UnauthorizedKeeper
+        );
+
+        require!(
+            self.round.status != RoundStatus::Closed,
+            GoldRushError::ClaimExpired
+        );
+        require!(
+            self.round.status == RoundStatus::Ended,
+            GoldRushError::RoundNotEnded
+        );
+        require!(
+            !is_claim_expired(
+                self.round.settled_at,
+                self.round.claim_window_secs,
+                Clock::get()?.unix_timestamp
+            )?,
+            GoldRushError::ClaimExpired
+        );
+
+        require!(
+            self.round.auto_distribute,
+            GoldRushError::AutoDistributeDisabled
+        );
+
+        require_keys_eq!(
+            self.round_vault.mint,
+            self.mint.key(),
+            GoldRushError::InvalidMint
+        );
+
+        // [Bet, Bettor ATA] pairs
+        require!(
+            remaining_accounts_len.is_multiple_of(2)
+                && remaining_accounts_len <= MAX_REMAINING_ACCOUNTS,
+            GoldRushError::InvalidRemainingAccountsLength
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, DistributeRewards<'info>>) -> Result<()> {
+    // validate
+    ctx.accounts.validate(ctx.remaining_accounts.len())?;
+
+    let round = &ctx.accounts.round;
+    let round_key = round.key();
+    let mint_key = ctx.accounts.mint.key();
+    let round_id = round.id;
+    let round_bump = round.bump;
+
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let (bet_ai, bettor_ata_ai) = (&pair[0], &pair[1]);
+
+        // Ownership must be our program (Bet PDA)
+        require_keys_eq!(
+            *bet_ai.owner,
+            *ctx.program_id,
+            GoldRushError::InvalidBetAccount
+        );
+
+        // Borrow and deserialize Bet
+        let mut data = bet_ai.try_borrow_mut_data()?;
+        let mut bet: Bet = Bet::try_deserialize(&mut &data[..])
+            .map_err(|_| GoldRushError::InvalidBetAccountData)?;
+
+        // Validate expected Bet PDA
+        let expected_pda = Pubkey::find_program_address(
+            &[
+                BET_SEED.as_bytes(),
+                round_key.as_ref(),
+                &bet.id.to_le_bytes(),
+            ],
+            ctx.program_id,
+        )
+        .0;
+        require_keys_eq!(*bet_ai.key, expected_pda, GoldRushError::InvalidBetAccount);
+
+        // The payout must go to the bettor's own ATA
+        require_keys_eq!(
+            *bettor_ata_ai.key,
+            get_associated_token_address(&bet.bettor, &mint_key),
+            GoldRushError::InvalidTokenAccount
+        );
+        let bettor_ata: Account<TokenAccount> =
+            Account::try_from(bettor_ata_ai).map_err(|_| GoldRushError::InvalidTokenAccount)?;
+        require_keys_eq!(
+            bettor_ata.owner,
+            bet.bettor,
+            GoldRushError::InvalidTokenAccount
+        );
+        require_keys_eq!(bettor_ata.mint, mint_key, GoldRushError::InvalidMint);
+
+        // Cashed-out, already claimed and Lost bets without a loss or fee rebate have nothing to push
+        if bet.claimed || !is_bet_claimable(&bet, round)? {
+            continue;
+        }
+        if bet.status == BetStatus::Won {
+            require!(
+                round.winners_weight > 0,
+                GoldRushError::RewardCalculationError
+            );
+        }
+
+        // calculate reward (the loss rebate for Lost bets), plus the staking fee rebate
+        let payout = calculate_claim_amount_with_rebate(&bet, round)?;
+        // promotional bets return their stake to the promo vault
+        let (reward_amount, promo_amount) =
+            split_promotional_payout(payout, bet.amount, bet.is_promotional)?;
+
+        let seeds = &[
+            ROUND_SEED.as_bytes(),
+            &round_id.to_le_bytes(),
+            &[round_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // transfer from vault to bettor
+        if reward_amount > 0 {
+            let transfer_accounts = Transfer {
+                from: ctx.accounts.round_vault.to_account_info(),
+                to: bettor_ata_ai.to_account_info(),
+                authority: round.to_account_info(),
+            };
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_accounts,
+                signer,
+            );
+            transfer(transfer_ctx, reward_amount)?;
+        }
+
+        // transfer the promotional stake from vault to promo vault
+        if promo_amount > 0 {
+            let promo_vault = ctx
+                .accounts
+                .promo_vault
+                .as_ref()
+                .ok_or(GoldRushError::MissingPromoVault)?;
+            let transfer_accounts = Transfer {
+                from: ctx.accounts.round_vault.to_account_info(),
+                to: promo_vault.to_account_info(),
+                authority: round.to_account_info(),
+            };
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_accounts,
+                signer,
+            );
+            transfer(transfer_ctx, promo_amount)?;
+        }
+
+        // set bet fields and serialize back
+        bet.claimed = true;
+        let serialized = bet
+            .try_to_vec()
+            .map_err(|_| GoldRushError::SerializeError)?;
+        if serialized.len() > data[8..].len() {
+            return Err(GoldRushError::AccountDataTooSmall.into());
+        }
+        data[8..8 + serialized.len()].copy_from_slice(&serialized);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,217 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::AccountDeserialize;
+
+#[derive(Accounts)]
+pub struct FinalizeEndAssets<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + KeeperStats::INIT_SPACE,
+        seeds = [KEEPER_STATS_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub keeper_stats: Account<'info, KeeperStats>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FinalizeEndAssets<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            matches!(
+                self.config.status,
+                ProgramStatus::Active | ProgramStatus::EmergencyPaused,
+            ),
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.config.keeper_authorities.contains(&self.signer.key()),
+            GoldRushError::UnauthorizedKeeper
+        );
+
+        require!(
+            self.round.status == RoundStatus::Active,
+            GoldRushError::InvalidRoundStatus
+        );
+        require!(
+            matches!(self.round.market_type, MarketType::AssetBattle),
+            GoldRushError::InvalidRoundMarketType
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= self.round.end_time,
+            GoldRushError::RoundNotReadyForSettlement
+        );
+
+        require!(
+            self.round.ranked_assets < self.round.total_assets,
+            GoldRushError::RoundAlreadyRankedAssets
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<FinalizeEndAssets>) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let remaining_accounts = &ctx.remaining_accounts;
+
+    // validate remaining accounts
+    require!(
+        !remaining_accounts.is_empty(),
+        GoldRushError::InvalidRemainingAccountsLength
+    );
+    require!(
+        remaining_accounts.len() <= MAX_REMAINING_ACCOUNTS,
+        GoldRushError::InvalidRemainingAccountsLength
+    );
+
+    let round = &mut ctx.accounts.round;
+
+    // Ranking is batched, so continue from the max/winners of previous batches
+    let mut max_growth = round.max_asset_growth_rate_e8;
+    let mut min_growth = round.min_asset_growth_rate_e8;
+    let mut winner_assets: Vec<Pubkey> = round.winner_assets.clone();
+    // Under Split every asset ranked so far is a winner while they all tie, even past the
+    // MAX_WINNER_ASSETS kept in winner_assets
+    let mut winner_count = if matches!(round.tie_break_rule, TieBreakRule::Split)
+        && max_growth.is_some()
+        && max_growth == min_growth
+    {
+        round.ranked_assets
+    } else {
+        winner_assets.len() as u64
+    };
+
+    for asset_ai in remaining_accounts.iter() {
+        // Ownership must be our program (Asset PDA)
+        require_keys_eq!(
+            *asset_ai.owner,
+            *ctx.program_id,
+            GoldRushError::InvalidAssetAccount
+        );
+
+        // Borrow and deserialize Asset
+        let mut asset_data = asset_ai.try_borrow_mut_data()?;
+        let mut asset: Asset = Asset::try_deserialize(&mut &asset_data[..])
+            .map_err(|_| GoldRushError::InvalidAssetAccountData)?;
+
+        // Validate asset PDA (seeded by its own group)
+        let expected_asset_pda = Pubkey::find_program_address(
+            &[
+                ASSET_SEED.as_bytes(),
+                asset.group.as_ref(),
+                &asset.id.to_le_bytes(),
+            ],
+            ctx.program_id,
+        )
+        .0;
+        require_keys_eq!(
+            *asset_ai.key,
+            expected_asset_pda,
+            GoldRushError::InvalidAssetAccount
+        );
+        require_keys_eq!(asset.round, round.key(), GoldRushError::InvalidAssetAccount);
+
+        // Each asset is ranked exactly once
+        require!(!asset.ranked, GoldRushError::AssetAlreadyRanked);
+
+        // Growth must be computed by finalize_end_group_asset
+        let growth = asset
+            .growth_rate_e8
+            .ok_or(GoldRushError::AssetNotFinalizedEndPrice)?;
+
+        match max_growth {
+            None => {
+                max_growth = Some(growth);
+                winner_assets.clear();
+                winner_assets.push(asset_ai.key());
+                winner_count = 1;
+            }
+            Some(current_max) => {
+                if growth > current_max {
+                    max_growth = Some(growth);
+                    winner_assets.clear();
+                    winner_assets.push(asset_ai.key());
+                    winner_count = 1;
+                } else if growth == current_max {
+                    match round.tie_break_rule {
+                        TieBreakRule::Split => {
+                            if winner_assets.len() < MAX_WINNER_ASSETS {
+                                winner_assets.push(asset_ai.key());
+                            }
+                            winner_count =
+                                winner_count.checked_add(1).ok_or(GoldRushError::Overflow)?;
+                        }
+                        TieBreakRule::LowestId => {
+                            if asset_ai.key() < winner_assets[0] {
+                                winner_assets[0] = asset_ai.key();
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Track the lowest growth so an all-tie round can settle as a full draw
+        min_growth = Some(min_growth.map_or(growth, |current_min| current_min.min(growth)));
+
+        // Update asset
+        asset.ranked = true;
+
+        // Serialize back
+        let serialized = asset
+            .try_to_vec()
+            .map_err(|_| GoldRushError::SerializeError)?;
+        if serialized.len() > asset_data[8..].len() {
+            return Err(GoldRushError::AccountDataTooSmall.into());
+        }
+        asset_data[8..8 + serialized.len()].copy_from_slice(&serialized);
+    }
+
+    // winners not exceed limit, unless every asset tied: the round is then a full draw and
+    // settlement does not look the winners up
+    require!(
+        winner_count <= MAX_WINNER_ASSETS as u64 || max_growth == min_growth,
+        GoldRushError::MaxWinnerAssetsExceeded
+    );
+
+    // Set round fields
+    round.max_asset_growth_rate_e8 = max_growth;
+    round.min_asset_growth_rate_e8 = min_growth;
+    round.winner_assets = winner_assets;
+    round.ranked_assets = round
+        .ranked_assets
+        .checked_add(ctx.remaining_accounts.len() as u64)
+        .ok_or(GoldRushError::Overflow)?;
+
+    // accrue keeper bounty
+    accrue_keeper_bounty(
+        &mut ctx.accounts.keeper_stats,
+        ctx.accounts.signer.key(),
+        ctx.bumps.keeper_stats,
+        ctx.remaining_accounts.len() as u64,
+        ctx.accounts.config.keeper_bounty_per_unit,
+    )?;
+
+    Ok(())
+}
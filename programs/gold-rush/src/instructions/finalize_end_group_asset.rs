@@ -1,4 +1,4 @@
-use crate::{constants::*, error::GoldRushError, state::*};
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_lang::AccountDeserialize;
 
@@ -13,6 +13,15 @@ pub struct FinalizeEndGroupAsset<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + KeeperStats::INIT_SPACE,
+        seeds = [KEEPER_STATS_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub keeper_stats: Account<'info, KeeperStats>,
+
     #[account(
         seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
         bump
@@ -49,7 +58,10 @@ impl<'info> FinalizeEndGroupAsset<'info> {
             GoldRushError::InvalidRoundStatus
         );
         require!(
-            matches!(self.round.market_type, MarketType::GroupBattle),
+            matches!(
+                self.round.market_type,
+                MarketType::GroupBattle | MarketType::AssetBattle
+            ),
             GoldRushError::InvalidRoundMarketType
         );
         require!(
@@ -92,6 +104,8 @@ pub fn handler(ctx: Context<FinalizeEndGroupAsset>) -> Result<()> {
 
     let now = Clock::get()?.unix_timestamp;
 
+    // Only assets finalized here earn the keeper bounty
+    let mut finalized_assets = 0u64;
     for asset_ai in remaining_accounts.iter() {
         // Ownership must be our program (Asset PDA)
         require_keys_eq!(
@@ -127,6 +141,11 @@ pub fn handler(ctx: Context<FinalizeEndGroupAsset>) -> Result<()> {
         );
         require_keys_eq!(asset.round, round.key(), GoldRushError::InvalidAssetAccount);
 
+        // Already finalized assets are skipped so they are not aggregated twice
+        if asset.finalized_at.is_some() {
+            continue;
+        }
+
         // Start price must be set
         let start_price = asset.start_price.ok_or(GoldRushError::InvalidAssetPrice)?;
         require!(start_price > 0, GoldRushError::InvalidAssetPrice);
@@ -135,21 +154,12 @@ pub fn handler(ctx: Context<FinalizeEndGroupAsset>) -> Result<()> {
         let final_price = asset.final_price.ok_or(GoldRushError::InvalidAssetPrice)?;
         require!(final_price > 0, GoldRushError::InvalidAssetPrice);
 
-        // Compute signed growth bps with wide arithmetic
-        let numerator: i128 = (final_price as i128)
-            .checked_sub(start_price as i128)
-            .ok_or(GoldRushError::Overflow)?;
-        let growth_rate_bps_i128 = numerator
-            .checked_mul(10_000)
-            .ok_or(GoldRushError::Overflow)?
-            .checked_div(start_price as i128)
-            .ok_or(GoldRushError::Underflow)?;
-        let growth_rate_bps: i64 =
-            i64::try_from(growth_rate_bps_i128).map_err(|_| GoldRushError::Overflow)?;
+        // Compute signed growth (1e-8 units)
+        let growth_rate_e8 = calculate_growth_rate_e8(start_price, final_price)?;
 
         // Update asset
         asset.final_price = Some(final_price);
-        asset.growth_rate_bps = Some(growth_rate_bps);
+        asset.growth_rate_e8 = Some(growth_rate_e8);
         asset.finalized_at = Some(now);
 
         // Serialize back
@@ -160,29 +170,54 @@ pub fn handler(ctx: Context<FinalizeEndGroupAsset>) -> Result<()> {
             return Err(GoldRushError::AccountDataTooSmall.into());
         }
         asset_data[8..8 + serialized.len()].copy_from_slice(&serialized);
+        group_asset.total_start_price = group_asset
+            .total_start_price
+            .checked_add(start_price)
+            .ok_or(GoldRushError::Overflow)?;
         group_asset.total_final_price = group_asset
             .total_final_price
             .checked_add(final_price)
             .ok_or(GoldRushError::Overflow)?;
-        group_asset.total_growth_rate_bps = group_asset
-            .total_growth_rate_bps
-            .checked_add(growth_rate_bps)
+        group_asset.total_growth_rate_e8 = group_asset
+            .total_growth_rate_e8
+            .checked_add(growth_rate_e8)
+            .ok_or(GoldRushError::Overflow)?;
+        group_asset.total_weighted_growth_rate_e8 = growth_rate_e8
+            .checked_mul(asset.weight_bps as i64)
+            .and_then(|weighted| {
+                group_asset
+                    .total_weighted_growth_rate_e8
+                    .checked_add(weighted)
+            })
             .ok_or(GoldRushError::Overflow)?;
+        require!(
+            group_asset.growth_rates_e8.len() < MAX_ASSETS_IN_GROUP,
+            GoldRushError::MaxAssetsReached
+        );
+        group_asset.growth_rates_e8.push(growth_rate_e8);
         group_asset.finalized_end_price_assets = group_asset
             .finalized_end_price_assets
             .checked_add(1)
             .ok_or(GoldRushError::Overflow)?;
+        finalized_assets = finalized_assets
+            .checked_add(1)
+            .ok_or(GoldRushError::Overflow)?;
     }
 
-    // Set group asset fields
+    // Set group asset fields (aggregated per the round's aggregation mode)
     if group_asset.finalized_end_price_assets >= group_asset.total_assets {
-        group_asset.avg_growth_rate_bps = Some(
-            group_asset
-                .total_growth_rate_bps
-                .checked_div(group_asset.finalized_end_price_assets as i64)
-                .ok_or(GoldRushError::Overflow)?,
-        );
+        let avg_growth_rate_e8 = calculate_group_growth_rate_e8(&round.aggregation, group_asset)?;
+        group_asset.avg_growth_rate_e8 = Some(avg_growth_rate_e8);
     }
 
+    // accrue keeper bounty
+    accrue_keeper_bounty(
+        &mut ctx.accounts.keeper_stats,
+        ctx.accounts.signer.key(),
+        ctx.bumps.keeper_stats,
+        finalized_assets,
+        ctx.accounts.config.keeper_bounty_per_unit,
+    )?;
+
     Ok(())
 }
@@ -1,4 +1,4 @@
-use crate::{constants::*, error::GoldRushError, state::*};
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_lang::AccountDeserialize;
 
@@ -13,6 +13,15 @@ pub struct FinalizeEndGroups<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + KeeperStats::INIT_SPACE,
+        seeds = [KEEPER_STATS_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub keeper_stats: Account<'info, KeeperStats>,
+
     #[account(
         mut,
         seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
@@ -86,6 +95,9 @@ pub fn handler(ctx: Context<FinalizeEndGroups>) -> Result<()> {
 
     let mut max_avg: Option<i64> = None;
     let mut winner_ids: Vec<u64> = Vec::new();
+    let mut min_avg: Option<i64> = None;
+    let mut loser_ids: Vec<u64> = Vec::new();
+    let mut finalized_ids: Vec<u64> = Vec::new();
 
     for acc_info in remaining_accounts.iter() {
         // Ownership must be our program (GroupAsset PDA)
@@ -128,9 +140,15 @@ pub fn handler(ctx: Context<FinalizeEndGroups>) -> Result<()> {
             GoldRushError::GroupAssetNotFullyCapturedEndPrice
         );
         let avg = group_asset
-            .avg_growth_rate_bps
+            .avg_growth_rate_e8
             .ok_or(GoldRushError::GroupAssetNotFullyCapturedEndPrice)?;
 
+        // A group passed twice is only ranked (and paid for) once
+        if finalized_ids.contains(&group_asset.id) {
+            continue;
+        }
+        finalized_ids.push(group_asset.id);
+
         match max_avg {
             None => {
                 max_avg = Some(avg);
@@ -147,20 +165,68 @@ pub fn handler(ctx: Context<FinalizeEndGroups>) -> Result<()> {
                 }
             }
         }
+
+        match min_avg {
+            None => {
+                min_avg = Some(avg);
+                loser_ids.clear();
+                loser_ids.push(group_asset.id);
+            }
+            Some(current_min) => {
+                if avg < current_min {
+                    min_avg = Some(avg);
+                    loser_ids.clear();
+                    loser_ids.push(group_asset.id);
+                } else if avg == current_min {
+                    loser_ids.push(group_asset.id);
+                }
+            }
+        }
     }
 
+    // Resolve ties; if every group tied they all stay winners (and losers) for a full draw.
+    // Settlement only needs to see the same ids on both sides then, so the lists are
+    // capped instead of failing on more tied groups than they can hold
+    let all_tied = max_avg == min_avg;
+    let (winner_ids, loser_ids) = if all_tied {
+        let mut tied_ids = winner_ids;
+        tied_ids.truncate(MAX_WINNER_GROUP_IDS.min(MAX_LOSER_GROUP_IDS));
+        (tied_ids.clone(), tied_ids)
+    } else {
+        (
+            resolve_tied_group_ids(winner_ids, &round.tie_break_rule, all_tied),
+            resolve_tied_group_ids(loser_ids, &round.tie_break_rule, all_tied),
+        )
+    };
+
     // winners not exceed limit
     require!(
         winner_ids.len() <= MAX_WINNER_GROUP_IDS,
         GoldRushError::MaxWinnerGroupIdsExceeded
     );
 
+    // losers not exceed limit
+    require!(
+        loser_ids.len() <= MAX_LOSER_GROUP_IDS,
+        GoldRushError::MaxLoserGroupIdsExceeded
+    );
+
     // Set round fields
     round.winner_group_ids = winner_ids;
+    round.loser_group_ids = loser_ids;
     round.captured_end_groups = round
         .captured_end_groups
-        .checked_add(ctx.remaining_accounts.len() as u64)
+        .checked_add(finalized_ids.len() as u64)
         .ok_or(GoldRushError::Overflow)?;
 
+    // accrue keeper bounty
+    accrue_keeper_bounty(
+        &mut ctx.accounts.keeper_stats,
+        ctx.accounts.signer.key(),
+        ctx.bumps.keeper_stats,
+        finalized_ids.len() as u64,
+        ctx.accounts.config.keeper_bounty_per_unit,
+    )?;
+
     Ok(())
 }
@@ -1,4 +1,4 @@
-use crate::{constants::*, error::GoldRushError, state::*};
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_lang::AccountDeserialize;
 
@@ -13,6 +13,15 @@ pub struct FinalizeStartGroupAsset<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + KeeperStats::INIT_SPACE,
+        seeds = [KEEPER_STATS_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub keeper_stats: Account<'info, KeeperStats>,
+
     #[account(
         seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
         bump
@@ -49,7 +58,10 @@ impl<'info> FinalizeStartGroupAsset<'info> {
             GoldRushError::InvalidRoundStatus
         );
         require!(
-            self.round.market_type == MarketType::GroupBattle,
+            matches!(
+                self.round.market_type,
+                MarketType::GroupBattle | MarketType::AssetBattle
+            ),
             GoldRushError::InvalidRoundMarketType
         );
 
@@ -126,11 +138,25 @@ pub fn handler(ctx: Context<FinalizeStartGroupAsset>) -> Result<()> {
         require!(start_price > 0, GoldRushError::AssetStartPriceNotSet);
     }
 
-    // Set group asset fields
+    // Set group asset fields; only assets still to finalize count (and earn the keeper bounty)
+    let finalized_assets = (ctx.remaining_accounts.len() as u64).min(
+        group_asset
+            .total_assets
+            .saturating_sub(group_asset.finalized_start_price_assets),
+    );
     group_asset.finalized_start_price_assets = group_asset
         .finalized_start_price_assets
-        .checked_add(ctx.remaining_accounts.len() as u64)
+        .checked_add(finalized_assets)
         .ok_or(GoldRushError::Overflow)?;
 
+    // accrue keeper bounty
+    accrue_keeper_bounty(
+        &mut ctx.accounts.keeper_stats,
+        ctx.accounts.signer.key(),
+        ctx.bumps.keeper_stats,
+        finalized_assets,
+        ctx.accounts.config.keeper_bounty_per_unit,
+    )?;
+
     Ok(())
 }
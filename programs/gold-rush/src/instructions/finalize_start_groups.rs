@@ -1,4 +1,4 @@
-use crate::{constants::*, error::GoldRushError, state::*};
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_lang::AccountDeserialize;
 
@@ -13,6 +13,15 @@ pub struct FinalizeStartGroups<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + KeeperStats::INIT_SPACE,
+        seeds = [KEEPER_STATS_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub keeper_stats: Account<'info, KeeperStats>,
+
     #[account(
         mut,
         seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
@@ -43,7 +52,10 @@ impl<'info> FinalizeStartGroups<'info> {
             GoldRushError::InvalidRoundStatus
         );
         require!(
-            self.round.market_type == MarketType::GroupBattle,
+            matches!(
+                self.round.market_type,
+                MarketType::GroupBattle | MarketType::AssetBattle
+            ),
             GoldRushError::InvalidRoundMarketType
         );
 
@@ -112,13 +124,35 @@ pub fn handler(ctx: Context<FinalizeStartGroups>) -> Result<()> {
             group_asset.finalized_start_price_assets >= group_asset.total_assets,
             GoldRushError::GroupAssetNotFullyCapturedStartPrice
         );
+
+        // Weighted groups must be fully allocated before the round starts
+        if group_asset.total_weight_bps > 0 {
+            require!(
+                group_asset.total_weight_bps == HUNDRED_PERCENT_BPS as u64,
+                GoldRushError::GroupAssetWeightsIncomplete
+            );
+        }
     }
 
-    // Set round fields
+    // Set round fields; only groups still to finalize count (and earn the keeper bounty)
+    let captured_groups = (ctx.remaining_accounts.len() as u64).min(
+        round
+            .total_groups
+            .saturating_sub(round.captured_start_groups),
+    );
     round.captured_start_groups = round
         .captured_start_groups
-        .checked_add(ctx.remaining_accounts.len() as u64)
+        .checked_add(captured_groups)
         .ok_or(GoldRushError::Overflow)?;
 
+    // accrue keeper bounty
+    accrue_keeper_bounty(
+        &mut ctx.accounts.keeper_stats,
+        ctx.accounts.signer.key(),
+        ctx.bumps.keeper_stats,
+        captured_groups,
+        ctx.accounts.config.keeper_bounty_per_unit,
+    )?;
+
     Ok(())
 }
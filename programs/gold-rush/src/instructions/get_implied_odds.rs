@@ -0,0 +1,115 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+/// Implied payout multipliers (in bps, 10000 = 1x) returned to the caller.
+/// They are amount-based and ignore time/direction factors, so they are an estimate.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ImpliedOdds {
+    pub total_pool: u64,
+    pub reward_pool: u64,
+    pub direction_totals: DirectionTotals,
+    pub direction_multipliers_bps: [u64; DIRECTION_BUCKETS], // SingleAsset only
+    pub group_multiplier_bps: u64, // GroupBattle only: payout if the group wins
+    pub underperform_multiplier_bps: u64, // GroupBattle only: payout if the group ends last
+}
+
+#[derive(Accounts)]
+pub struct GetImpliedOdds<'info> {
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    // Optional: per-group odds for GroupBattle rounds
+    pub group_asset: Option<Account<'info, GroupAsset>>,
+}
+
+impl<'info> GetImpliedOdds<'info> {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(ga) = self.group_asset.as_ref() {
+            require_keys_eq!(
+                ga.round,
+                self.round.key(),
+                GoldRushError::InvalidGroupAssetAccount
+            );
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<GetImpliedOdds>) -> Result<ImpliedOdds> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let config = &ctx.accounts.config;
+    let round = &ctx.accounts.round;
+
+    // Reward pool after the fee settlement would take
+    let fee_bps = match round.market_type {
+        MarketType::SingleAsset => config.fee_single_asset_bps,
+        MarketType::GroupBattle | MarketType::AssetBattle => config.fee_group_battle_bps,
+    };
+    let fee_amount = round
+        .total_pool
+        .checked_mul(fee_bps as u64)
+        .and_then(|x| x.checked_div(HUNDRED_PERCENT_BPS as u64))
+        .ok_or(GoldRushError::Overflow)?;
+    let reward_pool = round
+        .total_pool
+        .checked_sub(fee_amount)
+        .ok_or(GoldRushError::Underflow)?;
+
+    let mut odds = ImpliedOdds {
+        total_pool: round.total_pool,
+        reward_pool,
+        direction_totals: round.direction_totals.clone(),
+        direction_multipliers_bps: [0; DIRECTION_BUCKETS],
+        group_multiplier_bps: 0,
+        underperform_multiplier_bps: 0,
+    };
+
+    match round.market_type {
+        MarketType::SingleAsset => {
+            // Up and PercentageUp share the up side (likewise for down); a flat move is a draw
+            let up_multiplier_bps = calculate_implied_multiplier_bps(
+                reward_pool,
+                calculate_side_amount(&round.direction_totals, true)?,
+            )?;
+            let down_multiplier_bps = calculate_implied_multiplier_bps(
+                reward_pool,
+                calculate_side_amount(&round.direction_totals, false)?,
+            )?;
+            odds.direction_multipliers_bps[0] = up_multiplier_bps;
+            odds.direction_multipliers_bps[1] = down_multiplier_bps;
+            odds.direction_multipliers_bps[2] = up_multiplier_bps;
+            odds.direction_multipliers_bps[3] = down_multiplier_bps;
+        }
+        MarketType::GroupBattle => {
+            if let Some(group_asset) = ctx.accounts.group_asset.as_ref() {
+                // Everything except Underperform wins when the group wins
+                let group_amount = group_asset.direction_totals.amounts[..DIRECTION_BUCKETS - 1]
+                    .iter()
+                    .try_fold(0u64, |acc, x| acc.checked_add(*x))
+                    .ok_or(GoldRushError::Overflow)?;
+                odds.group_multiplier_bps =
+                    calculate_implied_multiplier_bps(reward_pool, group_amount)?;
+                odds.underperform_multiplier_bps = calculate_implied_multiplier_bps(
+                    reward_pool,
+                    group_asset.direction_totals.amounts[DIRECTION_BUCKETS - 1],
+                )?;
+            }
+        }
+        // Stakes are not tracked per asset, so there is nothing to quote
+        MarketType::AssetBattle => return err!(GoldRushError::InvalidRoundMarketType),
+    }
+
+    Ok(odds)
+}
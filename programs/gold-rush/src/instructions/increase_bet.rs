@@ -0,0 +1,253 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct IncreaseBet<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [BET_SEED.as_bytes(), round.key().as_ref(), &bet.id.to_le_bytes()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+
+    // Optional: only required for GroupBattle bets
+    #[account(mut)]
+    pub group_asset: Option<Account<'info, GroupAsset>>,
+
+    #[account(
+        mut,
+        seeds = [USER_ROUND_POSITION_SEED.as_bytes(), round.key().as_ref(), signer.key().as_ref()],
+        bump = user_round_position.bump
+    )]
+    pub user_round_position: Account<'info, UserRoundPosition>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes(), round.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> IncreaseBet<'info> {
+    pub fn validate(&self, amount: u64) -> Result<()> {
+        require!(
+            self.config.status == ProgramStatus::Active,
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.round.status == RoundStatus::Active,
+            GoldRushError::RoundNotActive
+        );
+
+        // Same cutoff as place_bet
+        require!(
+            Clock::get()?.unix_timestamp < self.round.bet_cutoff_time,
+            GoldRushError::RoundEnded
+        );
+
+        require_keys_eq!(
+            self.bet.bettor,
+            self.signer.key(),
+            GoldRushError::Unauthorized
+        );
+
+        require_keys_eq!(
+            self.bet.round,
+            self.round.key(),
+            GoldRushError::InvalidBetAccount
+        );
+
+        // Promotional bets can only be withdrawn or settled
+        require!(
+            !self.bet.is_promotional,
+            GoldRushError::PromotionalBetNotAllowed
+        );
+
+        require!(
+            self.bet.status == BetStatus::Pending,
+            GoldRushError::InvalidBetStatus
+        );
+
+        require!(amount > 0, GoldRushError::InvalidBetAmount);
+
+        // Exposure caps (0 = no cap)
+        if self.config.max_bet_amount > 0 {
+            let new_amount = self
+                .bet
+                .amount
+                .checked_add(amount)
+                .ok_or(GoldRushError::Overflow)?;
+            require!(
+                new_amount <= self.config.max_bet_amount,
+                GoldRushError::BetAboveMaximum
+            );
+        }
+
+        if self.config.max_user_stake_per_round > 0 {
+            let user_stake = self
+                .user_round_position
+                .total_stake
+                .checked_add(amount)
+                .ok_or(GoldRushError::Overflow)?;
+            require!(
+                user_stake <= self.config.max_user_stake_per_round,
+                GoldRushError::UserStakeCapExceeded
+            );
+        }
+
+        if self.round.max_total_pool > 0 {
+            let total_pool = self
+                .round
+                .total_pool
+                .checked_add(amount)
+                .ok_or(GoldRushError::Overflow)?;
+            require!(
+                total_pool <= self.round.max_total_pool,
+                GoldRushError::RoundPoolCapExceeded
+            );
+        }
+
+        // Group bets keep the group's running totals in sync
+        if let Some(group) = self.bet.group {
+            let ga = self
+                .group_asset
+                .as_ref()
+                .ok_or(GoldRushError::InvalidGroupAssetAccount)?;
+            require_keys_eq!(ga.key(), group, GoldRushError::InvalidGroupAssetAccount);
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<IncreaseBet>, amount: u64) -> Result<()> {
+    // validate
+    ctx.accounts.validate(amount)?;
+
+    // transfer from signer to vault
+    let transfer_accounts = Transfer {
+        from: ctx.accounts.token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+    );
+    transfer(transfer_ctx, amount)?;
+
+    let config = &ctx.accounts.config;
+    let round = &mut ctx.accounts.round;
+    let bet = &mut ctx.accounts.bet;
+    let user_round_position = &mut ctx.accounts.user_round_position;
+
+    // calculate weight of the top-up with the time factor at top-up time
+    let added_weight = calculate_round_bet_weight(
+        config,
+        round,
+        &bet.direction,
+        amount,
+        Clock::get()?.unix_timestamp,
+    )?
+    .weight;
+
+    // The top-up keeps the fee discount snapshotted when the bet was placed
+    let previous_discounted_stake = calculate_discounted_stake(bet.amount, bet.fee_discount_bps)?;
+
+    // set bet fields
+    bet.amount = bet
+        .amount
+        .checked_add(amount)
+        .ok_or(GoldRushError::Overflow)?;
+    bet.weight = bet
+        .weight
+        .checked_add(added_weight)
+        .ok_or(GoldRushError::Overflow)?;
+
+    // set user round position fields
+    user_round_position.total_stake = user_round_position
+        .total_stake
+        .checked_add(amount)
+        .ok_or(GoldRushError::Overflow)?;
+    user_round_position.total_weight = user_round_position
+        .total_weight
+        .checked_add(added_weight)
+        .ok_or(GoldRushError::Overflow)?;
+
+    // set round fields
+    round.total_pool = round
+        .total_pool
+        .checked_add(amount)
+        .ok_or(GoldRushError::Overflow)?;
+    round.total_stake = round
+        .total_stake
+        .checked_add(amount)
+        .ok_or(GoldRushError::Overflow)?;
+    if bet.referrer.is_some() {
+        round.referred_stake = round
+            .referred_stake
+            .checked_add(amount)
+            .ok_or(GoldRushError::Overflow)?;
+    }
+    let added_discounted_stake = calculate_discounted_stake(bet.amount, bet.fee_discount_bps)?
+        .checked_sub(previous_discounted_stake)
+        .ok_or(GoldRushError::Underflow)?;
+    round.discounted_stake = round
+        .discounted_stake
+        .checked_add(added_discounted_stake)
+        .ok_or(GoldRushError::Overflow)?;
+    add_to_direction_totals(
+        &mut round.direction_totals,
+        &bet.direction,
+        amount,
+        added_weight,
+    )?;
+
+    // set group asset fields
+    if bet.group.is_some() {
+        let group_asset = ctx
+            .accounts
+            .group_asset
+            .as_mut()
+            .ok_or(GoldRushError::InvalidGroupAssetAccount)?;
+        add_to_direction_totals(
+            &mut group_asset.direction_totals,
+            &bet.direction,
+            amount,
+            added_weight,
+        )?;
+    }
+
+    Ok(())
+}
@@ -124,7 +124,33 @@ pub fn handler(
     config.min_time_factor_bps = min_time_factor_bps;
     config.max_time_factor_bps = max_time_factor_bps;
     config.default_direction_factor_bps = default_direction_factor_bps;
+    config.tie_break_rule = TieBreakRule::Split;
+    config.draw_band_bps = 0;
+    config.claim_window_secs = 0;
+    config.loss_rebate_bps = 0;
+    config.early_exit_fee_bps = 0;
+    config.cash_out_haircut_bps = 0;
+    config.jackpot_fee_bps = 0;
+    config.referral_fee_bps = 0;
+    config.fee_schedule = FeeSchedule {
+        treasury_bps: HUNDRED_PERCENT_BPS,
+        keeper_pool_bps: 0,
+        burn_bps: 0,
+    };
+    config.keeper_reward_pool = Pubkey::default();
+    config.max_bet_amount = 0;
+    config.max_user_stake_per_round = 0;
+    config.max_total_pool = 0;
     config.status = ProgramStatus::Active;
+    config.jackpot_enabled = false;
+    config.keeper_bounty_per_unit = 0;
+    config.keeper_rewards_enabled = false;
+    config.referrals_enabled = false;
+    config.fee_discount_tiers = vec![];
+    config.unstake_cooldown_secs = 0;
+    config.staking_enabled = false;
+    config.current_voucher_counter = 0;
+    config.promotions_enabled = false;
     config.bump = ctx.bumps.config;
 
     Ok(())
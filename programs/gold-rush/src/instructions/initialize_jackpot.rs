@@ -0,0 +1,82 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct InitializeJackpot<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + Jackpot::INIT_SPACE,
+        seeds = [JACKPOT_SEED.as_bytes()],
+        bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = jackpot,
+        seeds = [JACKPOT_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub jackpot_vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> InitializeJackpot<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.signer.key() == self.config.admin,
+            GoldRushError::Unauthorized
+        );
+
+        require!(
+            !self.config.jackpot_enabled,
+            GoldRushError::JackpotAlreadyInitialized
+        );
+
+        require_keys_eq!(
+            self.mint.key(),
+            self.config.token_mint,
+            GoldRushError::InvalidMint
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<InitializeJackpot>) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let jackpot = &mut ctx.accounts.jackpot;
+
+    // set fields
+    jackpot.vault = ctx.accounts.jackpot_vault.key();
+    jackpot.vault_bump = ctx.bumps.jackpot_vault;
+    jackpot.total_funded = 0;
+    jackpot.total_paid_out = 0;
+    jackpot.created_at = Clock::get()?.unix_timestamp;
+    jackpot.bump = ctx.bumps.jackpot;
+
+    // set config fields
+    ctx.accounts.config.jackpot_enabled = true;
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct InitializeKeeperRewards<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + KeeperRewards::INIT_SPACE,
+        seeds = [KEEPER_REWARDS_SEED.as_bytes()],
+        bump
+    )]
+    pub keeper_rewards: Account<'info, KeeperRewards>,
+
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = keeper_rewards,
+        seeds = [KEEPER_REWARD_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub keeper_reward_vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> InitializeKeeperRewards<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.signer.key() == self.config.admin,
+            GoldRushError::Unauthorized
+        );
+
+        require!(
+            !self.config.keeper_rewards_enabled,
+            GoldRushError::KeeperRewardsAlreadyInitialized
+        );
+
+        require_keys_eq!(
+            self.mint.key(),
+            self.config.token_mint,
+            GoldRushError::InvalidMint
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<InitializeKeeperRewards>) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let keeper_rewards = &mut ctx.accounts.keeper_rewards;
+
+    // set fields
+    keeper_rewards.vault = ctx.accounts.keeper_reward_vault.key();
+    keeper_rewards.vault_bump = ctx.bumps.keeper_reward_vault;
+    keeper_rewards.total_paid_out = 0;
+    keeper_rewards.created_at = Clock::get()?.unix_timestamp;
+    keeper_rewards.bump = ctx.bumps.keeper_rewards;
+
+    // set config fields; the keeper pool leg of fees now funds the vault
+    let config = &mut ctx.accounts.config;
+    config.keeper_reward_pool = keeper_rewards.vault;
+    config.keeper_rewards_enabled = true;
+
+    Ok(())
+}
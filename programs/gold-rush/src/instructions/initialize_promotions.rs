@@ -0,0 +1,63 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct InitializePromotions<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = config,
+        seeds = [PROMO_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub promo_vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> InitializePromotions<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.signer.key() == self.config.admin,
+            GoldRushError::Unauthorized
+        );
+
+        require!(
+            !self.config.promotions_enabled,
+            GoldRushError::PromotionsAlreadyInitialized
+        );
+
+        require_keys_eq!(
+            self.mint.key(),
+            self.config.token_mint,
+            GoldRushError::InvalidMint
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<InitializePromotions>) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    // set config fields
+    ctx.accounts.config.promotions_enabled = true;
+
+    Ok(())
+}
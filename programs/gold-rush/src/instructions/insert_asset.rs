@@ -14,6 +14,7 @@ pub struct InsertAsset<'info> {
     pub config: Account<'info, Config>,
 
     #[account(
+        mut,
         seeds = [ROUND_SEED.as_bytes(),&round.id.to_le_bytes()],
         bump,
     )]
@@ -42,7 +43,7 @@ pub struct InsertAsset<'info> {
 }
 
 impl<'info> InsertAsset<'info> {
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, weight_bps: u16) -> Result<()> {
         require!(
             matches!(
                 self.config.status,
@@ -56,7 +57,10 @@ impl<'info> InsertAsset<'info> {
             GoldRushError::InvalidRoundStatus
         );
         require!(
-            matches!(self.round.market_type, MarketType::GroupBattle),
+            matches!(
+                self.round.market_type,
+                MarketType::GroupBattle | MarketType::AssetBattle
+            ),
             GoldRushError::InvalidRoundStatus
         );
 
@@ -70,15 +74,32 @@ impl<'info> InsertAsset<'info> {
             GoldRushError::MaxAssetsReached
         );
 
+        // weights only apply to mean aggregation
+        require!(
+            weight_bps == 0 || self.round.aggregation == GroupAggregation::Mean,
+            GoldRushError::InvalidAssetWeight
+        );
+
+        // weights are optional (0 = unweighted), but a group never exceeds 100%
+        let total_weight_bps = self
+            .group_asset
+            .total_weight_bps
+            .checked_add(weight_bps as u64)
+            .ok_or(GoldRushError::Overflow)?;
+        require!(
+            total_weight_bps <= HUNDRED_PERCENT_BPS as u64,
+            GoldRushError::InvalidAssetWeight
+        );
+
         Ok(())
     }
 }
 
-pub fn handler(ctx: Context<InsertAsset>, symbol: [u8; 8]) -> Result<()> {
+pub fn handler(ctx: Context<InsertAsset>, symbol: [u8; 8], weight_bps: u16) -> Result<()> {
     // validate
-    ctx.accounts.validate()?;
+    ctx.accounts.validate(weight_bps)?;
 
-    let round = &ctx.accounts.round;
+    let round = &mut ctx.accounts.round;
     let group_asset = &mut ctx.accounts.group_asset;
     let asset = &mut ctx.accounts.asset;
 
@@ -91,6 +112,7 @@ pub fn handler(ctx: Context<InsertAsset>, symbol: [u8; 8]) -> Result<()> {
     asset.round = round.key();
     asset.feed_id = ctx.accounts.feed_price_account.price_message.feed_id;
     asset.symbol = symbol;
+    asset.weight_bps = weight_bps;
     asset.created_at = Clock::get()?.unix_timestamp;
     asset.bump = ctx.bumps.asset;
 
@@ -99,6 +121,16 @@ pub fn handler(ctx: Context<InsertAsset>, symbol: [u8; 8]) -> Result<()> {
         .total_assets
         .checked_add(1)
         .ok_or(GoldRushError::Overflow)?;
+    group_asset.total_weight_bps = group_asset
+        .total_weight_bps
+        .checked_add(weight_bps as u64)
+        .ok_or(GoldRushError::Overflow)?;
+
+    // set round fields
+    round.total_assets = round
+        .total_assets
+        .checked_add(1)
+        .ok_or(GoldRushError::Overflow)?;
 
     Ok(())
 }
@@ -46,7 +46,10 @@ impl<'info> InsertGroupAsset<'info> {
             GoldRushError::InvalidRoundStatus
         );
         require!(
-            self.round.market_type == MarketType::GroupBattle,
+            matches!(
+                self.round.market_type,
+                MarketType::GroupBattle | MarketType::AssetBattle
+            ),
             GoldRushError::InvalidRoundMarketType,
         );
 
@@ -0,0 +1,84 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MintVoucher<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + Voucher::INIT_SPACE,
+        seeds = [VOUCHER_SEED.as_bytes(), &(config.current_voucher_counter + 1).to_le_bytes()],
+        bump
+    )]
+    pub voucher: Account<'info, Voucher>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MintVoucher<'info> {
+    pub fn validate(&self, amount: u64, expires_at: i64) -> Result<()> {
+        require!(
+            self.config.status == ProgramStatus::Active,
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.signer.key() == self.config.admin,
+            GoldRushError::Unauthorized
+        );
+
+        require!(
+            self.config.promotions_enabled,
+            GoldRushError::PromotionsNotInitialized
+        );
+
+        require!(amount > 0, GoldRushError::InvalidVoucherAmount);
+
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            GoldRushError::InvalidVoucherExpiry
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<MintVoucher>,
+    user: Pubkey,
+    amount: u64,
+    expires_at: i64,
+) -> Result<()> {
+    // validate
+    ctx.accounts.validate(amount, expires_at)?;
+
+    let config = &mut ctx.accounts.config;
+    let voucher = &mut ctx.accounts.voucher;
+
+    // set voucher fields
+    voucher.id = config.current_voucher_counter + 1;
+    voucher.user = user;
+    voucher.amount = amount;
+    voucher.expires_at = expires_at;
+    voucher.redeemed = false;
+    voucher.created_at = Clock::get()?.unix_timestamp;
+    voucher.bump = ctx.bumps.voucher;
+
+    // set config fields
+    config.current_voucher_counter = config
+        .current_voucher_counter
+        .checked_add(1)
+        .ok_or(GoldRushError::Overflow)?;
+
+    Ok(())
+}
@@ -3,45 +3,91 @@
 pub mod cancel_round;
 pub mod capture_end_price;
 pub mod capture_start_price;
+pub mod cash_out_bet;
+pub mod claim_keeper_rewards;
+pub mod claim_position_rewards;
 pub mod claim_reward;
+pub mod claim_rewards_batch;
 pub mod create_round;
+pub mod distribute_rewards;
 pub mod emergency_pause;
 pub mod emergency_unpause;
+pub mod finalize_end_assets;
 pub mod finalize_end_group_asset;
 pub mod finalize_end_groups;
 pub mod finalize_start_group_asset;
 pub mod finalize_start_groups;
+pub mod get_implied_odds;
+pub mod increase_bet;
 pub mod initialize;
+pub mod initialize_jackpot;
+pub mod initialize_keeper_rewards;
+pub mod initialize_promotions;
+pub mod initialize_referrals;
+pub mod initialize_staking;
 pub mod insert_asset;
 pub mod insert_group_asset;
+pub mod mint_voucher;
 pub mod place_bet;
+pub mod place_bet_with_voucher;
+pub mod preview_bet;
+pub mod preview_reward;
 pub mod program_pause;
 pub mod program_unpause;
+pub mod register_referrer;
 pub mod settle_group_round;
 pub mod settle_single_round;
+pub mod stake;
 pub mod start_round;
+pub mod sweep_unclaimed_rewards;
+pub mod unstake;
 pub mod update_config;
 pub mod withdraw_bet;
+pub mod withdraw_referral_rewards;
+pub mod withdraw_stake;
 
 pub use cancel_round::*;
 pub use capture_end_price::*;
 pub use capture_start_price::*;
+pub use cash_out_bet::*;
+pub use claim_keeper_rewards::*;
+pub use claim_position_rewards::*;
 pub use claim_reward::*;
+pub use claim_rewards_batch::*;
 pub use create_round::*;
+pub use distribute_rewards::*;
 pub use emergency_pause::*;
 pub use emergency_unpause::*;
+pub use finalize_end_assets::*;
 pub use finalize_end_group_asset::*;
 pub use finalize_end_groups::*;
 pub use finalize_start_group_asset::*;
 pub use finalize_start_groups::*;
+pub use get_implied_odds::*;
+pub use increase_bet::*;
 pub use initialize::*;
+pub use initialize_jackpot::*;
+pub use initialize_keeper_rewards::*;
+pub use initialize_promotions::*;
+pub use initialize_referrals::*;
+pub use initialize_staking::*;
 pub use insert_asset::*;
 pub use insert_group_asset::*;
+pub use mint_voucher::*;
 pub use place_bet::*;
+pub use place_bet_with_voucher::*;
+pub use preview_bet::*;
+pub use preview_reward::*;
 pub use program_pause::*;
 pub use program_unpause::*;
+pub use register_referrer::*;
 pub use settle_group_round::*;
 pub use settle_single_round::*;
+pub use stake::*;
 pub use start_round::*;
+pub use sweep_unclaimed_rewards::*;
+pub use unstake::*;
 pub use update_config::*;
 pub use withdraw_bet::*;
+pub use withdraw_referral_rewards::*;
+pub use withdraw_stake::*;
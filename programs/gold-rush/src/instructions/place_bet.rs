@@ -21,8 +21,26 @@ pub struct PlaceBet<'info> {
     pub round: Account<'info, Round>,
 
     // Optional: only required for GroupBattle rounds
+    #[account(mut)]
     pub group_asset: Option<Account<'info, GroupAsset>>,
 
+    // Optional: only required for AssetBattle rounds
+    pub asset: Option<Account<'info, Asset>>,
+
+    // Optional: only required when a referrer is named
+    #[account(
+        seeds = [REFERRER_SEED.as_bytes(), referrer_account.authority.as_ref()],
+        bump = referrer_account.bump
+    )]
+    pub referrer_account: Option<Account<'info, Referrer>>,
+
+    // Optional: the bettor's stake, for a staking fee discount
+    #[account(
+        seeds = [STAKE_ACCOUNT_SEED.as_bytes(), stake_account.owner.as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Option<Account<'info, StakeAccount>>,
+
     #[account(
         init,
         payer = signer,
@@ -32,6 +50,15 @@ pub struct PlaceBet<'info> {
     )]
     pub bet: Account<'info, Bet>,
 
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = user_round_position_account_space(user_round_position)?,
+        seeds = [USER_ROUND_POSITION_SEED.as_bytes(), round.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub user_round_position: Account<'info, UserRoundPosition>,
+
     #[account(
         mut,
         seeds = [VAULT_SEED.as_bytes(), round.key().as_ref()],
@@ -53,48 +80,73 @@ pub struct PlaceBet<'info> {
 }
 
 impl<'info> PlaceBet<'info> {
-    pub fn validate(&self, amount: u64) -> Result<()> {
+    pub fn validate(
+        &self,
+        amount: u64,
+        direction: &BetDirection,
+        max_timestamp: Option<i64>,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
         require!(
             self.config.status == ProgramStatus::Active,
             GoldRushError::ProgramPaused
         );
 
-        require!(
-            self.round.status == RoundStatus::Active,
-            GoldRushError::RoundNotActive
-        );
+        validate_bet_round(
+            &self.round,
+            self.group_asset.as_ref(),
+            self.asset.as_ref(),
+            direction,
+        )?;
 
-        // Enforce bet cutoff rather than the exact end_time to prevent last-second betting
-        require!(
-            Clock::get()?.unix_timestamp < self.round.bet_cutoff_time,
-            GoldRushError::RoundEnded
-        );
+        validate_bet_amount(&self.config, &self.round, &self.user_round_position, amount)?;
 
-        // For GroupBattle, group_asset must be provided and belong to this round
-        if matches!(self.round.market_type, MarketType::GroupBattle) {
-            let ga = self
-                .group_asset
+        // The named referrer must be registered and cannot be the bettor
+        if let Some(referrer) = referrer {
+            require!(referrer != self.signer.key(), GoldRushError::SelfReferral);
+            let referrer_account = self
+                .referrer_account
                 .as_ref()
-                .ok_or(GoldRushError::InvalidGroupAssetAccount)?;
+                .ok_or(GoldRushError::InvalidReferrerAccount)?;
             require_keys_eq!(
-                ga.round,
-                self.round.key(),
-                GoldRushError::InvalidGroupAssetAccount
+                referrer_account.authority,
+                referrer,
+                GoldRushError::InvalidReferrerAccount
             );
         }
 
-        require!(
-            amount >= self.config.min_bet_amount,
-            GoldRushError::BetBelowMinimum
-        );
+        // Only the bettor's own stake counts towards a discount
+        if let Some(stake_account) = &self.stake_account {
+            require_keys_eq!(
+                stake_account.owner,
+                self.signer.key(),
+                GoldRushError::InvalidStakeAccount
+            );
+        }
+
+        // Reject a tx that lands after the caller's deadline
+        if let Some(max_timestamp) = max_timestamp {
+            require!(
+                Clock::get()?.unix_timestamp <= max_timestamp,
+                GoldRushError::BetDeadlineExceeded
+            );
+        }
 
         Ok(())
     }
 }
 
-pub fn handler(ctx: Context<PlaceBet>, amount: u64, direction: BetDirection) -> Result<()> {
+pub fn handler(
+    ctx: Context<PlaceBet>,
+    amount: u64,
+    direction: BetDirection,
+    min_weight: Option<u64>,
+    max_timestamp: Option<i64>,
+    referrer: Option<Pubkey>,
+) -> Result<()> {
     // validate
-    ctx.accounts.validate(amount)?;
+    ctx.accounts
+        .validate(amount, &direction, max_timestamp, referrer)?;
 
     // transfer from signer to vault
     let transfer_accounts = Transfer {
@@ -111,69 +163,60 @@ pub fn handler(ctx: Context<PlaceBet>, amount: u64, direction: BetDirection) ->
     let config = &ctx.accounts.config;
     let round = &mut ctx.accounts.round;
     let bet = &mut ctx.accounts.bet;
-
-    // Assign group only for GroupBattle
-    if matches!(round.market_type, MarketType::GroupBattle) {
-        let ga = ctx
-            .accounts
-            .group_asset
-            .as_ref()
-            .ok_or(GoldRushError::InvalidAssetAccount)?;
-        bet.group = Some(ga.key());
-    }
+    let user_round_position = &mut ctx.accounts.user_round_position;
+
+    // Assign the group for GroupBattle, the asset for AssetBattle
+    assign_bet_market(
+        bet,
+        round,
+        ctx.accounts.group_asset.as_ref(),
+        ctx.accounts.asset.as_ref(),
+    )?;
 
     // calculate bet weight
-    let round_duration = round
-        .end_time
-        .checked_sub(round.start_time)
-        .ok_or(GoldRushError::Underflow)?;
-    let now = Clock::get()?.unix_timestamp;
-    let time_elapsed = now
-        .checked_sub(round.start_time)
-        .ok_or(GoldRushError::Underflow)?;
-    let direction_factor = calculate_direction_factor(
-        &round.market_type,
-        &direction,
-        config.default_direction_factor_bps as u64,
-    )?;
-    let time_factor = calculate_time_factor(
-        &round.market_type,
-        time_elapsed,
-        config.min_time_factor_bps as u64,
-        config.max_time_factor_bps as u64,
-        round_duration,
-    )?;
-    let numerator = (amount as u128)
-        .checked_mul(direction_factor as u128)
-        .ok_or(GoldRushError::Overflow)?
-        .checked_mul(time_factor as u128)
-        .ok_or(GoldRushError::Overflow)?;
-    let denominator = (HUNDRED_PERCENT_BPS as u128)
-        .checked_mul(HUNDRED_PERCENT_BPS as u128)
-        .ok_or(GoldRushError::Overflow)?;
-    let weight = numerator
-        .checked_div(denominator)
-        .ok_or(GoldRushError::Underflow)? as u64;
+    let weight = calculate_placed_bet_weight(config, round, &direction, amount, min_weight)?;
+
+    // Snapshot the staking fee discount of the bettor's current stake
+    let fee_discount_bps = match &ctx.accounts.stake_account {
+        Some(stake_account) if config.staking_enabled => {
+            calculate_fee_discount_bps(&config.fee_discount_tiers, stake_account.staked_amount)
+        }
+        _ => 0,
+    };
 
     // set bet fields
     bet.id = round.total_bets + 1;
     bet.round = round.key();
     bet.bettor = ctx.accounts.signer.key();
     bet.amount = amount;
-    bet.direction = direction;
+    bet.direction = direction.clone();
     bet.weight = weight;
+    bet.referrer = referrer;
+    bet.fee_discount_bps = fee_discount_bps;
     bet.status = BetStatus::Pending;
     bet.created_at = Clock::get()?.unix_timestamp;
     bet.bump = ctx.bumps.bet;
 
-    // set round fields
-    round.total_pool = round
-        .total_pool
-        .checked_add(amount)
-        .ok_or(GoldRushError::Overflow)?;
-    round.total_bets = round
-        .total_bets
-        .checked_add(1)
+    // set user round position fields (first bet initializes it)
+    add_bet_to_position(
+        user_round_position,
+        bet,
+        ctx.bumps.user_round_position,
+        &ctx.accounts.signer,
+        &ctx.accounts.system_program,
+    )?;
+
+    // set round and group asset fields
+    add_bet_to_round(round, ctx.accounts.group_asset.as_mut(), bet)?;
+    if referrer.is_some() {
+        round.referred_stake = round
+            .referred_stake
+            .checked_add(amount)
+            .ok_or(GoldRushError::Overflow)?;
+    }
+    round.discounted_stake = round
+        .discounted_stake
+        .checked_add(calculate_discounted_stake(amount, fee_discount_bps)?)
         .ok_or(GoldRushError::Overflow)?;
 
     Ok(())
@@ -0,0 +1,204 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct PlaceBetWithVoucher<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    // Optional: only required for GroupBattle rounds
+    #[account(mut)]
+    pub group_asset: Option<Account<'info, GroupAsset>>,
+
+    // Optional: only required for AssetBattle rounds
+    pub asset: Option<Account<'info, Asset>>,
+
+    #[account(
+        mut,
+        seeds = [VOUCHER_SEED.as_bytes(), &voucher.id.to_le_bytes()],
+        bump = voucher.bump
+    )]
+    pub voucher: Account<'info, Voucher>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + Bet::INIT_SPACE,
+        seeds = [BET_SEED.as_bytes(), round.key().as_ref(), &(round.total_bets + 1).to_le_bytes()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = user_round_position_account_space(user_round_position)?,
+        seeds = [USER_ROUND_POSITION_SEED.as_bytes(), round.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub user_round_position: Account<'info, UserRoundPosition>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes(), round.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROMO_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub promo_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlaceBetWithVoucher<'info> {
+    pub fn validate(&self, direction: &BetDirection, max_timestamp: Option<i64>) -> Result<()> {
+        require!(
+            self.config.status == ProgramStatus::Active,
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.config.promotions_enabled,
+            GoldRushError::PromotionsNotInitialized
+        );
+
+        // The voucher is single-use and only redeemable by its user before expiry
+        require_keys_eq!(
+            self.voucher.user,
+            self.signer.key(),
+            GoldRushError::InvalidVoucher
+        );
+        require!(
+            !self.voucher.redeemed,
+            GoldRushError::VoucherAlreadyRedeemed
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.voucher.expires_at,
+            GoldRushError::VoucherExpired
+        );
+
+        require_keys_eq!(
+            self.promo_vault.mint,
+            self.vault.mint,
+            GoldRushError::InvalidMint
+        );
+
+        validate_bet_round(
+            &self.round,
+            self.group_asset.as_ref(),
+            self.asset.as_ref(),
+            direction,
+        )?;
+
+        // The voucher amount is the stake
+        validate_bet_amount(
+            &self.config,
+            &self.round,
+            &self.user_round_position,
+            self.voucher.amount,
+        )?;
+
+        // Reject a tx that lands after the caller's deadline
+        if let Some(max_timestamp) = max_timestamp {
+            require!(
+                Clock::get()?.unix_timestamp <= max_timestamp,
+                GoldRushError::BetDeadlineExceeded
+            );
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<PlaceBetWithVoucher>,
+    direction: BetDirection,
+    min_weight: Option<u64>,
+    max_timestamp: Option<i64>,
+) -> Result<()> {
+    // validate
+    ctx.accounts.validate(&direction, max_timestamp)?;
+
+    let amount = ctx.accounts.voucher.amount;
+
+    // transfer from promo vault to vault
+    let transfer_accounts = Transfer {
+        from: ctx.accounts.promo_vault.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    let seeds = &[CONFIG_SEED.as_bytes(), &[ctx.accounts.config.bump]];
+    let signer = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        signer,
+    );
+    transfer(transfer_ctx, amount)?;
+
+    let config = &ctx.accounts.config;
+    let round = &mut ctx.accounts.round;
+    let bet = &mut ctx.accounts.bet;
+    let user_round_position = &mut ctx.accounts.user_round_position;
+    let voucher = &mut ctx.accounts.voucher;
+
+    // Assign the group for GroupBattle, the asset for AssetBattle
+    assign_bet_market(
+        bet,
+        round,
+        ctx.accounts.group_asset.as_ref(),
+        ctx.accounts.asset.as_ref(),
+    )?;
+
+    // calculate bet weight
+    let weight = calculate_placed_bet_weight(config, round, &direction, amount, min_weight)?;
+
+    // set bet fields
+    bet.id = round.total_bets + 1;
+    bet.round = round.key();
+    bet.bettor = ctx.accounts.signer.key();
+    bet.amount = amount;
+    bet.direction = direction.clone();
+    bet.weight = weight;
+    bet.is_promotional = true;
+    bet.status = BetStatus::Pending;
+    bet.created_at = Clock::get()?.unix_timestamp;
+    bet.bump = ctx.bumps.bet;
+
+    // set voucher fields
+    voucher.redeemed = true;
+
+    // set user round position fields (first bet initializes it)
+    add_bet_to_position(
+        user_round_position,
+        bet,
+        ctx.bumps.user_round_position,
+        &ctx.accounts.signer,
+        &ctx.accounts.system_program,
+    )?;
+
+    // set round and group asset fields
+    add_bet_to_round(round, ctx.accounts.group_asset.as_mut(), bet)?;
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+/// Weight a bet would get if it landed now (same math as `place_bet`)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BetPreview {
+    pub direction_factor: u64,
+    pub time_factor: u64,
+    pub weight: u64,
+    pub timestamp: i64, // The clock the preview was computed at.
+}
+
+#[derive(Accounts)]
+pub struct PreviewBet<'info> {
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+}
+
+impl<'info> PreviewBet<'info> {
+    pub fn validate(&self, direction: &BetDirection) -> Result<()> {
+        require!(
+            self.round.status == RoundStatus::Active,
+            GoldRushError::RoundNotActive
+        );
+
+        require!(
+            Clock::get()?.unix_timestamp < self.round.bet_cutoff_time,
+            GoldRushError::RoundEnded
+        );
+
+        if !matches!(self.round.market_type, MarketType::GroupBattle) {
+            require!(
+                !matches!(direction, BetDirection::Underperform),
+                GoldRushError::InvalidBetDirection
+            );
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<PreviewBet>,
+    amount: u64,
+    direction: BetDirection,
+) -> Result<BetPreview> {
+    // validate
+    ctx.accounts.validate(&direction)?;
+
+    let config = &ctx.accounts.config;
+    let round = &ctx.accounts.round;
+
+    // calculate bet weight
+    let now = Clock::get()?.unix_timestamp;
+    let BetWeight {
+        direction_factor,
+        time_factor,
+        weight,
+    } = calculate_round_bet_weight(config, round, &direction, amount, now)?;
+
+    Ok(BetPreview {
+        direction_factor,
+        time_factor,
+        weight,
+        timestamp: now,
+    })
+}
@@ -0,0 +1,66 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+/// Payout `claim_reward` would transfer for a settled bet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct RewardPreview {
+    pub status: BetStatus,
+    pub claimed: bool,
+    pub reward_amount: u64, // Includes the staking fee rebate; Lost bets get only their loss and fee rebates, promotional bets only the profit, CashedOut bets 0.
+}
+
+#[derive(Accounts)]
+pub struct PreviewReward<'info> {
+    #[account(
+        seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        seeds = [BET_SEED.as_bytes(), round.key().as_ref(), &bet.id.to_le_bytes()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+}
+
+impl<'info> PreviewReward<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.round.status == RoundStatus::Ended,
+            GoldRushError::RoundNotEnded
+        );
+
+        require_keys_eq!(
+            self.bet.round,
+            self.round.key(),
+            GoldRushError::InvalidBetAccount
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<PreviewReward>) -> Result<RewardPreview> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let round = &ctx.accounts.round;
+    let bet = &ctx.accounts.bet;
+
+    // same payout math as claim_reward; bets with nothing to claim preview as 0
+    let reward_amount = match bet.status {
+        BetStatus::Won | BetStatus::Draw | BetStatus::Lost => {
+            let payout = calculate_claim_amount_with_rebate(bet, round)?;
+            split_promotional_payout(payout, bet.amount, bet.is_promotional)?.0
+        }
+        BetStatus::CashedOut => 0,
+        BetStatus::Pending => return Err(GoldRushError::ClaimPendingBet.into()),
+    };
+
+    Ok(RewardPreview {
+        status: bet.status.clone(),
+        claimed: bet.claimed,
+        reward_amount,
+    })
+}
@@ -0,0 +1,59 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + Referrer::INIT_SPACE,
+        seeds = [REFERRER_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterReferrer<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.config.status == ProgramStatus::Active,
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.config.referrals_enabled,
+            GoldRushError::ReferralsNotInitialized
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RegisterReferrer>) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let referrer = &mut ctx.accounts.referrer;
+
+    // set fields
+    referrer.authority = ctx.accounts.signer.key();
+    referrer.referred_bets = 0;
+    referrer.claimable = 0;
+    referrer.total_earned = 0;
+    referrer.total_withdrawn = 0;
+    referrer.created_at = Clock::get()?.unix_timestamp;
+    referrer.bump = ctx.bumps.referrer;
+
+    Ok(())
+}
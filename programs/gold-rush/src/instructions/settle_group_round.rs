@@ -1,9 +1,9 @@
-use crate::{constants::*, error::GoldRushError, state::*};
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_lang::AccountDeserialize;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{transfer, Mint, Token, TokenAccount, Transfer},
+    token::{Mint, Token, TokenAccount},
 };
 #[derive(Accounts)]
 pub struct SettleGroupRound<'info> {
@@ -16,6 +16,15 @@ pub struct SettleGroupRound<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + KeeperStats::INIT_SPACE,
+        seeds = [KEEPER_STATS_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub keeper_stats: Account<'info, KeeperStats>,
+
     #[account(
         mut,
         seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
@@ -41,6 +50,34 @@ pub struct SettleGroupRound<'info> {
     )]
     pub treasury_token_account: Account<'info, TokenAccount>,
 
+    // Required when the fee schedule has a keeper pool leg
+    #[account(mut)]
+    pub keeper_reward_pool: Option<Account<'info, TokenAccount>>,
+
+    // Required once the jackpot is initialized
+    #[account(
+        mut,
+        seeds = [JACKPOT_SEED.as_bytes()],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+
+    #[account(
+        mut,
+        seeds = [JACKPOT_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub jackpot_vault: Option<Account<'info, TokenAccount>>,
+
+    // Required once referrals are initialized
+    #[account(
+        mut,
+        seeds = [REFERRAL_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
     pub mint: Account<'info, Mint>,
 
     pub token_program: Program<'info, Token>,
@@ -65,8 +102,55 @@ impl<'info> SettleGroupRound<'info> {
             GoldRushError::InvalidTreasuryAuthority
         );
 
+        // Each fee schedule leg is checked against its destination
+        if self.config.fee_schedule.keeper_pool_bps > 0 {
+            let keeper_reward_pool = self
+                .keeper_reward_pool
+                .as_ref()
+                .ok_or(GoldRushError::InvalidKeeperRewardPool)?;
+            require_keys_eq!(
+                keeper_reward_pool.key(),
+                self.config.keeper_reward_pool,
+                GoldRushError::InvalidKeeperRewardPool
+            );
+            require_keys_eq!(
+                keeper_reward_pool.mint,
+                self.mint.key(),
+                GoldRushError::InvalidMint
+            );
+        }
+        if self.config.fee_schedule.burn_bps > 0 {
+            require_keys_eq!(
+                self.mint.key(),
+                self.config.token_mint,
+                GoldRushError::InvalidMint
+            );
+            require_keys_eq!(
+                self.round_vault.mint,
+                self.mint.key(),
+                GoldRushError::InvalidMint
+            );
+        }
+
+        if self.config.jackpot_enabled {
+            require!(
+                self.jackpot.is_some() && self.jackpot_vault.is_some(),
+                GoldRushError::MissingJackpotAccount
+            );
+        }
+
+        if self.config.referrals_enabled {
+            require!(
+                self.referral_vault.is_some(),
+                GoldRushError::MissingReferralAccount
+            );
+        }
+
         require!(
-            matches!(self.round.market_type, MarketType::GroupBattle),
+            matches!(
+                self.round.market_type,
+                MarketType::GroupBattle | MarketType::AssetBattle
+            ),
             GoldRushError::InvalidRoundMarketType
         );
         require!(
@@ -81,6 +165,14 @@ impl<'info> SettleGroupRound<'info> {
             GoldRushError::RoundNotReadyForSettlement
         );
 
+        // AssetBattle winners are only known once every asset is ranked
+        if matches!(self.round.market_type, MarketType::AssetBattle) {
+            require!(
+                self.round.ranked_assets >= self.round.total_assets,
+                GoldRushError::RoundAssetsNotRanked
+            );
+        }
+
         Ok(())
     }
 }
@@ -94,9 +186,23 @@ pub fn handler(ctx: Context<SettleGroupRound>) -> Result<()> {
 
     // Consider full-draw if all groups are tied as winners.
     // In this simplified definition, if winner_group_ids covers all groups in the round,
-    // we treat the round as a draw for payout purposes.
-    let is_full_draw =
-        round.total_groups > 0 && (round.winner_group_ids.len() as u64) >= round.total_groups;
+    // we treat the round as a draw for payout purposes; a tie across more groups than
+    // winner_group_ids holds leaves the same ids on both sides. AssetBattle applies the same
+    // rule to winner_assets against all assets in the round, and also treats a round where
+    // every asset tied (even if LowestId kept a single winner) as a full draw.
+    let is_full_draw = match round.market_type {
+        MarketType::AssetBattle => {
+            round.total_assets > 0
+                && ((round.winner_assets.len() as u64) >= round.total_assets
+                    || round.max_asset_growth_rate_e8 == round.min_asset_growth_rate_e8)
+        }
+        _ => {
+            round.total_groups > 0
+                && ((round.winner_group_ids.len() as u64) >= round.total_groups
+                    || (!round.winner_group_ids.is_empty()
+                        && round.winner_group_ids == round.loser_group_ids))
+        }
+    };
 
     // If no bets, end quickly
     if round.total_bets == 0 {
@@ -105,53 +211,32 @@ pub fn handler(ctx: Context<SettleGroupRound>) -> Result<()> {
         return Ok(());
     }
 
-    // If first batch, compute and lock fee and reward pool once (GroupBattle fee)
-    if round.total_reward_pool == 0 && round.total_fee_collected == 0 {
-        if is_full_draw {
-            // Full draw: skip fee; entire pool becomes reward pool for refunds
-            round.total_fee_collected = 0;
-            round.total_reward_pool = round.total_pool;
-        } else {
-            let fee_bps = config.fee_group_battle_bps;
-            let fee_amount = round
-                .total_pool
-                .checked_mul(fee_bps as u64)
-                .and_then(|x| x.checked_div(HUNDRED_PERCENT_BPS as u64))
-                .ok_or(GoldRushError::Overflow)?;
-            round.total_fee_collected = fee_amount;
-            round.total_reward_pool = round
-                .total_pool
-                .checked_sub(fee_amount)
-                .ok_or(GoldRushError::Underflow)?;
-
-            // Transfer fee to treasury
-            if fee_amount > 0 {
-                let transfer_accounts = Transfer {
-                    from: ctx.accounts.round_vault.to_account_info(),
-                    to: ctx.accounts.treasury_token_account.to_account_info(),
-                    authority: round.to_account_info(),
-                };
-                let round_bump = round.bump;
-                let round_id = round.id;
-                let seeds = &[
-                    ROUND_SEED.as_bytes(),
-                    &round_id.to_le_bytes(),
-                    &[round_bump],
-                ];
-                let signer = &[&seeds[..]];
-                let transfer_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    transfer_accounts,
-                    signer,
-                );
-                transfer(transfer_ctx, fee_amount)?;
-            }
-        }
-    }
+    // Fee legs are routed out of the round vault on the first batch and on rollover
+    let mut settlement_accounts = SettlementAccounts {
+        round_vault: &ctx.accounts.round_vault,
+        treasury_token_account: &ctx.accounts.treasury_token_account,
+        keeper_reward_pool: ctx.accounts.keeper_reward_pool.as_ref(),
+        jackpot: ctx.accounts.jackpot.as_mut(),
+        jackpot_vault: ctx.accounts.jackpot_vault.as_ref(),
+        referral_vault: ctx.accounts.referral_vault.as_ref(),
+        mint: &ctx.accounts.mint,
+        token_program: &ctx.accounts.token_program,
+    };
+
+    // If first batch, compute and lock fee and reward pool once
+    lock_round_fees(
+        round,
+        config,
+        config.fee_group_battle_bps,
+        is_full_draw,
+        &mut settlement_accounts,
+    )?;
 
     // Iterate over Bet PDAs in remaining accounts (batched)
     let mut batch_winners_weight = 0u64;
-    for acc_info in ctx.remaining_accounts.iter() {
+    let mut batch_bets = 0u64;
+    let mut accounts_iter = ctx.remaining_accounts.iter();
+    while let Some(acc_info) = accounts_iter.next() {
         // Ownership must be our program (Bet PDA)
         require_keys_eq!(
             *acc_info.owner,
@@ -180,14 +265,49 @@ pub fn handler(ctx: Context<SettleGroupRound>) -> Result<()> {
             GoldRushError::InvalidBetAccount
         );
 
+        // Only unsettled bets are accepted, so a bet resubmitted in a later batch cannot
+        // accrue its loss rebate or referral share twice; cashed-out bets are counted once
+        require!(
+            bet.status == BetStatus::Pending
+                || (bet.status == BetStatus::CashedOut && !bet.claimed),
+            GoldRushError::InvalidBetStatus
+        );
+        batch_bets = batch_bets.checked_add(1).ok_or(GoldRushError::Overflow)?;
+
+        // Referred bets are followed by their Referrer PDA
+        let referrer_ai = bet
+            .referrer
+            .map(|_| {
+                accounts_iter
+                    .next()
+                    .ok_or(GoldRushError::MissingReferralAccount)
+            })
+            .transpose()?;
+
+        // Cashed-out bets already left the pool; keep their status and mark them counted
+        if skip_cashed_out_bet(&mut bet, &mut data)? {
+            continue;
+        }
+
         if is_full_draw {
             // Full draw: mark bet as Draw
             bet.status = BetStatus::Draw;
         } else {
-            // Decide result safely. A bet wins if its group (when present)
-            // matches any winner_group_ids' PDA.
-            let is_winner = if let Some(group_key) = bet.group {
-                round.winner_group_ids.iter().any(|gid| {
+            // Decide result safely. In AssetBattle a bet wins if its asset is
+            // one of winner_assets. Otherwise a bet wins if its group (when
+            // present) matches any PDA of the ids it was ranked against: the
+            // top groups by default, the bottom groups for Underperform bets.
+            let is_winner = if matches!(round.market_type, MarketType::AssetBattle) {
+                bet.asset
+                    .map(|asset_key| round.winner_assets.contains(&asset_key))
+                    .unwrap_or(false)
+            } else if let Some(group_key) = bet.group {
+                let group_ids = if bet.direction == BetDirection::Underperform {
+                    &round.loser_group_ids
+                } else {
+                    &round.winner_group_ids
+                };
+                group_ids.iter().any(|gid| {
                     let expected_group_pda = Pubkey::find_program_address(
                         &[
                             GROUP_ASSET_SEED.as_bytes(),
@@ -213,6 +333,14 @@ pub fn handler(ctx: Context<SettleGroupRound>) -> Result<()> {
             }
         }
 
+        // Set aside Lost bets' loss rebate from the reward pool (promotional stakes get none)
+        set_aside_loss_rebate(round, &mut bet)?;
+
+        // Accrue the referrer's share of the round's referral fee
+        if let (Some(referrer_key), Some(referrer_ai)) = (bet.referrer, referrer_ai) {
+            accrue_referral_share(round, &bet, referrer_key, referrer_ai, ctx.program_id)?;
+        }
+
         // Serialize back
         let serialized = bet
             .try_to_vec()
@@ -230,11 +358,14 @@ pub fn handler(ctx: Context<SettleGroupRound>) -> Result<()> {
         .ok_or(GoldRushError::Overflow)?;
     round.settled_bets = round
         .settled_bets
-        .checked_add(ctx.remaining_accounts.len() as u64)
+        .checked_add(batch_bets)
         .ok_or(GoldRushError::Overflow)?;
 
     // Finalize when all bets processed
     if round.settled_bets >= round.total_bets {
+        // Nobody won: the reward pool rolls over into the jackpot
+        roll_over_to_jackpot(round, config, is_full_draw, &mut settlement_accounts)?;
+
         round.status = RoundStatus::Ended;
         round.settled_at = Some(Clock::get()?.unix_timestamp);
     } else if round.status == RoundStatus::Active {
@@ -242,5 +373,14 @@ pub fn handler(ctx: Context<SettleGroupRound>) -> Result<()> {
         round.status = RoundStatus::PendingSettlement;
     }
 
+    // accrue keeper bounty
+    accrue_keeper_bounty(
+        &mut ctx.accounts.keeper_stats,
+        ctx.accounts.signer.key(),
+        ctx.bumps.keeper_stats,
+        batch_bets,
+        ctx.accounts.config.keeper_bounty_per_unit,
+    )?;
+
     Ok(())
 }
@@ -3,7 +3,7 @@ use anchor_lang::prelude::*;
 use anchor_lang::AccountDeserialize;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{transfer, Mint, Token, TokenAccount, Transfer},
+    token::{Mint, Token, TokenAccount},
 };
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
@@ -18,6 +18,15 @@ pub struct SettleSingleRound<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + KeeperStats::INIT_SPACE,
+        seeds = [KEEPER_STATS_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub keeper_stats: Account<'info, KeeperStats>,
+
     #[account(
         mut,
         seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
@@ -46,6 +55,34 @@ pub struct SettleSingleRound<'info> {
     )]
     pub treasury_token_account: Account<'info, TokenAccount>,
 
+    // Required when the fee schedule has a keeper pool leg
+    #[account(mut)]
+    pub keeper_reward_pool: Option<Account<'info, TokenAccount>>,
+
+    // Required once the jackpot is initialized
+    #[account(
+        mut,
+        seeds = [JACKPOT_SEED.as_bytes()],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+
+    #[account(
+        mut,
+        seeds = [JACKPOT_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub jackpot_vault: Option<Account<'info, TokenAccount>>,
+
+    // Required once referrals are initialized
+    #[account(
+        mut,
+        seeds = [REFERRAL_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
     pub mint: Account<'info, Mint>,
 
     pub token_program: Program<'info, Token>,
@@ -70,6 +107,50 @@ impl<'info> SettleSingleRound<'info> {
             GoldRushError::InvalidTreasuryAuthority
         );
 
+        // Each fee schedule leg is checked against its destination
+        if self.config.fee_schedule.keeper_pool_bps > 0 {
+            let keeper_reward_pool = self
+                .keeper_reward_pool
+                .as_ref()
+                .ok_or(GoldRushError::InvalidKeeperRewardPool)?;
+            require_keys_eq!(
+                keeper_reward_pool.key(),
+                self.config.keeper_reward_pool,
+                GoldRushError::InvalidKeeperRewardPool
+            );
+            require_keys_eq!(
+                keeper_reward_pool.mint,
+                self.mint.key(),
+                GoldRushError::InvalidMint
+            );
+        }
+        if self.config.fee_schedule.burn_bps > 0 {
+            require_keys_eq!(
+                self.mint.key(),
+                self.config.token_mint,
+                GoldRushError::InvalidMint
+            );
+            require_keys_eq!(
+                self.round_vault.mint,
+                self.mint.key(),
+                GoldRushError::InvalidMint
+            );
+        }
+
+        if self.config.jackpot_enabled {
+            require!(
+                self.jackpot.is_some() && self.jackpot_vault.is_some(),
+                GoldRushError::MissingJackpotAccount
+            );
+        }
+
+        if self.config.referrals_enabled {
+            require!(
+                self.referral_vault.is_some(),
+                GoldRushError::MissingReferralAccount
+            );
+        }
+
         require!(
             matches!(self.round.market_type, MarketType::SingleAsset),
             GoldRushError::InvalidRoundMarketType
@@ -140,54 +221,35 @@ pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleSingleRound<'info
     let price_change: i64 = (final_price as i64)
         .checked_sub(start_price as i64)
         .ok_or(GoldRushError::Overflow)?;
+    // Moves inside the round's draw band settle as a full draw
+    let price_change = apply_draw_band(price_change, start_price, round.draw_band_bps)?;
+
+    // Fee legs are routed out of the round vault on the first batch and on rollover
+    let mut settlement_accounts = SettlementAccounts {
+        round_vault: &ctx.accounts.round_vault,
+        treasury_token_account: &ctx.accounts.treasury_token_account,
+        keeper_reward_pool: ctx.accounts.keeper_reward_pool.as_ref(),
+        jackpot: ctx.accounts.jackpot.as_mut(),
+        jackpot_vault: ctx.accounts.jackpot_vault.as_ref(),
+        referral_vault: ctx.accounts.referral_vault.as_ref(),
+        mint: &ctx.accounts.mint,
+        token_program: &ctx.accounts.token_program,
+    };
 
     // If first batch, compute and lock fee and reward pool once
-    if round.total_reward_pool == 0 && round.total_fee_collected == 0 {
-        if price_change == 0 {
-            // Full draw: no fee collected, reward pool equals total pool
-            round.total_fee_collected = 0;
-            round.total_reward_pool = round.total_pool;
-        } else {
-            let fee_bps = config.fee_single_asset_bps;
-            let fee_amount = round
-                .total_pool
-                .checked_mul(fee_bps as u64)
-                .and_then(|x| x.checked_div(HUNDRED_PERCENT_BPS as u64))
-                .ok_or(GoldRushError::Overflow)?;
-            round.total_fee_collected = fee_amount;
-            round.total_reward_pool = round
-                .total_pool
-                .checked_sub(fee_amount)
-                .ok_or(GoldRushError::Underflow)?;
-
-            // Transfer fee to treasury
-            if fee_amount > 0 {
-                let transfer_accounts = Transfer {
-                    from: ctx.accounts.round_vault.to_account_info(),
-                    to: ctx.accounts.treasury_token_account.to_account_info(),
-                    authority: round.to_account_info(),
-                };
-                let round_bump = round.bump;
-                let round_id = round.id;
-                let seeds = &[
-                    ROUND_SEED.as_bytes(),
-                    &round_id.to_le_bytes(),
-                    &[round_bump],
-                ];
-                let signer = &[&seeds[..]];
-                let transfer_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    transfer_accounts,
-                    signer,
-                );
-                transfer(transfer_ctx, fee_amount)?;
-            }
-        }
-    }
+    lock_round_fees(
+        round,
+        config,
+        config.fee_single_asset_bps,
+        price_change == 0,
+        &mut settlement_accounts,
+    )?;
 
     // Iterate over Bet PDAs in remaining accounts (batched)
     let mut batch_winners_weight = 0u64;
-    for acc_info in ctx.remaining_accounts.iter() {
+    let mut batch_bets = 0u64;
+    let mut accounts_iter = ctx.remaining_accounts.iter();
+    while let Some(acc_info) = accounts_iter.next() {
         // Ownership must be our program (Bet PDA)
         require_keys_eq!(
             *acc_info.owner,
@@ -216,6 +278,30 @@ pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleSingleRound<'info
             GoldRushError::InvalidBetAccount
         );
 
+        // Only unsettled bets are accepted, so a bet resubmitted in a later batch cannot
+        // accrue its loss rebate or referral share twice; cashed-out bets are counted once
+        require!(
+            bet.status == BetStatus::Pending
+                || (bet.status == BetStatus::CashedOut && !bet.claimed),
+            GoldRushError::InvalidBetStatus
+        );
+        batch_bets = batch_bets.checked_add(1).ok_or(GoldRushError::Overflow)?;
+
+        // Referred bets are followed by their Referrer PDA
+        let referrer_ai = bet
+            .referrer
+            .map(|_| {
+                accounts_iter
+                    .next()
+                    .ok_or(GoldRushError::MissingReferralAccount)
+            })
+            .transpose()?;
+
+        // Cashed-out bets already left the pool; keep their status and mark them counted
+        if skip_cashed_out_bet(&mut bet, &mut data)? {
+            continue;
+        }
+
         // Decide result
         let is_winner = is_bet_winner(bet.direction.clone(), price_change);
         match is_winner {
@@ -233,6 +319,14 @@ pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleSingleRound<'info
             }
         }
 
+        // Set aside Lost bets' loss rebate from the reward pool (promotional stakes get none)
+        set_aside_loss_rebate(round, &mut bet)?;
+
+        // Accrue the referrer's share of the round's referral fee
+        if let (Some(referrer_key), Some(referrer_ai)) = (bet.referrer, referrer_ai) {
+            accrue_referral_share(round, &bet, referrer_key, referrer_ai, ctx.program_id)?;
+        }
+
         // Serialize back
         let serialized = bet
             .try_to_vec()
@@ -250,11 +344,14 @@ pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleSingleRound<'info
         .ok_or(GoldRushError::Overflow)?;
     round.settled_bets = round
         .settled_bets
-        .checked_add((ctx.remaining_accounts.len()) as u64)
+        .checked_add(batch_bets)
         .ok_or(GoldRushError::Overflow)?;
 
     // Finalize when all bets processed
     if round.settled_bets >= round.total_bets {
+        // Nobody won: the reward pool rolls over into the jackpot
+        roll_over_to_jackpot(round, config, price_change == 0, &mut settlement_accounts)?;
+
         round.status = RoundStatus::Ended;
         round.final_price = Some(final_price);
         round.settled_at = Some(now.unix_timestamp);
@@ -263,5 +360,14 @@ pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleSingleRound<'info
         round.status = RoundStatus::PendingSettlement;
     }
 
+    // accrue keeper bounty
+    accrue_keeper_bounty(
+        &mut ctx.accounts.keeper_stats,
+        ctx.accounts.signer.key(),
+        ctx.bumps.keeper_stats,
+        batch_bets,
+        ctx.accounts.config.keeper_bounty_per_unit,
+    )?;
+
     Ok(())
 }
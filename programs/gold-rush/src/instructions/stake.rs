@@ -0,0 +1,101 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISRIMINATOR_SIZE as usize + StakeAccount::INIT_SPACE,
+        seeds = [STAKE_ACCOUNT_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Stake<'info> {
+    pub fn validate(&self, amount: u64) -> Result<()> {
+        require!(
+            self.config.status == ProgramStatus::Active,
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.config.staking_enabled,
+            GoldRushError::StakingNotInitialized
+        );
+
+        require!(amount > 0, GoldRushError::InvalidStakeAmount);
+
+        require_keys_eq!(
+            self.stake_vault.mint,
+            self.mint.key(),
+            GoldRushError::InvalidMint
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<Stake>, amount: u64) -> Result<()> {
+    // validate
+    ctx.accounts.validate(amount)?;
+
+    // transfer from signer to stake vault
+    let transfer_accounts = Transfer {
+        from: ctx.accounts.token_account.to_account_info(),
+        to: ctx.accounts.stake_vault.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+    );
+    transfer(transfer_ctx, amount)?;
+
+    let stake_account = &mut ctx.accounts.stake_account;
+
+    // first stake of this wallet
+    if stake_account.owner == Pubkey::default() {
+        stake_account.owner = ctx.accounts.signer.key();
+        stake_account.created_at = Clock::get()?.unix_timestamp;
+        stake_account.bump = ctx.bumps.stake_account;
+    }
+
+    // set fields
+    stake_account.staked_amount = stake_account
+        .staked_amount
+        .checked_add(amount)
+        .ok_or(GoldRushError::Overflow)?;
+
+    Ok(())
+}
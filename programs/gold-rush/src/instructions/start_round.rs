@@ -56,7 +56,10 @@ impl<'info> StartRound<'info> {
             );
         }
 
-        if matches!(self.round.market_type, MarketType::GroupBattle) {
+        if matches!(
+            self.round.market_type,
+            MarketType::GroupBattle | MarketType::AssetBattle
+        ) {
             require!(
                 self.round.captured_start_groups >= self.round.total_groups,
                 GoldRushError::RoundNotCapturedStartPrice
@@ -0,0 +1,144 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{close_account, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+
+#[derive(Accounts)]
+pub struct SweepUnclaimedRewards<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED.as_bytes(), &round.id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED.as_bytes(), round.key().as_ref()],
+        bump
+    )]
+    pub round_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Treasury pubkey from config
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SweepUnclaimedRewards<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.config.status == ProgramStatus::Active,
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.signer.key() == self.config.admin
+                || self.config.keeper_authorities.contains(&self.signer.key()),
+            GoldRushError::Unauthorized
+        );
+
+        require!(
+            self.treasury.key() == self.config.treasury,
+            GoldRushError::InvalidTreasuryAuthority
+        );
+
+        require!(
+            self.round.status == RoundStatus::Ended,
+            GoldRushError::RoundNotEnded
+        );
+
+        // Rounds without a claim window can be claimed forever
+        require!(
+            is_claim_expired(
+                self.round.settled_at,
+                self.round.claim_window_secs,
+                Clock::get()?.unix_timestamp
+            )?,
+            GoldRushError::ClaimWindowNotExpired
+        );
+
+        require_keys_eq!(
+            self.round_vault.mint,
+            self.mint.key(),
+            GoldRushError::InvalidMint
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SweepUnclaimedRewards>) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let round = &mut ctx.accounts.round;
+    let unclaimed_amount = ctx.accounts.round_vault.amount;
+
+    let round_id = round.id;
+    let round_bump = round.bump;
+    let seeds = &[
+        ROUND_SEED.as_bytes(),
+        &round_id.to_le_bytes(),
+        &[round_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // transfer unclaimed rewards from vault to treasury
+    if unclaimed_amount > 0 {
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.round_vault.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: round.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            signer,
+        );
+        transfer(transfer_ctx, unclaimed_amount)?;
+    }
+
+    // close the empty vault; its rent goes to the treasury
+    let close_accounts = CloseAccount {
+        account: ctx.accounts.round_vault.to_account_info(),
+        destination: ctx.accounts.treasury.to_account_info(),
+        authority: round.to_account_info(),
+    };
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        close_accounts,
+        signer,
+    );
+    close_account(close_ctx)?;
+
+    // set round fields
+    round.unclaimed_swept = unclaimed_amount;
+    round.status = RoundStatus::Closed;
+
+    Ok(())
+}
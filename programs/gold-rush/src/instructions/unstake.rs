@@ -0,0 +1,65 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_ACCOUNT_SEED.as_bytes(), signer.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+}
+
+impl<'info> Unstake<'info> {
+    pub fn validate(&self, amount: u64) -> Result<()> {
+        require!(
+            matches!(
+                self.config.status,
+                ProgramStatus::Active | ProgramStatus::EmergencyPaused
+            ),
+            GoldRushError::ProgramPaused
+        );
+
+        require!(amount > 0, GoldRushError::InvalidStakeAmount);
+
+        require!(
+            amount <= self.stake_account.staked_amount,
+            GoldRushError::InsufficientStake
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    // validate
+    ctx.accounts.validate(amount)?;
+
+    let stake_account = &mut ctx.accounts.stake_account;
+
+    // set fields; the cooldown restarts for everything still unstaking
+    stake_account.staked_amount = stake_account
+        .staked_amount
+        .checked_sub(amount)
+        .ok_or(GoldRushError::Underflow)?;
+    stake_account.unstaking_amount = stake_account
+        .unstaking_amount
+        .checked_add(amount)
+        .ok_or(GoldRushError::Overflow)?;
+    stake_account.unstake_available_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(ctx.accounts.config.unstake_cooldown_secs)
+        .ok_or(GoldRushError::Overflow)?;
+
+    Ok(())
+}
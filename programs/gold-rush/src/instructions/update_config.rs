@@ -1,6 +1,37 @@
 use crate::{constants::*, error::GoldRushError, state::*};
 use anchor_lang::prelude::*;
 
+/// Config fields to change; `None` leaves the current value untouched.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateConfigParams {
+    pub new_admin: Option<Pubkey>,
+    pub new_keeper_authorities: Option<Vec<Pubkey>>,
+    pub new_token_mint: Option<Pubkey>,
+    pub new_treasury: Option<Pubkey>,
+    pub new_single_asset_feed_id: Option<[u8; 32]>,
+    pub new_max_price_update_age_secs: Option<u64>,
+    pub new_fee_single_asset_bps: Option<u16>,
+    pub new_fee_group_battle_bps: Option<u16>,
+    pub new_min_bet_amount: Option<u64>,
+    pub new_bet_cutoff_window_secs: Option<i64>,
+    pub new_tie_break_rule: Option<TieBreakRule>,
+    pub new_draw_band_bps: Option<u16>,
+    pub new_early_exit_fee_bps: Option<u16>,
+    pub new_cash_out_haircut_bps: Option<u16>,
+    pub new_max_bet_amount: Option<u64>,
+    pub new_max_user_stake_per_round: Option<u64>,
+    pub new_max_total_pool: Option<u64>,
+    pub new_claim_window_secs: Option<i64>,
+    pub new_jackpot_fee_bps: Option<u16>,
+    pub new_fee_schedule: Option<FeeSchedule>,
+    pub new_keeper_reward_pool: Option<Pubkey>,
+    pub new_keeper_bounty_per_unit: Option<u64>,
+    pub new_referral_fee_bps: Option<u16>,
+    pub new_fee_discount_tiers: Option<Vec<FeeDiscountTier>>,
+    pub new_unstake_cooldown_secs: Option<i64>,
+    pub new_loss_rebate_bps: Option<u16>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateConfig<'info> {
     #[account(mut)]
@@ -15,30 +46,20 @@ pub struct UpdateConfig<'info> {
 }
 
 impl<'info> UpdateConfig<'info> {
-    pub fn validate(
-        &self,
-        new_admin: Option<Pubkey>,
-        new_keeper_authorities: &Option<Vec<Pubkey>>,
-        new_token_mint: Option<Pubkey>,
-        new_treasury: Option<Pubkey>,
-        new_fee_single_asset_bps: Option<u16>,
-        new_fee_group_battle_bps: Option<u16>,
-        new_min_bet_amount: Option<u64>,
-        new_bet_cutoff_window_secs: Option<i64>,
-    ) -> Result<()> {
+    pub fn validate(&self, params: &UpdateConfigParams) -> Result<()> {
         require!(
             self.signer.key() == self.config.admin,
             GoldRushError::Unauthorized
         );
 
-        if let Some(new_admin) = new_admin {
+        if let Some(new_admin) = params.new_admin {
             require!(
                 new_admin != self.config.admin,
                 GoldRushError::InvalidNewAdmin
             );
         }
 
-        if let Some(new_keeper_authorities) = new_keeper_authorities {
+        if let Some(new_keeper_authorities) = &params.new_keeper_authorities {
             require!(
                 new_keeper_authorities.len() > 0,
                 GoldRushError::NoNewKeeperAuthorities
@@ -50,110 +71,293 @@ impl<'info> UpdateConfig<'info> {
             );
         }
 
-        if let Some(new_token_mint) = new_token_mint {
+        if let Some(new_token_mint) = params.new_token_mint {
             require!(
                 new_token_mint != self.config.token_mint,
                 GoldRushError::InvalidNewTokenMint
             );
         }
 
-        if let Some(new_treasury) = new_treasury {
+        if let Some(new_treasury) = params.new_treasury {
             require!(
                 new_treasury != self.config.treasury,
                 GoldRushError::InvalidNewTreasury
             );
         }
 
-        if let Some(new_fee_single_asset_bps) = new_fee_single_asset_bps {
+        if let Some(new_fee_single_asset_bps) = params.new_fee_single_asset_bps {
             require!(
                 new_fee_single_asset_bps < HUNDRED_PERCENT_BPS,
                 GoldRushError::InvalidNewFeeGoldPriceBps
             );
         }
 
-        if let Some(new_fee_group_battle_bps) = new_fee_group_battle_bps {
+        if let Some(new_fee_group_battle_bps) = params.new_fee_group_battle_bps {
             require!(
                 new_fee_group_battle_bps < HUNDRED_PERCENT_BPS,
                 GoldRushError::InvalidNewFeeStockPriceBps
             );
         }
 
-        if let Some(new_min_bet_amount) = new_min_bet_amount {
+        if let Some(new_min_bet_amount) = params.new_min_bet_amount {
             require!(
                 new_min_bet_amount > 0,
                 GoldRushError::InvalidNewMinBetAmount
             );
         }
 
-        if let Some(new_bet_cutoff_window_secs) = new_bet_cutoff_window_secs {
+        if let Some(new_bet_cutoff_window_secs) = params.new_bet_cutoff_window_secs {
             require!(
                 new_bet_cutoff_window_secs >= 0,
                 GoldRushError::InvalidNewBetCutoffWindowSecs
             );
         }
 
+        if let Some(new_draw_band_bps) = params.new_draw_band_bps {
+            require!(
+                new_draw_band_bps < HUNDRED_PERCENT_BPS,
+                GoldRushError::InvalidNewDrawBandBps
+            );
+        }
+
+        if let Some(new_early_exit_fee_bps) = params.new_early_exit_fee_bps {
+            require!(
+                new_early_exit_fee_bps < HUNDRED_PERCENT_BPS,
+                GoldRushError::InvalidNewEarlyExitFeeBps
+            );
+        }
+
+        if let Some(new_cash_out_haircut_bps) = params.new_cash_out_haircut_bps {
+            require!(
+                new_cash_out_haircut_bps < HUNDRED_PERCENT_BPS,
+                GoldRushError::InvalidNewCashOutHaircutBps
+            );
+        }
+
+        // Bet limits are checked as a pair whenever either side changes.
+        // 0 disables a cap; otherwise it cannot be below the minimum bet
+        let min_bet_amount = params
+            .new_min_bet_amount
+            .unwrap_or(self.config.min_bet_amount);
+        if params.new_min_bet_amount.is_some() || params.new_max_bet_amount.is_some() {
+            let max_bet_amount = params
+                .new_max_bet_amount
+                .unwrap_or(self.config.max_bet_amount);
+            require!(
+                max_bet_amount == 0 || max_bet_amount >= min_bet_amount,
+                GoldRushError::InvalidNewMaxBetAmount
+            );
+        }
+
+        if params.new_min_bet_amount.is_some() || params.new_max_user_stake_per_round.is_some() {
+            let max_user_stake_per_round = params
+                .new_max_user_stake_per_round
+                .unwrap_or(self.config.max_user_stake_per_round);
+            require!(
+                max_user_stake_per_round == 0 || max_user_stake_per_round >= min_bet_amount,
+                GoldRushError::InvalidNewMaxUserStakePerRound
+            );
+        }
+
+        // 0 only stands for the cap never being set; a new cap cannot be below the max bet
+        if let Some(new_max_total_pool) = params.new_max_total_pool {
+            require!(
+                new_max_total_pool > 0,
+                GoldRushError::InvalidNewMaxTotalPool
+            );
+        }
+
+        if params.new_max_bet_amount.is_some() || params.new_max_total_pool.is_some() {
+            let max_bet_amount = params
+                .new_max_bet_amount
+                .unwrap_or(self.config.max_bet_amount);
+            let max_total_pool = params
+                .new_max_total_pool
+                .unwrap_or(self.config.max_total_pool);
+            require!(
+                max_total_pool == 0 || max_total_pool >= max_bet_amount,
+                GoldRushError::InvalidNewMaxTotalPool
+            );
+        }
+
+        if let Some(new_claim_window_secs) = params.new_claim_window_secs {
+            require!(
+                new_claim_window_secs >= 0,
+                GoldRushError::InvalidNewClaimWindowSecs
+            );
+        }
+
+        // The jackpot and referral slices both come off the top of the fee
+        let top_slices_bps = (params
+            .new_jackpot_fee_bps
+            .unwrap_or(self.config.jackpot_fee_bps) as u32)
+            + (params
+                .new_referral_fee_bps
+                .unwrap_or(self.config.referral_fee_bps) as u32);
+        if params.new_jackpot_fee_bps.is_some() {
+            require!(
+                top_slices_bps <= HUNDRED_PERCENT_BPS as u32,
+                GoldRushError::InvalidNewJackpotFeeBps
+            );
+        }
+
+        if params.new_referral_fee_bps.is_some() {
+            require!(
+                top_slices_bps <= HUNDRED_PERCENT_BPS as u32,
+                GoldRushError::InvalidNewReferralFeeBps
+            );
+        }
+
+        if let Some(new_fee_schedule) = &params.new_fee_schedule {
+            let total_bps = (new_fee_schedule.treasury_bps as u32)
+                + (new_fee_schedule.keeper_pool_bps as u32)
+                + (new_fee_schedule.burn_bps as u32);
+            require!(
+                total_bps == HUNDRED_PERCENT_BPS as u32,
+                GoldRushError::InvalidNewFeeSchedule
+            );
+        }
+
+        // Every settlement fails without a destination for the keeper pool leg
+        if params.new_fee_schedule.is_some() || params.new_keeper_reward_pool.is_some() {
+            let keeper_pool_bps = params
+                .new_fee_schedule
+                .as_ref()
+                .unwrap_or(&self.config.fee_schedule)
+                .keeper_pool_bps;
+            let keeper_reward_pool = params
+                .new_keeper_reward_pool
+                .unwrap_or(self.config.keeper_reward_pool);
+            require!(
+                keeper_pool_bps == 0 || keeper_reward_pool != Pubkey::default(),
+                GoldRushError::KeeperRewardPoolNotSet
+            );
+        }
+
+        if let Some(new_fee_discount_tiers) = &params.new_fee_discount_tiers {
+            require!(
+                new_fee_discount_tiers.len() <= MAX_FEE_DISCOUNT_TIERS,
+                GoldRushError::InvalidNewFeeDiscountTiers
+            );
+
+            // Tiers ascend by min stake, and each discount fits in 100%
+            require!(
+                new_fee_discount_tiers
+                    .windows(2)
+                    .all(|tiers| tiers[0].min_stake < tiers[1].min_stake),
+                GoldRushError::InvalidNewFeeDiscountTiers
+            );
+            require!(
+                new_fee_discount_tiers
+                    .iter()
+                    .all(|tier| tier.discount_bps <= HUNDRED_PERCENT_BPS),
+                GoldRushError::InvalidNewFeeDiscountTiers
+            );
+        }
+
+        if let Some(new_unstake_cooldown_secs) = params.new_unstake_cooldown_secs {
+            require!(
+                new_unstake_cooldown_secs >= 0,
+                GoldRushError::InvalidNewUnstakeCooldownSecs
+            );
+        }
+
+        if let Some(new_loss_rebate_bps) = params.new_loss_rebate_bps {
+            require!(
+                new_loss_rebate_bps <= HUNDRED_PERCENT_BPS,
+                GoldRushError::InvalidNewLossRebateBps
+            );
+        }
+
         Ok(())
     }
 }
 
-pub fn handler(
-    ctx: Context<UpdateConfig>,
-    new_admin: Option<Pubkey>,
-    new_keeper_authorities: Option<Vec<Pubkey>>,
-    new_token_mint: Option<Pubkey>,
-    new_treasury: Option<Pubkey>,
-    new_single_asset_feed_id: Option<[u8; 32]>,
-    new_max_price_update_age_secs: Option<u64>,
-    new_fee_single_asset_bps: Option<u16>,
-    new_fee_group_battle_bps: Option<u16>,
-    new_min_bet_amount: Option<u64>,
-    new_bet_cutoff_window_secs: Option<i64>,
-) -> Result<()> {
+pub fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
     // validate
-    ctx.accounts.validate(
-        new_admin,
-        &new_keeper_authorities,
-        new_token_mint,
-        new_treasury,
-        new_fee_single_asset_bps,
-        new_fee_group_battle_bps,
-        new_min_bet_amount,
-        new_bet_cutoff_window_secs,
-    )?;
+    ctx.accounts.validate(&params)?;
 
     let config = &mut ctx.accounts.config;
 
     // set fields
-    if let Some(new_admin) = new_admin {
+    if let Some(new_admin) = params.new_admin {
         config.admin = new_admin;
     }
-    if let Some(new_keeper_authorities) = new_keeper_authorities {
+    if let Some(new_keeper_authorities) = params.new_keeper_authorities {
         config.keeper_authorities = new_keeper_authorities;
     }
-    if let Some(new_token_mint) = new_token_mint {
+    if let Some(new_token_mint) = params.new_token_mint {
         config.token_mint = new_token_mint;
     }
-    if let Some(new_treasury) = new_treasury {
+    if let Some(new_treasury) = params.new_treasury {
         config.treasury = new_treasury;
     }
-    if let Some(new_single_asset_feed_id) = new_single_asset_feed_id {
+    if let Some(new_single_asset_feed_id) = params.new_single_asset_feed_id {
         config.single_asset_feed_id = new_single_asset_feed_id;
     }
-    if let Some(new_max_price_update_age_secs) = new_max_price_update_age_secs {
+    if let Some(new_max_price_update_age_secs) = params.new_max_price_update_age_secs {
         config.max_price_update_age_secs = new_max_price_update_age_secs;
     }
-    if let Some(new_fee_single_asset_bps) = new_fee_single_asset_bps {
+    if let Some(new_fee_single_asset_bps) = params.new_fee_single_asset_bps {
         config.fee_single_asset_bps = new_fee_single_asset_bps;
     }
-    if let Some(new_fee_group_battle_bps) = new_fee_group_battle_bps {
+    if let Some(new_fee_group_battle_bps) = params.new_fee_group_battle_bps {
         config.fee_group_battle_bps = new_fee_group_battle_bps;
     }
-    if let Some(new_min_bet_amount) = new_min_bet_amount {
+    if let Some(new_min_bet_amount) = params.new_min_bet_amount {
         config.min_bet_amount = new_min_bet_amount;
     }
-    if let Some(new_bet_cutoff_window_secs) = new_bet_cutoff_window_secs {
+    if let Some(new_bet_cutoff_window_secs) = params.new_bet_cutoff_window_secs {
         config.bet_cutoff_window_secs = new_bet_cutoff_window_secs;
     }
+    if let Some(new_tie_break_rule) = params.new_tie_break_rule {
+        config.tie_break_rule = new_tie_break_rule;
+    }
+    if let Some(new_draw_band_bps) = params.new_draw_band_bps {
+        config.draw_band_bps = new_draw_band_bps;
+    }
+    if let Some(new_early_exit_fee_bps) = params.new_early_exit_fee_bps {
+        config.early_exit_fee_bps = new_early_exit_fee_bps;
+    }
+    if let Some(new_cash_out_haircut_bps) = params.new_cash_out_haircut_bps {
+        config.cash_out_haircut_bps = new_cash_out_haircut_bps;
+    }
+    if let Some(new_max_bet_amount) = params.new_max_bet_amount {
+        config.max_bet_amount = new_max_bet_amount;
+    }
+    if let Some(new_max_user_stake_per_round) = params.new_max_user_stake_per_round {
+        config.max_user_stake_per_round = new_max_user_stake_per_round;
+    }
+    if let Some(new_max_total_pool) = params.new_max_total_pool {
+        config.max_total_pool = new_max_total_pool;
+    }
+    if let Some(new_claim_window_secs) = params.new_claim_window_secs {
+        config.claim_window_secs = new_claim_window_secs;
+    }
+    if let Some(new_jackpot_fee_bps) = params.new_jackpot_fee_bps {
+        config.jackpot_fee_bps = new_jackpot_fee_bps;
+    }
+    if let Some(new_fee_schedule) = params.new_fee_schedule {
+        config.fee_schedule = new_fee_schedule;
+    }
+    if let Some(new_keeper_reward_pool) = params.new_keeper_reward_pool {
+        config.keeper_reward_pool = new_keeper_reward_pool;
+    }
+    if let Some(new_keeper_bounty_per_unit) = params.new_keeper_bounty_per_unit {
+        config.keeper_bounty_per_unit = new_keeper_bounty_per_unit;
+    }
+    if let Some(new_referral_fee_bps) = params.new_referral_fee_bps {
+        config.referral_fee_bps = new_referral_fee_bps;
+    }
+    if let Some(new_fee_discount_tiers) = params.new_fee_discount_tiers {
+        config.fee_discount_tiers = new_fee_discount_tiers;
+    }
+    if let Some(new_unstake_cooldown_secs) = params.new_unstake_cooldown_secs {
+        config.unstake_cooldown_secs = new_unstake_cooldown_secs;
+    }
+    if let Some(new_loss_rebate_bps) = params.new_loss_rebate_bps {
+        config.loss_rebate_bps = new_loss_rebate_bps;
+    }
 
     // update config version
     config.version = config
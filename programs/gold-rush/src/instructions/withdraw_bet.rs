@@ -1,4 +1,4 @@
-use crate::{constants::*, error::GoldRushError, state::*};
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
 
@@ -20,11 +20,20 @@ pub struct WithdrawBet<'info> {
     )]
     pub round: Account<'info, Round>,
 
+    // Closed in the handler on a full withdrawal
+    #[account(mut)]
+    pub bet: Account<'info, Bet>,
+
+    // Optional: only required for GroupBattle bets
+    #[account(mut)]
+    pub group_asset: Option<Account<'info, GroupAsset>>,
+
     #[account(
         mut,
-        close = signer,
+        seeds = [USER_ROUND_POSITION_SEED.as_bytes(), round.key().as_ref(), signer.key().as_ref()],
+        bump = user_round_position.bump
     )]
-    pub bet: Account<'info, Bet>,
+    pub user_round_position: Account<'info, UserRoundPosition>,
 
     #[account(
         mut,
@@ -40,6 +49,26 @@ pub struct WithdrawBet<'info> {
     )]
     pub bettor_token_account: Account<'info, TokenAccount>,
 
+    // Optional: only required for promotional bets, whose stake goes back to the promo vault
+    #[account(
+        mut,
+        seeds = [PROMO_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub promo_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Treasury pubkey from config
+    pub treasury: UncheckedAccount<'info>,
+
+    // Optional: only required when an early-exit fee is charged. Must already exist; the
+    // bettor does not pay for the treasury ATA
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
     pub mint: Account<'info, Mint>,
 
     pub token_program: Program<'info, Token>,
@@ -47,7 +76,7 @@ pub struct WithdrawBet<'info> {
 }
 
 impl<'info> WithdrawBet<'info> {
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, amount: u64) -> Result<()> {
         require!(
             self.config.status == ProgramStatus::Active,
             GoldRushError::ProgramPaused
@@ -80,51 +109,219 @@ impl<'info> WithdrawBet<'info> {
             GoldRushError::InvalidBetStatus
         );
 
+        // Group bets keep the group's running totals in sync
+        if let Some(group) = self.bet.group {
+            let ga = self
+                .group_asset
+                .as_ref()
+                .ok_or(GoldRushError::InvalidGroupAssetAccount)?;
+            require_keys_eq!(ga.key(), group, GoldRushError::InvalidGroupAssetAccount);
+        }
+
+        require!(
+            self.treasury.key() == self.config.treasury,
+            GoldRushError::InvalidTreasuryAuthority
+        );
+
+        if self.bet.is_promotional {
+            require!(self.promo_vault.is_some(), GoldRushError::MissingPromoVault);
+        }
+
+        require!(
+            amount > 0 && amount <= self.bet.amount,
+            GoldRushError::InvalidBetAmount
+        );
+
+        // A partial withdrawal must leave at least the minimum bet behind
+        if amount < self.bet.amount {
+            require!(
+                self.bet.amount - amount >= self.config.min_bet_amount,
+                GoldRushError::BetBelowMinimum
+            );
+        }
+
+        if self.early_exit_fee(amount)? > 0 {
+            require!(
+                self.treasury_token_account.is_some(),
+                GoldRushError::MissingTreasuryTokenAccount
+            );
+        }
+
         Ok(())
     }
+
+    /// The early-exit fee on `amount`, charged alike on partial and full withdrawals so a
+    /// full exit cannot be used to re-bet without it; a promotional stake goes back to the
+    /// promo vault in full
+    pub fn early_exit_fee(&self, amount: u64) -> Result<u64> {
+        if self.bet.is_promotional {
+            return Ok(0);
+        }
+
+        amount
+            .checked_mul(self.config.early_exit_fee_bps as u64)
+            .and_then(|x| x.checked_div(HUNDRED_PERCENT_BPS as u64))
+            .ok_or(GoldRushError::Overflow.into())
+    }
 }
 
-pub fn handler(ctx: Context<WithdrawBet>) -> Result<()> {
+pub fn handler(ctx: Context<WithdrawBet>, amount: Option<u64>) -> Result<()> {
+    // None withdraws the whole bet
+    let amount = amount.unwrap_or(ctx.accounts.bet.amount);
+
     // validate
-    ctx.accounts.validate()?;
+    ctx.accounts.validate(amount)?;
+
+    // The early-exit fee stays with the treasury
+    let fee_amount = ctx.accounts.early_exit_fee(amount)?;
 
     let round = &mut ctx.accounts.round;
-    let bet = &ctx.accounts.bet;
+    let bet = &mut ctx.accounts.bet;
+    let user_round_position = &mut ctx.accounts.user_round_position;
+
+    let refund_amount = amount
+        .checked_sub(fee_amount)
+        .ok_or(GoldRushError::Underflow)?;
+
+    let round_bump = round.bump;
+    let round_id = round.id;
+    let seeds = &[
+        ROUND_SEED.as_bytes(),
+        &round_id.to_le_bytes(),
+        &[round_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Transfer tokens from round vault back to bettor (or the promo vault)
+    if refund_amount > 0 {
+        let to = if bet.is_promotional {
+            ctx.accounts
+                .promo_vault
+                .as_ref()
+                .ok_or(GoldRushError::MissingPromoVault)?
+                .to_account_info()
+        } else {
+            ctx.accounts.bettor_token_account.to_account_info()
+        };
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.round_vault.to_account_info(),
+            to,
+            authority: round.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            signer,
+        );
+        transfer(transfer_ctx, refund_amount)?;
+    }
 
-    // Transfer tokens from round vault back to bettor
-    let amount = bet.amount;
-    if amount > 0 {
+    // Transfer early-exit fee to treasury
+    if fee_amount > 0 {
+        let treasury_token_account = ctx
+            .accounts
+            .treasury_token_account
+            .as_ref()
+            .ok_or(GoldRushError::MissingTreasuryTokenAccount)?;
         let transfer_accounts = Transfer {
             from: ctx.accounts.round_vault.to_account_info(),
-            to: ctx.accounts.bettor_token_account.to_account_info(),
+            to: treasury_token_account.to_account_info(),
             authority: round.to_account_info(),
         };
-        let round_bump = round.bump;
-        let round_id = round.id;
-        let seeds = &[
-            ROUND_SEED.as_bytes(),
-            &round_id.to_le_bytes(),
-            &[round_bump],
-        ];
-        let signer = &[&seeds[..]];
         let transfer_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             transfer_accounts,
             signer,
         );
-        transfer(transfer_ctx, amount)?;
+        transfer(transfer_ctx, fee_amount)?;
     }
 
     // Update round aggregates
     round.total_pool = round
         .total_pool
-        .checked_sub(bet.amount)
+        .checked_sub(amount)
+        .ok_or(GoldRushError::Underflow)?;
+    round.total_stake = round
+        .total_stake
+        .checked_sub(amount)
+        .ok_or(GoldRushError::Underflow)?;
+    if bet.referrer.is_some() {
+        round.referred_stake = round
+            .referred_stake
+            .checked_sub(amount)
+            .ok_or(GoldRushError::Underflow)?;
+    }
+
+    // Partial withdrawal cuts weight in proportion to the remaining amount
+    let remaining_amount = bet
+        .amount
+        .checked_sub(amount)
+        .ok_or(GoldRushError::Underflow)?;
+    let remaining_weight = (bet.weight as u128)
+        .checked_mul(remaining_amount as u128)
+        .ok_or(GoldRushError::Overflow)?
+        .checked_div(bet.amount as u128)
+        .ok_or(GoldRushError::Underflow)? as u64;
+    let removed_discounted_stake = calculate_discounted_stake(bet.amount, bet.fee_discount_bps)?
+        .checked_sub(calculate_discounted_stake(
+            remaining_amount,
+            bet.fee_discount_bps,
+        )?)
         .ok_or(GoldRushError::Underflow)?;
-    round.total_bets = round
-        .total_bets
-        .checked_sub(1)
+    round.discounted_stake = round
+        .discounted_stake
+        .checked_sub(removed_discounted_stake)
         .ok_or(GoldRushError::Underflow)?;
+    let removed_weight = bet
+        .weight
+        .checked_sub(remaining_weight)
+        .ok_or(GoldRushError::Underflow)?;
+    sub_from_direction_totals(
+        &mut round.direction_totals,
+        &bet.direction,
+        amount,
+        removed_weight,
+    )?;
+
+    // Update user round position
+    user_round_position.total_stake = user_round_position
+        .total_stake
+        .checked_sub(amount)
+        .ok_or(GoldRushError::Underflow)?;
+    user_round_position.total_weight = user_round_position
+        .total_weight
+        .checked_sub(removed_weight)
+        .ok_or(GoldRushError::Underflow)?;
+
+    // Update group aggregates
+    if bet.group.is_some() {
+        let group_asset = ctx
+            .accounts
+            .group_asset
+            .as_mut()
+            .ok_or(GoldRushError::InvalidGroupAssetAccount)?;
+        sub_from_direction_totals(
+            &mut group_asset.direction_totals,
+            &bet.direction,
+            amount,
+            removed_weight,
+        )?;
+    }
+
+    if remaining_amount > 0 {
+        bet.amount = remaining_amount;
+        bet.weight = remaining_weight;
+    } else {
+        round.total_bets = round
+            .total_bets
+            .checked_sub(1)
+            .ok_or(GoldRushError::Underflow)?;
+        let bet_id = bet.id;
+        user_round_position.bet_ids.retain(|id| *id != bet_id);
+
+        // Full withdrawal: close bet account and refund rent to bettor
+        bet.close(ctx.accounts.signer.to_account_info())?;
+    }
 
-    // bet account will be closed automatically to bettor by close attribute
     Ok(())
 }
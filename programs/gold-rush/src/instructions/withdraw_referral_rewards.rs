@@ -0,0 +1,94 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct WithdrawReferralRewards<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [REFERRER_SEED.as_bytes(), signer.key().as_ref()],
+        bump = referrer.bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    #[account(
+        mut,
+        seeds = [REFERRAL_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub referral_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+    )]
+    pub referrer_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawReferralRewards<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            matches!(
+                self.config.status,
+                ProgramStatus::Active | ProgramStatus::EmergencyPaused
+            ),
+            GoldRushError::ProgramPaused
+        );
+
+        require!(self.referrer.claimable > 0, GoldRushError::NothingToClaim);
+
+        require_keys_eq!(
+            self.referral_vault.mint,
+            self.mint.key(),
+            GoldRushError::InvalidMint
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<WithdrawReferralRewards>) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let referrer = &mut ctx.accounts.referrer;
+    let withdraw_amount = referrer.claimable;
+
+    // transfer from referral vault to signer
+    let transfer_accounts = Transfer {
+        from: ctx.accounts.referral_vault.to_account_info(),
+        to: ctx.accounts.referrer_token_account.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    let seeds = &[CONFIG_SEED.as_bytes(), &[ctx.accounts.config.bump]];
+    let signer = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        signer,
+    );
+    transfer(transfer_ctx, withdraw_amount)?;
+
+    // set fields
+    referrer.claimable = 0;
+    referrer.total_withdrawn = referrer
+        .total_withdrawn
+        .checked_add(withdraw_amount)
+        .ok_or(GoldRushError::Overflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_ACCOUNT_SEED.as_bytes(), signer.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawStake<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            matches!(
+                self.config.status,
+                ProgramStatus::Active | ProgramStatus::EmergencyPaused
+            ),
+            GoldRushError::ProgramPaused
+        );
+
+        require!(
+            self.stake_account.unstaking_amount > 0,
+            GoldRushError::NothingToWithdraw
+        );
+
+        require!(
+            Clock::get()?.unix_timestamp >= self.stake_account.unstake_available_at,
+            GoldRushError::UnstakeCooldownActive
+        );
+
+        require_keys_eq!(
+            self.stake_vault.mint,
+            self.mint.key(),
+            GoldRushError::InvalidMint
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<WithdrawStake>) -> Result<()> {
+    // validate
+    ctx.accounts.validate()?;
+
+    let stake_account = &mut ctx.accounts.stake_account;
+    let withdraw_amount = stake_account.unstaking_amount;
+
+    // transfer from stake vault to signer
+    let transfer_accounts = Transfer {
+        from: ctx.accounts.stake_vault.to_account_info(),
+        to: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    let seeds = &[CONFIG_SEED.as_bytes(), &[ctx.accounts.config.bump]];
+    let signer = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        signer,
+    );
+    transfer(transfer_ctx, withdraw_amount)?;
+
+    // set fields
+    stake_account.unstaking_amount = 0;
+
+    Ok(())
+}
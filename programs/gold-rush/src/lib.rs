@@ -52,32 +52,8 @@ pub mod gold_rush {
         )
     }
 
-    pub fn update_config(
-        ctx: Context<UpdateConfig>,
-        new_admin: Option<Pubkey>,
-        new_keeper_authorities: Option<Vec<Pubkey>>,
-        new_token_mint: Option<Pubkey>,
-        new_treasury: Option<Pubkey>,
-        new_single_asset_feed_id: Option<[u8; 32]>,
-        new_max_price_update_age_secs: Option<u64>,
-        new_fee_single_asset_bps: Option<u16>,
-        new_fee_group_battle_bps: Option<u16>,
-        new_min_bet_amount: Option<u64>,
-        new_bet_cutoff_window_secs: Option<i64>,
-    ) -> Result<()> {
-        update_config::handler(
-            ctx,
-            new_admin,
-            new_keeper_authorities,
-            new_token_mint,
-            new_treasury,
-            new_single_asset_feed_id,
-            new_max_price_update_age_secs,
-            new_fee_single_asset_bps,
-            new_fee_group_battle_bps,
-            new_min_bet_amount,
-            new_bet_cutoff_window_secs,
-        )
+    pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+        update_config::handler(ctx, params)
     }
 
     pub fn program_pause(ctx: Context<ProgramPause>) -> Result<()> {
@@ -101,19 +77,32 @@ pub mod gold_rush {
         market_type: MarketType,
         start_time: i64,
         end_time: i64,
+        aggregation: GroupAggregation,
+        auto_distribute: bool,
+        is_jackpot_round: bool,
     ) -> Result<()> {
-        create_round::handler(ctx, market_type, start_time, end_time)
+        create_round::handler(
+            ctx,
+            market_type,
+            start_time,
+            end_time,
+            aggregation,
+            auto_distribute,
+            is_jackpot_round,
+        )
     }
 
     pub fn insert_group_asset(ctx: Context<InsertGroupAsset>, symbol: [u8; 8]) -> Result<()> {
         insert_group_asset::handler(ctx, symbol)
     }
 
-    pub fn insert_asset(ctx: Context<InsertAsset>, symbol: [u8; 8]) -> Result<()> {
-        insert_asset::handler(ctx, symbol)
+    pub fn insert_asset(ctx: Context<InsertAsset>, symbol: [u8; 8], weight_bps: u16) -> Result<()> {
+        insert_asset::handler(ctx, symbol, weight_bps)
     }
 
-    pub fn cancel_round<'info>(ctx: Context<'_, '_, '_, 'info, CancelRound<'info>>) -> Result<()> {
+    pub fn cancel_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelRound<'info>>,
+    ) -> Result<()> {
         cancel_round::handler(ctx)
     }
 
@@ -133,12 +122,48 @@ pub mod gold_rush {
         start_round::handler(ctx)
     }
 
-    pub fn place_bet(ctx: Context<PlaceBet>, amount: u64, direction: BetDirection) -> Result<()> {
-        place_bet::handler(ctx, amount, direction)
+    pub fn place_bet(
+        ctx: Context<PlaceBet>,
+        amount: u64,
+        direction: BetDirection,
+        min_weight: Option<u64>,
+        max_timestamp: Option<i64>,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        place_bet::handler(ctx, amount, direction, min_weight, max_timestamp, referrer)
     }
 
-    pub fn withdraw_bet(ctx: Context<WithdrawBet>) -> Result<()> {
-        withdraw_bet::handler(ctx)
+    pub fn place_bet_with_voucher(
+        ctx: Context<PlaceBetWithVoucher>,
+        direction: BetDirection,
+        min_weight: Option<u64>,
+        max_timestamp: Option<i64>,
+    ) -> Result<()> {
+        place_bet_with_voucher::handler(ctx, direction, min_weight, max_timestamp)
+    }
+
+    pub fn increase_bet(ctx: Context<IncreaseBet>, amount: u64) -> Result<()> {
+        increase_bet::handler(ctx, amount)
+    }
+
+    pub fn withdraw_bet(ctx: Context<WithdrawBet>, amount: Option<u64>) -> Result<()> {
+        withdraw_bet::handler(ctx, amount)
+    }
+
+    pub fn cash_out_bet(ctx: Context<CashOutBet>) -> Result<()> {
+        cash_out_bet::handler(ctx)
+    }
+
+    pub fn get_implied_odds(ctx: Context<GetImpliedOdds>) -> Result<ImpliedOdds> {
+        get_implied_odds::handler(ctx)
+    }
+
+    pub fn preview_bet(
+        ctx: Context<PreviewBet>,
+        amount: u64,
+        direction: BetDirection,
+    ) -> Result<BetPreview> {
+        preview_bet::handler(ctx, amount, direction)
     }
 
     pub fn capture_end_price(ctx: Context<CaptureEndPrice>) -> Result<()> {
@@ -153,6 +178,10 @@ pub mod gold_rush {
         finalize_end_groups::handler(ctx)
     }
 
+    pub fn finalize_end_assets(ctx: Context<FinalizeEndAssets>) -> Result<()> {
+        finalize_end_assets::handler(ctx)
+    }
+
     pub fn settle_single_round<'info>(
         ctx: Context<'_, '_, 'info, 'info, SettleSingleRound<'info>>,
     ) -> Result<()> {
@@ -163,7 +192,86 @@ pub mod gold_rush {
         settle_group_round::handler(ctx)
     }
 
+    pub fn preview_reward(ctx: Context<PreviewReward>) -> Result<RewardPreview> {
+        preview_reward::handler(ctx)
+    }
+
     pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
         claim_reward::handler(ctx)
     }
+
+    pub fn claim_position_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimPositionRewards<'info>>,
+    ) -> Result<()> {
+        claim_position_rewards::handler(ctx)
+    }
+
+    pub fn claim_rewards_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimRewardsBatch<'info>>,
+    ) -> Result<()> {
+        claim_rewards_batch::handler(ctx)
+    }
+
+    pub fn distribute_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeRewards<'info>>,
+    ) -> Result<()> {
+        distribute_rewards::handler(ctx)
+    }
+
+    pub fn sweep_unclaimed_rewards(ctx: Context<SweepUnclaimedRewards>) -> Result<()> {
+        sweep_unclaimed_rewards::handler(ctx)
+    }
+
+    pub fn initialize_jackpot(ctx: Context<InitializeJackpot>) -> Result<()> {
+        initialize_jackpot::handler(ctx)
+    }
+
+    pub fn initialize_keeper_rewards(ctx: Context<InitializeKeeperRewards>) -> Result<()> {
+        initialize_keeper_rewards::handler(ctx)
+    }
+
+    pub fn claim_keeper_rewards(ctx: Context<ClaimKeeperRewards>) -> Result<()> {
+        claim_keeper_rewards::handler(ctx)
+    }
+
+    pub fn initialize_referrals(ctx: Context<InitializeReferrals>) -> Result<()> {
+        initialize_referrals::handler(ctx)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        register_referrer::handler(ctx)
+    }
+
+    pub fn withdraw_referral_rewards(ctx: Context<WithdrawReferralRewards>) -> Result<()> {
+        withdraw_referral_rewards::handler(ctx)
+    }
+
+    pub fn initialize_staking(ctx: Context<InitializeStaking>) -> Result<()> {
+        initialize_staking::handler(ctx)
+    }
+
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        stake::handler(ctx, amount)
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        unstake::handler(ctx, amount)
+    }
+
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        withdraw_stake::handler(ctx)
+    }
+
+    pub fn initialize_promotions(ctx: Context<InitializePromotions>) -> Result<()> {
+        initialize_promotions::handler(ctx)
+    }
+
+    pub fn mint_voucher(
+        ctx: Context<MintVoucher>,
+        user: Pubkey,
+        amount: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        mint_voucher::handler(ctx, user, amount, expires_at)
+    }
 }
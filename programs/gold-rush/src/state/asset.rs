@@ -11,9 +11,11 @@ pub struct Asset {
 
     // --- State ---
     pub symbol: [u8; 8],
+    pub weight_bps: u16,
     pub start_price: Option<u64>,
     pub final_price: Option<u64>,
-    pub growth_rate_bps: Option<i64>,
+    pub growth_rate_e8: Option<i64>,
+    pub ranked: bool,
 
     // --- Metadata ---
     pub created_at: i64,
@@ -9,15 +9,21 @@ pub struct Bet {
     pub round: Pubkey,         // The round this bet is associated with.
     pub bettor: Pubkey,        // The address of the player placing the bet.
     pub group: Option<Pubkey>, // The group this bet is associated with (GroupBattle only).
+    pub asset: Option<Pubkey>, // The asset this bet is associated with (AssetBattle only).
 
     // --- Bet Info ---
-    pub amount: u64,             // The amount of GRT bet.
-    pub direction: BetDirection, // The type of bet (Up, Down, PercentageChange).
-    pub claimed: bool,           // Whether the reward has been claimed.
-    pub weight: u64,             // The weight of the bet (for reward calculation).
+    pub amount: u64,              // The amount of GRT bet.
+    pub direction: BetDirection,  // The type of bet (Up, Down, PercentageChange).
+    pub claimed: bool,            // Whether the reward has been claimed.
+    pub weight: u64,              // The weight of the bet (for reward calculation).
+    pub cash_out_amount: u64,     // The amount paid out by an early cash-out (0 if not cashed out).
+    pub referrer: Option<Pubkey>, // The referrer authority named when the bet was placed.
+    pub fee_discount_bps: u16,    // The staking fee discount snapshotted when the bet was placed.
+    pub is_promotional: bool,     // Whether the stake was drawn from the promo vault by a voucher.
 
     // --- State ---
-    pub status: BetStatus, // The status of the bet (Pending, Won, Lost).
+    pub status: BetStatus, // The status of the bet (Pending, Won, Lost, Draw, CashedOut).
+    pub loss_rebate: u64,  // The principal refunded at claim if the bet lost (set at settlement).
 
     // --- Metadata ---
     pub created_at: i64, // The timestamp when the bet was placed.
@@ -12,25 +12,54 @@ pub struct Config {
     // --- Token & Treasury ---
     pub token_mint: Pubkey, // The Gold Rush Token (GRT) used for betting.
     pub treasury: Pubkey,   // The address where the fees are sent.
+    pub keeper_reward_pool: Pubkey, // The token account receiving the keeper pool leg of fees.
     pub single_asset_feed_id: [u8; 32], // The Pyth feed id for the single asset game.
     pub max_price_update_age_secs: u64, // The maximum age of the price update in seconds.
 
     // --- Fee Config ---
     pub fee_single_asset_bps: u16, // The fee percentage charged on bets based on Single Asset.
     pub fee_group_battle_bps: u16, // The fee percentage charged on bets based on Group Battle.
+    pub early_exit_fee_bps: u16, // The fee percentage charged on withdrawn bet amounts before cutoff.
+    pub cash_out_haircut_bps: u16, // The haircut on the marked value of a cash-out after cutoff (0 = disabled).
+    pub jackpot_fee_bps: u16, // The share of collected fees routed to the jackpot, in bps of the fee.
+    pub referral_fee_bps: u16, // The share of a referred bet's fee accrued to its referrer, in bps of the fee.
+    pub fee_schedule: FeeSchedule, // How the rest of the fees is split between treasury, keeper pool and burn.
 
     // --- Betting Rules ---
-    pub min_bet_amount: u64,         // The minimum bet amount.
-    pub bet_cutoff_window_secs: i64, // Window before end_time when betting closes.
+    pub min_bet_amount: u64,           // The minimum bet amount.
+    pub bet_cutoff_window_secs: i64,   // Window before end_time when betting closes.
+    pub max_bet_amount: u64,           // The maximum amount of a single bet (0 = no cap).
+    pub max_user_stake_per_round: u64, // The maximum total stake of one bettor in a round (0 = no cap).
+    pub max_total_pool: u64,           // The maximum total pool of new rounds (0 = no cap).
 
     // --- Reward Calculations ---
     pub min_time_factor_bps: u16, // The minimum time factor in basis points.
     pub max_time_factor_bps: u16, // The maximum time factor in basis points.
     pub default_direction_factor_bps: u16, // The default direction factor in basis points.
 
+    // --- Settlement Rules ---
+    pub tie_break_rule: TieBreakRule, // How exact growth ties are resolved for new rounds.
+    pub draw_band_bps: u16, // Single-asset price moves below this (in bps) settle as a full draw for new rounds.
+    pub claim_window_secs: i64, // Time after settlement during which rewards of new rounds can be claimed (0 = no deadline).
+    pub loss_rebate_bps: u16, // The share of a losing bet's principal it can claim back in new rounds (0 = disabled).
+
+    // --- Keeper Incentives ---
+    pub keeper_bounty_per_unit: u64, // The GRT a keeper earns per asset captured, group finalized or bet settled (0 = disabled).
+
+    // --- Staking ---
+    #[max_len(MAX_FEE_DISCOUNT_TIERS)]
+    pub fee_discount_tiers: Vec<FeeDiscountTier>, // Fee discounts by staked GRT, ascending by min_stake (empty = no discounts).
+    pub unstake_cooldown_secs: i64, // Time unstaked GRT stays locked before it can be withdrawn.
+
     // --- Global State ---
     pub status: ProgramStatus, // Overall contract status (Active / Paused / EmergencyPaused)
     pub current_round_counter: u64, // Incremental counter for new round IDs
+    pub current_voucher_counter: u64, // Incremental counter for new voucher IDs
+    pub jackpot_enabled: bool, // Whether the jackpot PDA has been initialized
+    pub keeper_rewards_enabled: bool, // Whether the keeper rewards PDA has been initialized
+    pub referrals_enabled: bool, // Whether the referral vault has been initialized
+    pub staking_enabled: bool, // Whether the stake vault has been initialized
+    pub promotions_enabled: bool, // Whether the promo vault has been initialized
 
     // --- Metadata ---
     pub version: u8, // The version of the contract.
@@ -1,3 +1,4 @@
+use crate::constants::*;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -10,14 +11,20 @@ pub struct GroupAsset {
     // --- State ---
     pub symbol: [u8; 8],
     pub total_assets: u64,
+    pub total_start_price: u64,
     pub total_final_price: u64,
-    pub total_growth_rate_bps: i64,
+    pub total_growth_rate_e8: i64,
+    pub total_weight_bps: u64,
+    pub total_weighted_growth_rate_e8: i64,
+    #[max_len(MAX_ASSETS_IN_GROUP)]
+    pub growth_rates_e8: Vec<i64>,
     pub captured_start_price_assets: u64,
     pub captured_end_price_assets: u64,
-    pub avg_growth_rate_bps: Option<i64>,
+    pub avg_growth_rate_e8: Option<i64>,
     pub finalized_start_price_assets: u64,
     pub finalized_end_price_assets: u64,
     pub settled_assets: u64,
+    pub direction_totals: DirectionTotals,
 
     // --- Metadata ---
     pub created_at: i64,
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Jackpot {
+    // --- Identity ---
+    pub vault: Pubkey,  // The token account holding the jackpot.
+    pub vault_bump: u8, // A bump seed for vault PDA.
+
+    // --- State ---
+    pub total_funded: u64, // The total GRT added to the jackpot (fee slices and no-winner rounds).
+    pub total_paid_out: u64, // The total GRT paid into the reward pool of jackpot rounds.
+
+    // --- Metadata ---
+    pub created_at: i64, // The timestamp when the jackpot was initialized.
+    pub bump: u8,        // A bump seed for PDA.
+}
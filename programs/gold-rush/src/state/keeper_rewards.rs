@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct KeeperRewards {
+    // --- Identity ---
+    pub vault: Pubkey,  // The token account holding the keeper bounties.
+    pub vault_bump: u8, // A bump seed for vault PDA.
+
+    // --- State ---
+    pub total_paid_out: u64, // The total GRT paid to keepers.
+
+    // --- Metadata ---
+    pub created_at: i64, // The timestamp when the keeper rewards were initialized.
+    pub bump: u8,        // A bump seed for PDA.
+}
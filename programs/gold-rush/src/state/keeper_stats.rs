@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct KeeperStats {
+    // --- Identity ---
+    pub keeper: Pubkey, // The keeper these earnings belong to.
+
+    // --- State ---
+    pub units_processed: u64, // The assets captured, groups finalized and bets settled by this keeper.
+    pub total_earned: u64,    // The total bounty earned by this keeper.
+    pub total_claimed: u64,   // The part of total_earned already paid out.
+
+    // --- Metadata ---
+    pub bump: u8, // A bump seed for PDA.
+}
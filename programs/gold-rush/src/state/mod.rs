@@ -2,10 +2,24 @@ pub mod asset;
 pub mod bet;
 pub mod config;
 pub mod group_asset;
+pub mod jackpot;
+pub mod keeper_rewards;
+pub mod keeper_stats;
+pub mod referrer;
 pub mod round;
+pub mod stake_account;
+pub mod user_round_position;
+pub mod voucher;
 
 pub use asset::*;
 pub use bet::*;
 pub use config::*;
 pub use group_asset::*;
+pub use jackpot::*;
+pub use keeper_rewards::*;
+pub use keeper_stats::*;
+pub use referrer::*;
 pub use round::*;
+pub use stake_account::*;
+pub use user_round_position::*;
+pub use voucher::*;
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Referrer {
+    // --- Identity ---
+    pub authority: Pubkey, // The wallet that registered as referrer and withdraws its earnings.
+
+    // --- State ---
+    pub referred_bets: u64, // The number of settled bets that named this referrer.
+    pub claimable: u64,     // The earnings not withdrawn yet.
+    pub total_earned: u64,  // The total share of fees accrued to this referrer.
+    pub total_withdrawn: u64, // The part of total_earned already withdrawn.
+
+    // --- Metadata ---
+    pub created_at: i64, // The timestamp when the referrer registered.
+    pub bump: u8,        // A bump seed for PDA.
+}
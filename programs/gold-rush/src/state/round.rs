@@ -12,26 +12,55 @@ pub struct Round {
     pub vault: Pubkey, // The vault account holding the bets for this round.
     pub vault_bump: u8, // A bump seed for vault PDA.
     pub market_type: MarketType, // The type of market (GoldPrice, StockPrice).
+    pub aggregation: GroupAggregation, // How group growth is aggregated from assets (Mean, Median, IndexReturn).
+    pub tie_break_rule: TieBreakRule, // How exact growth ties are resolved (snapshot of config at creation).
+    pub draw_band_bps: u16, // Single-asset price moves below this (in bps) are a full draw (snapshot of config at creation).
+    pub max_total_pool: u64, // The maximum total pool of this round, 0 = no cap (snapshot of config at creation).
+    pub claim_window_secs: i64, // Time after settled_at during which rewards can be claimed, 0 = no deadline (snapshot of config at creation).
+    pub loss_rebate_bps: u16, // The share of a losing bet's principal refunded at claim (snapshot of config at creation).
+    pub is_jackpot_round: bool, // Whether the jackpot is paid into this round's reward pool at settlement.
+    pub auto_distribute: bool, // Whether keepers push payouts to bettors with distribute_rewards after settlement.
 
     // --- State ---
     pub status: RoundStatus, // The current status of the round (Scheduled, Active, PendingSettlement, Ended).
     pub start_price: Option<u64>, // Only for single-asset markets
     pub final_price: Option<u64>, // Only for single-asset markets
     pub total_pool: u64,     // The total amount of GRT bet in this round.
+    pub total_stake: u64, // The amount staked by bets still in the pool (excludes withdrawn and cashed-out bets).
+    pub direction_totals: DirectionTotals, // The amount and weight staked per direction bucket (Up, Down, PercentageUp, PercentageDown, PercentageFlat, Underperform).
     pub total_bets: u64,     // The total number of bets placed in this round.
     pub total_fee_collected: u64, // The total fees collected for this round.
     pub total_reward_pool: u64, // The total reward pool after deducting fees.
+    pub fees_locked: bool, // Whether the fee and reward pool were locked by the first settlement batch.
+    pub jackpot_payout: u64, // The jackpot added to the reward pool at settlement (jackpot rounds only).
+    pub jackpot_contribution: u64, // The GRT sent to the jackpot (fee slice, plus the reward pool when nobody won).
+    pub fee_burned: u64, // The GRT burned from the fee at settlement.
+    pub referred_stake: u64, // The stake of bets with a referrer still in the pool.
+    pub referral_fee: u64, // The GRT of the fee moved to the referral vault for referrers at settlement.
+    pub referral_accrued: u64, // The part of referral_fee already credited to referrers during settlement.
+    pub discounted_stake: u64, // The stake of bets still in the pool weighted by their fee discount.
+    pub fee_rebate: u64, // The part of the fee kept in the vault and rebated to staking bettors at claim.
+    pub loss_rebate: u64, // The part of the reward pool set aside for Lost bets' loss rebates during settlement.
     pub winners_weight: u64, // The total weight of winning bets (for reward calculation). Default to 0 if no winners.
     pub settled_bets: u64,   // Number of bets that have been processed (for incremental settlement)
     pub cancelled_bets: u64, // Number of bets that have been cancelled in this round.
     #[max_len(MAX_WINNER_GROUP_IDS)]
     pub winner_group_ids: Vec<u64>, // The IDs of the groups that won the round.
+    #[max_len(MAX_LOSER_GROUP_IDS)]
+    pub loser_group_ids: Vec<u64>, // The IDs of the groups with the lowest avg growth (Underperform bets).
     pub total_groups: u64,   // The total number of groups created in this round.
     pub captured_start_groups: u64, // The total number of group assets already captured the start price.
     pub captured_end_groups: u64, // The total number of group assets already captured the end price.
+    pub total_assets: u64, // The total number of assets inserted across all groups in this round.
+    pub ranked_assets: u64, // The total number of assets already ranked for the best asset (AssetBattle only).
+    pub max_asset_growth_rate_e8: Option<i64>, // The highest asset growth seen while ranking (AssetBattle only).
+    pub min_asset_growth_rate_e8: Option<i64>, // The lowest asset growth seen while ranking; equal to the highest when every asset tied (AssetBattle only).
+    #[max_len(MAX_WINNER_ASSETS)]
+    pub winner_assets: Vec<Pubkey>, // The Asset PDAs with the highest growth (AssetBattle only).
 
     // --- Metadata ---
     pub created_at: i64,         // The timestamp when the round was created.
     pub settled_at: Option<i64>, // The timestamp when the round was settled.
+    pub unclaimed_swept: u64,    // The unclaimed rewards moved to the treasury once the claim window expired.
     pub bump: u8,                // A bump seed for PDA.
 }
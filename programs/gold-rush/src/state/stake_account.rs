@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakeAccount {
+    // --- Identity ---
+    pub owner: Pubkey, // The wallet that staked the GRT.
+
+    // --- State ---
+    pub staked_amount: u64, // The GRT currently staked (counts towards fee discounts).
+    pub unstaking_amount: u64, // The GRT unstaked and waiting for the cooldown.
+    pub unstake_available_at: i64, // The timestamp when the unstaking GRT can be withdrawn.
+
+    // --- Metadata ---
+    pub created_at: i64, // The timestamp when the wallet first staked.
+    pub bump: u8,        // A bump seed for PDA.
+}
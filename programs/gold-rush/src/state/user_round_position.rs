@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserRoundPosition {
+    // --- Identity ---
+    pub round: Pubkey,  // The round this position belongs to.
+    pub bettor: Pubkey, // The bettor owning this position.
+
+    // --- State ---
+    pub total_stake: u64, // The amount the bettor currently has staked in this round.
+    pub total_weight: u64, // The weight the bettor currently has in this round.
+    // Unbounded: sized for no ids at init, the account grows by one id per bet placed
+    #[max_len(0)]
+    pub bet_ids: Vec<u64>, // The IDs of the bettor's bets in this round (withdrawn bets are removed).
+
+    // --- Metadata ---
+    pub created_at: i64, // The timestamp when the first bet was placed.
+    pub bump: u8,        // A bump seed for PDA.
+}
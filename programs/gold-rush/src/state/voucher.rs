@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Voucher {
+    // --- Identity ---
+    pub id: u64, // Unique identifier of the voucher (incremental from config.current_voucher_counter).
+    pub user: Pubkey, // The wallet allowed to redeem the voucher.
+
+    // --- Voucher Info ---
+    pub amount: u64, // The GRT staked from the promo vault when the voucher is redeemed.
+    pub expires_at: i64, // The timestamp after which the voucher can no longer be redeemed.
+
+    // --- State ---
+    pub redeemed: bool, // Whether the voucher has been used for a bet.
+
+    // --- Metadata ---
+    pub created_at: i64, // The timestamp when the voucher was minted.
+    pub bump: u8,        // A bump seed for PDA.
+}
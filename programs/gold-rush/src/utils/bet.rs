@@ -1,4 +1,5 @@
-use crate::{constants::*};
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
 
 pub fn is_bet_winner(
     bet_direction: BetDirection,
@@ -15,6 +16,183 @@ pub fn is_bet_winner(
             Some((percent > 0 && price_change > 0)
               || (percent < 0 && price_change < 0))
         }
+        // Underperform only ranks groups; it never wins a single-asset round
+        BetDirection::Underperform => Some(false),
+    }
+}
+
+pub fn calculate_bet_weight(
+    amount: u64,
+    direction_factor: u64,
+    time_factor: u64
+) -> Result<u64> {
+    // weight = amount * direction_factor * time_factor / (10_000 * 10_000)
+    let numerator = (amount as u128)
+        .checked_mul(direction_factor as u128)
+        .ok_or(GoldRushError::Overflow)?
+        .checked_mul(time_factor as u128)
+        .ok_or(GoldRushError::Overflow)?;
+    let denominator = (HUNDRED_PERCENT_BPS as u128)
+        .checked_mul(HUNDRED_PERCENT_BPS as u128)
+        .ok_or(GoldRushError::Overflow)?;
+    let weight = numerator
+        .checked_div(denominator)
+        .ok_or(GoldRushError::Underflow)?;
+
+    u64::try_from(weight).map_err(|_| GoldRushError::Overflow.into())
+}
+
+/// Factors and weight of `amount` bet on `direction` in `round` at `now`
+pub struct BetWeight {
+    pub direction_factor: u64,
+    pub time_factor: u64,
+    pub weight: u64,
+}
+
+/// The weight `place_bet`, `increase_bet` and `preview_bet` give a stake placed at `now`
+pub fn calculate_round_bet_weight(
+    config: &Config,
+    round: &Round,
+    direction: &BetDirection,
+    amount: u64,
+    now: i64
+) -> Result<BetWeight> {
+    let round_duration = round
+        .end_time
+        .checked_sub(round.start_time)
+        .ok_or(GoldRushError::Underflow)?;
+    let time_elapsed = now
+        .checked_sub(round.start_time)
+        .ok_or(GoldRushError::Underflow)?;
+    let direction_factor = calculate_direction_factor(
+        &round.market_type,
+        direction,
+        config.default_direction_factor_bps as u64,
+    )?;
+    let time_factor = calculate_time_factor(
+        &round.market_type,
+        time_elapsed,
+        config.min_time_factor_bps as u64,
+        config.max_time_factor_bps as u64,
+        round_duration,
+    )?;
+    let weight = calculate_bet_weight(amount, direction_factor, time_factor)?;
+
+    Ok(BetWeight {
+        direction_factor,
+        time_factor,
+        weight,
+    })
+}
+
+pub fn apply_draw_band(
+    price_change: i64,
+    start_price: u64,
+    draw_band_bps: u16
+) -> Result<i64> {
+    // |change| / start < band  <=>  |change| * 10_000 < band * start
+    let scaled_change = (price_change.unsigned_abs() as u128)
+        .checked_mul(HUNDRED_PERCENT_BPS as u128)
+        .ok_or(GoldRushError::Overflow)?;
+    let scaled_band = (draw_band_bps as u128)
+        .checked_mul(start_price as u128)
+        .ok_or(GoldRushError::Overflow)?;
+
+    if scaled_change < scaled_band {
+        return Ok(0);
+    }
+
+    Ok(price_change)
+}
+
+pub fn calculate_cash_out_amount(
+    amount: u64,
+    weight: u64,
+    total_pool: u64,
+    winning_side_weight: u64,
+    fee_bps: u16,
+    is_winner: Option<bool>,
+    haircut_bps: u16
+) -> Result<u64> {
+    // Mark the bet as if the round settled now
+    let mark_value = match is_winner {
+        // share by weight of the pool net of the settlement fee, as settlement would pay it
+        Some(true) => {
+            require!(winning_side_weight >= weight, GoldRushError::RewardCalculationError);
+            let fee_amount = (total_pool as u128)
+                .checked_mul(fee_bps as u128)
+                .ok_or(GoldRushError::Overflow)?
+                .checked_div(HUNDRED_PERCENT_BPS as u128)
+                .ok_or(GoldRushError::Underflow)?;
+            let reward_pool = (total_pool as u128)
+                .checked_sub(fee_amount)
+                .ok_or(GoldRushError::Underflow)?;
+            (weight as u128)
+                .checked_mul(reward_pool)
+                .ok_or(GoldRushError::Overflow)?
+                .checked_div(winning_side_weight as u128)
+                .ok_or(GoldRushError::Underflow)?
+        }
+        // flat: stake back
+        None => amount as u128,
+        // out of the money: nothing to cash out
+        Some(false) => return Err(GoldRushError::CashOutNotAvailable.into()),
+    };
+
+    let cash_out_amount = mark_value
+        .checked_mul(HUNDRED_PERCENT_BPS.checked_sub(haircut_bps).ok_or(GoldRushError::Underflow)? as u128)
+        .ok_or(GoldRushError::Overflow)?
+        .checked_div(HUNDRED_PERCENT_BPS as u128)
+        .ok_or(GoldRushError::Underflow)?;
+
+    u64::try_from(cash_out_amount).map_err(|_| GoldRushError::Overflow.into())
+}
+
+pub fn calculate_refund_amount(
+    amount: u64,
+    pool: u64,
+    total_stake: u64
+) -> Result<u64> {
+    // Without cash-outs the pool equals the stake and refunds are exact
+    if total_stake == 0 || pool == total_stake {
+        return Ok(amount);
+    }
+
+    // Otherwise cash-out gains/losses are shared pro-rata by stake
+    let refund_amount = (amount as u128)
+        .checked_mul(pool as u128)
+        .ok_or(GoldRushError::Overflow)?
+        .checked_div(total_stake as u128)
+        .ok_or(GoldRushError::Underflow)?;
+
+    u64::try_from(refund_amount).map_err(|_| GoldRushError::Overflow.into())
+}
+
+pub fn calculate_claim_amount(
+    status: &BetStatus,
+    amount: u64,
+    weight: u64,
+    total_reward_pool: u64,
+    winners_weight: u64,
+    total_stake: u64
+) -> Result<u64> {
+    match status {
+        // share of the reward pool by weight
+        BetStatus::Won => {
+            require!(winners_weight > 0, GoldRushError::RewardCalculationError);
+            (weight as u128)
+                .checked_mul(total_reward_pool as u128)
+                .and_then(|intermediate_result| {
+                    intermediate_result.checked_div(winners_weight as u128)
+                })
+                .map(|final_result| final_result as u64)
+                .ok_or(GoldRushError::Underflow.into())
+        }
+        // Refund stake, sharing any cash-out gains/losses pro-rata
+        BetStatus::Draw => calculate_refund_amount(amount, total_reward_pool, total_stake),
+        BetStatus::Pending => Err(GoldRushError::ClaimPendingBet.into()),
+        BetStatus::Lost => Err(GoldRushError::ClaimLosingBet.into()),
+        BetStatus::CashedOut => Err(GoldRushError::BetNotWonOrDraw.into()),
     }
 }
 
@@ -38,6 +216,8 @@ mod tests {
         assert_eq!(is_bet_winner(BetDirection::Down, 1), Some(false));
         assert_eq!(is_bet_winner(BetDirection::PercentageChangeBps(1), -1), Some(false));
         assert_eq!(is_bet_winner(BetDirection::PercentageChangeBps(-1), 1), Some(false));
+        assert_eq!(is_bet_winner(BetDirection::Underperform, 1), Some(false));
+        assert_eq!(is_bet_winner(BetDirection::Underperform, -1), Some(false));
     }
 
     #[test]
@@ -46,4 +226,65 @@ mod tests {
         assert_eq!(is_bet_winner(BetDirection::Down, 0), None);
         assert_eq!(is_bet_winner(BetDirection::PercentageChangeBps(0), 0), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_apply_draw_band() {
+        // start 100.000000, band 5 bps = 0.05 (50_000 micro)
+        assert_eq!(apply_draw_band(1, 100_000_000, 5).unwrap(), 0);
+        assert_eq!(apply_draw_band(-49_999, 100_000_000, 5).unwrap(), 0);
+        assert_eq!(apply_draw_band(50_000, 100_000_000, 5).unwrap(), 50_000);
+        assert_eq!(apply_draw_band(-50_000, 100_000_000, 5).unwrap(), -50_000);
+        // band 0 keeps only the exact-zero draw
+        assert_eq!(apply_draw_band(1, 100_000_000, 0).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_calculate_bet_weight() {
+        // 1.0x direction, 1.0x time keeps the amount
+        assert_eq!(calculate_bet_weight(10_000_000, 10_000, 10_000).unwrap(), 10_000_000);
+        // 1.1x direction, 2.0x time
+        assert_eq!(calculate_bet_weight(10_000_000, 11_000, 20_000).unwrap(), 22_000_000);
+        // top-ups add up to the weight of a single bet at the same factors
+        let split = calculate_bet_weight(4_000_000, 11_000, 15_000).unwrap()
+            + calculate_bet_weight(6_000_000, 11_000, 15_000).unwrap();
+        assert_eq!(split, calculate_bet_weight(10_000_000, 11_000, 15_000).unwrap());
+    }
+
+    #[test]
+    fn test_calculate_cash_out_amount() {
+        // winning side holds 400 of the weight in a 1_000 pool with a 10% fee:
+        // mark = 100 * 900 / 400 = 225, 10% haircut
+        assert_eq!(calculate_cash_out_amount(100, 100, 1_000, 400, 1_000, Some(true), 1_000).unwrap(), 202);
+        // a late bet with half the weight marks at half the value
+        assert_eq!(calculate_cash_out_amount(100, 50, 1_000, 400, 1_000, Some(true), 1_000).unwrap(), 100);
+        // never more than settlement would pay the bet, even with no haircut
+        assert_eq!(calculate_cash_out_amount(100, 100, 1_000, 400, 1_000, Some(true), 0).unwrap(), 225);
+        // flat: stake back minus haircut
+        assert_eq!(calculate_cash_out_amount(10_000, 10_000, 100_000, 40_000, 1_000, None, 1_000).unwrap(), 9_000);
+        // out of the money
+        assert!(calculate_cash_out_amount(10, 10, 100, 40, 1_000, Some(false), 1_000).is_err());
+    }
+
+    #[test]
+    fn test_calculate_refund_amount() {
+        // no cash-outs: exact refund
+        assert_eq!(calculate_refund_amount(10, 100, 100).unwrap(), 10);
+        // pool short of stake after a winning cash-out
+        assert_eq!(calculate_refund_amount(10, 80, 100).unwrap(), 8);
+        // haircut surplus is shared with remaining bettors
+        assert_eq!(calculate_refund_amount(10, 110, 100).unwrap(), 11);
+    }
+
+    #[test]
+    fn test_calculate_claim_amount() {
+        // winner: 25% of the winners' weight gets 25% of the reward pool
+        assert_eq!(calculate_claim_amount(&BetStatus::Won, 10, 250, 1_000, 1_000, 100).unwrap(), 250);
+        // draw: stake back
+        assert_eq!(calculate_claim_amount(&BetStatus::Draw, 10, 250, 100, 0, 100).unwrap(), 10);
+        // nothing to claim on losing or pending bets
+        assert!(calculate_claim_amount(&BetStatus::Lost, 10, 250, 1_000, 1_000, 100).is_err());
+        assert!(calculate_claim_amount(&BetStatus::Pending, 10, 250, 1_000, 1_000, 100).is_err());
+        // no winners weight means no reward can be computed
+        assert!(calculate_claim_amount(&BetStatus::Won, 10, 250, 1_000, 0, 100).is_err());
+    }
+}
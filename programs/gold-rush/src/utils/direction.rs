@@ -7,7 +7,9 @@ pub fn calculate_direction_factor(
     default_direction_factor_bps: u64,
 ) -> Result<u64> {
     match bet_direction {
-        BetDirection::Up | BetDirection::Down => Ok(default_direction_factor_bps),
+        BetDirection::Up | BetDirection::Down | BetDirection::Underperform => {
+            Ok(default_direction_factor_bps)
+        }
         BetDirection::PercentageChangeBps(percent) => {
             if *percent == 0 {
                 return Ok(default_direction_factor_bps);
@@ -31,7 +33,7 @@ pub fn calculate_direction_factor(
                         .try_into() // Convert u128 back to u64
                         .map_err(|_| GoldRushError::Overflow.into())
                 }
-                MarketType::GroupBattle => {
+                MarketType::GroupBattle | MarketType::AssetBattle => {
                     let result = default_direction_factor_bps
                         .checked_add(abs_percent_bps)
                         .ok_or(GoldRushError::Overflow)?;
@@ -43,6 +45,18 @@ pub fn calculate_direction_factor(
     }
 }
 
+/// Pool accounting bucket of a bet direction (index into `DirectionTotals`)
+pub fn direction_bucket(bet_direction: &BetDirection) -> usize {
+    match bet_direction {
+        BetDirection::Up => 0,
+        BetDirection::Down => 1,
+        BetDirection::PercentageChangeBps(percent) if *percent > 0 => 2,
+        BetDirection::PercentageChangeBps(percent) if *percent < 0 => 3,
+        BetDirection::PercentageChangeBps(_) => 4,
+        BetDirection::Underperform => 5,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,6 +75,15 @@ mod tests {
                 .unwrap(),
             default
         );
+        assert_eq!(
+            calculate_direction_factor(
+                &MarketType::GroupBattle,
+                &BetDirection::Underperform,
+                default
+            )
+            .unwrap(),
+            default
+        );
     }
 
     #[test]
@@ -136,4 +159,14 @@ mod tests {
         .unwrap();
         assert_eq!(result, 10_200); // 1.02x
     }
+
+    #[test]
+    fn test_direction_bucket() {
+        assert_eq!(direction_bucket(&BetDirection::Up), 0);
+        assert_eq!(direction_bucket(&BetDirection::Down), 1);
+        assert_eq!(direction_bucket(&BetDirection::PercentageChangeBps(25)), 2);
+        assert_eq!(direction_bucket(&BetDirection::PercentageChangeBps(-25)), 3);
+        assert_eq!(direction_bucket(&BetDirection::PercentageChangeBps(0)), 4);
+        assert_eq!(direction_bucket(&BetDirection::Underperform), 5);
+    }
 }
@@ -0,0 +1,154 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+
+pub fn calculate_growth_rate_e8(start_price: u64, final_price: u64) -> Result<i64> {
+    if start_price == 0 {
+        return Err(GoldRushError::InvalidAssetPrice.into());
+    }
+
+    // Compute signed growth in 1e-8 units with wide arithmetic
+    let numerator: i128 = (final_price as i128)
+        .checked_sub(start_price as i128)
+        .ok_or(GoldRushError::Overflow)?;
+    let growth_rate_e8 = numerator
+        .checked_mul(GROWTH_RATE_PRECISION as i128)
+        .ok_or(GoldRushError::Overflow)?
+        .checked_div(start_price as i128)
+        .ok_or(GoldRushError::Underflow)?;
+
+    i64::try_from(growth_rate_e8).map_err(|_| GoldRushError::Overflow.into())
+}
+
+pub fn calculate_median_growth_rate_e8(growth_rates_e8: &[i64]) -> Result<i64> {
+    if growth_rates_e8.is_empty() {
+        return Err(GoldRushError::GroupAssetNotFullyCapturedEndPrice.into());
+    }
+
+    let mut sorted = growth_rates_e8.to_vec();
+    sorted.sort_unstable();
+
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 1 {
+        return Ok(sorted[mid]);
+    }
+
+    // Even count: mean of the two middle values
+    let sum = (sorted[mid - 1] as i128)
+        .checked_add(sorted[mid] as i128)
+        .ok_or(GoldRushError::Overflow)?;
+    i64::try_from(sum / 2).map_err(|_| GoldRushError::Overflow.into())
+}
+
+pub fn calculate_group_growth_rate_e8(
+    aggregation: &GroupAggregation,
+    group_asset: &GroupAsset,
+) -> Result<i64> {
+    match aggregation {
+        GroupAggregation::Mean => {
+            // Weighted mean for weighted groups, plain mean otherwise
+            if group_asset.total_weight_bps > 0 {
+                group_asset
+                    .total_weighted_growth_rate_e8
+                    .checked_div(group_asset.total_weight_bps as i64)
+                    .ok_or(GoldRushError::Overflow.into())
+            } else {
+                group_asset
+                    .total_growth_rate_e8
+                    .checked_div(group_asset.finalized_end_price_assets as i64)
+                    .ok_or(GoldRushError::Overflow.into())
+            }
+        }
+        GroupAggregation::Median => calculate_median_growth_rate_e8(&group_asset.growth_rates_e8),
+        GroupAggregation::IndexReturn => {
+            calculate_growth_rate_e8(group_asset.total_start_price, group_asset.total_final_price)
+        }
+    }
+}
+
+/// Applies the tie-break rule to the groups tied for the best (or worst) growth; the whole
+/// set is kept when every group tied, so the round settles as a full draw
+pub fn resolve_tied_group_ids(
+    tied_ids: Vec<u64>,
+    tie_break_rule: &TieBreakRule,
+    all_tied: bool,
+) -> Vec<u64> {
+    match tie_break_rule {
+        TieBreakRule::LowestId if !all_tied => tied_ids.iter().min().copied().into_iter().collect(),
+        _ => tied_ids,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_growth_rate_e8() {
+        // +5% = 5_000_000 (1e-8 units)
+        assert_eq!(
+            calculate_growth_rate_e8(100_000, 105_000).unwrap(),
+            5_000_000
+        );
+        // -2.5% = -2_500_000
+        assert_eq!(
+            calculate_growth_rate_e8(100_000, 97_500).unwrap(),
+            -2_500_000
+        );
+        // unchanged
+        assert_eq!(calculate_growth_rate_e8(100_000, 100_000).unwrap(), 0);
+        // zero start price is invalid
+        assert!(calculate_growth_rate_e8(0, 100_000).is_err());
+    }
+
+    #[test]
+    fn test_growth_rate_sub_bps_precision() {
+        // 0.3 bps apart would truncate to the same whole bps
+        let a = calculate_growth_rate_e8(1_000_000, 1_001_000).unwrap();
+        let b = calculate_growth_rate_e8(1_000_000, 1_001_030).unwrap();
+        assert_eq!(a, 100_000);
+        assert_eq!(b, 103_000);
+        assert!(b > a);
+    }
+
+    #[test]
+    fn test_median_odd() {
+        // Outlier 5000 does not move the median
+        assert_eq!(
+            calculate_median_growth_rate_e8(&[5_000, 100, -50]).unwrap(),
+            100
+        );
+    }
+
+    #[test]
+    fn test_median_even() {
+        // Mean of the two middle values: (100 + 200) / 2 = 150
+        assert_eq!(
+            calculate_median_growth_rate_e8(&[300, 100, 200, -400]).unwrap(),
+            150
+        );
+    }
+
+    #[test]
+    fn test_median_empty() {
+        assert!(calculate_median_growth_rate_e8(&[]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_tied_group_ids() {
+        // LowestId keeps only the lowest tied id
+        assert_eq!(
+            resolve_tied_group_ids(vec![3, 1, 2], &TieBreakRule::LowestId, false),
+            vec![1]
+        );
+        // Split keeps every tied id
+        assert_eq!(
+            resolve_tied_group_ids(vec![3, 1], &TieBreakRule::Split, false),
+            vec![3, 1]
+        );
+        // all groups tied: kept whole so the round is a full draw, even under LowestId
+        assert_eq!(
+            resolve_tied_group_ids(vec![1, 2, 3], &TieBreakRule::LowestId, true),
+            vec![1, 2, 3]
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+
+/// Credits the keeper with `bounty_per_unit` for each unit processed by a crank; returns the bounty
+pub fn accrue_keeper_bounty(
+    keeper_stats: &mut KeeperStats,
+    keeper: Pubkey,
+    bump: u8,
+    units: u64,
+    bounty_per_unit: u64,
+) -> Result<u64> {
+    // first crank of this keeper
+    if keeper_stats.keeper == Pubkey::default() {
+        keeper_stats.keeper = keeper;
+        keeper_stats.bump = bump;
+    }
+
+    let bounty = units
+        .checked_mul(bounty_per_unit)
+        .ok_or(GoldRushError::Overflow)?;
+    keeper_stats.units_processed = keeper_stats
+        .units_processed
+        .checked_add(units)
+        .ok_or(GoldRushError::Overflow)?;
+    keeper_stats.total_earned = keeper_stats
+        .total_earned
+        .checked_add(bounty)
+        .ok_or(GoldRushError::Overflow)?;
+
+    Ok(bounty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accrue_keeper_bounty() {
+        let keeper = Pubkey::new_unique();
+        let mut keeper_stats = KeeperStats {
+            keeper: Pubkey::default(),
+            units_processed: 0,
+            total_earned: 0,
+            total_claimed: 0,
+            bump: 0,
+        };
+
+        assert_eq!(
+            accrue_keeper_bounty(&mut keeper_stats, keeper, 254, 3, 100).unwrap(),
+            300
+        );
+        assert_eq!(
+            accrue_keeper_bounty(&mut keeper_stats, Pubkey::new_unique(), 1, 2, 100).unwrap(),
+            200
+        );
+        assert_eq!(keeper_stats.keeper, keeper);
+        assert_eq!(keeper_stats.bump, 254);
+        assert_eq!(keeper_stats.units_processed, 5);
+        assert_eq!(keeper_stats.total_earned, 500);
+
+        assert!(accrue_keeper_bounty(&mut keeper_stats, keeper, 254, u64::MAX, 2).is_err());
+    }
+}
@@ -1,9 +1,25 @@
 pub mod bet;
 pub mod direction;
+pub mod growth;
+pub mod keeper;
+pub mod placement;
+pub mod pool;
+pub mod position;
 pub mod price;
+pub mod promo;
+pub mod settlement;
+pub mod staking;
 pub mod time;
 
 pub use bet::*;
 pub use direction::*;
+pub use growth::*;
+pub use keeper::*;
+pub use placement::*;
+pub use pool::*;
+pub use position::*;
 pub use price::*;
+pub use promo::*;
+pub use settlement::*;
+pub use staking::*;
 pub use time::*;
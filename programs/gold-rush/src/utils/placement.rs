@@ -0,0 +1,176 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+/// Checks a new bet on `direction` can still join `round`, with the market account it needs
+pub fn validate_bet_round(
+    round: &Account<Round>,
+    group_asset: Option<&Account<GroupAsset>>,
+    asset: Option<&Account<Asset>>,
+    direction: &BetDirection,
+) -> Result<()> {
+    require!(
+        round.status == RoundStatus::Active,
+        GoldRushError::RoundNotActive
+    );
+
+    // Enforce bet cutoff rather than the exact end_time to prevent last-second betting
+    require!(
+        Clock::get()?.unix_timestamp < round.bet_cutoff_time,
+        GoldRushError::RoundEnded
+    );
+
+    // For GroupBattle, group_asset must be provided and belong to this round
+    if matches!(round.market_type, MarketType::GroupBattle) {
+        let ga = group_asset.ok_or(GoldRushError::InvalidGroupAssetAccount)?;
+        require_keys_eq!(
+            ga.round,
+            round.key(),
+            GoldRushError::InvalidGroupAssetAccount
+        );
+    }
+
+    // For AssetBattle, asset must be provided and belong to this round
+    if matches!(round.market_type, MarketType::AssetBattle) {
+        let asset = asset.ok_or(GoldRushError::InvalidAssetAccount)?;
+        require_keys_eq!(asset.round, round.key(), GoldRushError::InvalidAssetAccount);
+    }
+
+    // Underperform ranks groups against each other, so it only exists in GroupBattle
+    if !matches!(round.market_type, MarketType::GroupBattle) {
+        require!(
+            !matches!(direction, BetDirection::Underperform),
+            GoldRushError::InvalidBetDirection
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks a new `amount` stake against the minimum bet and the exposure caps
+pub fn validate_bet_amount(
+    config: &Config,
+    round: &Round,
+    user_round_position: &UserRoundPosition,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        amount >= config.min_bet_amount,
+        GoldRushError::BetBelowMinimum
+    );
+
+    // Exposure caps (0 = no cap)
+    if config.max_bet_amount > 0 {
+        require!(
+            amount <= config.max_bet_amount,
+            GoldRushError::BetAboveMaximum
+        );
+    }
+
+    if config.max_user_stake_per_round > 0 {
+        let user_stake = user_round_position
+            .total_stake
+            .checked_add(amount)
+            .ok_or(GoldRushError::Overflow)?;
+        require!(
+            user_stake <= config.max_user_stake_per_round,
+            GoldRushError::UserStakeCapExceeded
+        );
+    }
+
+    if round.max_total_pool > 0 {
+        let total_pool = round
+            .total_pool
+            .checked_add(amount)
+            .ok_or(GoldRushError::Overflow)?;
+        require!(
+            total_pool <= round.max_total_pool,
+            GoldRushError::RoundPoolCapExceeded
+        );
+    }
+
+    Ok(())
+}
+
+/// Records the group or asset a new bet is placed on, as the round's market requires
+pub fn assign_bet_market(
+    bet: &mut Bet,
+    round: &Round,
+    group_asset: Option<&Account<GroupAsset>>,
+    asset: Option<&Account<Asset>>,
+) -> Result<()> {
+    if matches!(round.market_type, MarketType::GroupBattle) {
+        let ga = group_asset.ok_or(GoldRushError::InvalidGroupAssetAccount)?;
+        bet.group = Some(ga.key());
+    }
+
+    if matches!(round.market_type, MarketType::AssetBattle) {
+        let asset = asset.ok_or(GoldRushError::InvalidAssetAccount)?;
+        bet.asset = Some(asset.key());
+    }
+
+    Ok(())
+}
+
+/// Weight of a new `amount` stake on `direction`, rejected below the caller's `min_weight`
+pub fn calculate_placed_bet_weight(
+    config: &Config,
+    round: &Round,
+    direction: &BetDirection,
+    amount: u64,
+    min_weight: Option<u64>,
+) -> Result<u64> {
+    let weight = calculate_round_bet_weight(
+        config,
+        round,
+        direction,
+        amount,
+        Clock::get()?.unix_timestamp,
+    )?
+    .weight;
+
+    // Slippage protection: a delayed tx must not go through on worse terms
+    if let Some(min_weight) = min_weight {
+        require!(weight >= min_weight, GoldRushError::BetWeightBelowMinimum);
+    }
+
+    Ok(weight)
+}
+
+/// Adds a newly placed bet to the round totals and, in GroupBattle, to its group's totals
+pub fn add_bet_to_round(
+    round: &mut Round,
+    group_asset: Option<&mut Account<GroupAsset>>,
+    bet: &Bet,
+) -> Result<()> {
+    round.total_pool = round
+        .total_pool
+        .checked_add(bet.amount)
+        .ok_or(GoldRushError::Overflow)?;
+    round.total_bets = round
+        .total_bets
+        .checked_add(1)
+        .ok_or(GoldRushError::Overflow)?;
+    round.total_stake = round
+        .total_stake
+        .checked_add(bet.amount)
+        .ok_or(GoldRushError::Overflow)?;
+    add_to_direction_totals(
+        &mut round.direction_totals,
+        &bet.direction,
+        bet.amount,
+        bet.weight,
+    )?;
+
+    // set group asset fields
+    if matches!(round.market_type, MarketType::GroupBattle) {
+        let group_asset = group_asset.ok_or(GoldRushError::InvalidGroupAssetAccount)?;
+        add_to_direction_totals(
+            &mut group_asset.direction_totals,
+            &bet.direction,
+            bet.amount,
+            bet.weight,
+        )?;
+    }
+
+    Ok(())
+}
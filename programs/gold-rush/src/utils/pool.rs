@@ -0,0 +1,236 @@
+use crate::{constants::*, error::GoldRushError, utils::direction_bucket};
+use anchor_lang::prelude::*;
+
+pub fn add_to_direction_totals(
+    direction_totals: &mut DirectionTotals,
+    direction: &BetDirection,
+    amount: u64,
+    weight: u64,
+) -> Result<()> {
+    let bucket = direction_bucket(direction);
+    direction_totals.amounts[bucket] = direction_totals.amounts[bucket]
+        .checked_add(amount)
+        .ok_or(GoldRushError::Overflow)?;
+    direction_totals.weights[bucket] = direction_totals.weights[bucket]
+        .checked_add(weight)
+        .ok_or(GoldRushError::Overflow)?;
+
+    Ok(())
+}
+
+pub fn sub_from_direction_totals(
+    direction_totals: &mut DirectionTotals,
+    direction: &BetDirection,
+    amount: u64,
+    weight: u64,
+) -> Result<()> {
+    let bucket = direction_bucket(direction);
+    direction_totals.amounts[bucket] = direction_totals.amounts[bucket]
+        .checked_sub(amount)
+        .ok_or(GoldRushError::Underflow)?;
+    direction_totals.weights[bucket] = direction_totals.weights[bucket]
+        .checked_sub(weight)
+        .ok_or(GoldRushError::Underflow)?;
+
+    Ok(())
+}
+
+/// Amount staked on one side of a single-asset move (Up + PercentageUp, or Down + PercentageDown)
+pub fn calculate_side_amount(direction_totals: &DirectionTotals, is_up: bool) -> Result<u64> {
+    let (direction, percentage) = if is_up { (0, 2) } else { (1, 3) };
+    direction_totals.amounts[direction]
+        .checked_add(direction_totals.amounts[percentage])
+        .ok_or(GoldRushError::Overflow.into())
+}
+
+/// Weight staked on one side of a single-asset move (Up + PercentageUp, or Down + PercentageDown)
+pub fn calculate_side_weight(direction_totals: &DirectionTotals, is_up: bool) -> Result<u64> {
+    let (direction, percentage) = if is_up { (0, 2) } else { (1, 3) };
+    direction_totals.weights[direction]
+        .checked_add(direction_totals.weights[percentage])
+        .ok_or(GoldRushError::Overflow.into())
+}
+
+/// Payout per staked token (in bps) if `winning_amount` splits the whole reward pool; 0 when nothing is staked
+pub fn calculate_implied_multiplier_bps(reward_pool: u64, winning_amount: u64) -> Result<u64> {
+    if winning_amount == 0 {
+        return Ok(0);
+    }
+
+    let multiplier_bps = (reward_pool as u128)
+        .checked_mul(HUNDRED_PERCENT_BPS as u128)
+        .ok_or(GoldRushError::Overflow)?
+        .checked_div(winning_amount as u128)
+        .ok_or(GoldRushError::Underflow)?;
+
+    u64::try_from(multiplier_bps).map_err(|_| GoldRushError::Overflow.into())
+}
+
+/// Share (in bps) of a round's fee, e.g. the jackpot slice or a fee schedule leg
+pub fn calculate_fee_share(fee_amount: u64, share_bps: u16) -> Result<u64> {
+    let share = (fee_amount as u128)
+        .checked_mul(share_bps as u128)
+        .ok_or(GoldRushError::Overflow)?
+        .checked_div(HUNDRED_PERCENT_BPS as u128)
+        .ok_or(GoldRushError::Underflow)?;
+
+    u64::try_from(share).map_err(|_| GoldRushError::Overflow.into())
+}
+
+/// Splits a fee by the schedule into (treasury, keeper pool, burn); rounding dust stays with the treasury
+pub fn split_fee(fee_amount: u64, fee_schedule: &FeeSchedule) -> Result<(u64, u64, u64)> {
+    let keeper_pool_fee = calculate_fee_share(fee_amount, fee_schedule.keeper_pool_bps)?;
+    let burn_fee = calculate_fee_share(fee_amount, fee_schedule.burn_bps)?;
+    let treasury_fee = fee_amount
+        .checked_sub(keeper_pool_fee)
+        .and_then(|x| x.checked_sub(burn_fee))
+        .ok_or(GoldRushError::Underflow)?;
+
+    Ok((treasury_fee, keeper_pool_fee, burn_fee))
+}
+
+/// Referrers' slice of a round's fee: `referral_fee_bps` of the fee charged on the referred stake
+pub fn calculate_referral_fee(
+    referred_stake: u64,
+    fee_bps: u16,
+    referral_fee_bps: u16,
+) -> Result<u64> {
+    let referred_fee = calculate_fee_share(referred_stake, fee_bps)?;
+
+    calculate_fee_share(referred_fee, referral_fee_bps)
+}
+
+/// A referred bet's pro-rata share of the round's referral fee, capped to what is not accrued yet
+pub fn calculate_referral_share(
+    amount: u64,
+    referral_fee: u64,
+    referral_accrued: u64,
+    referred_stake: u64,
+) -> Result<u64> {
+    if referred_stake == 0 {
+        return Ok(0);
+    }
+
+    let share = (amount as u128)
+        .checked_mul(referral_fee as u128)
+        .ok_or(GoldRushError::Overflow)?
+        .checked_div(referred_stake as u128)
+        .ok_or(GoldRushError::Underflow)?;
+    let share = u64::try_from(share).map_err(|_| GoldRushError::Overflow)?;
+
+    Ok(share.min(referral_fee.saturating_sub(referral_accrued)))
+}
+
+/// A losing bet's rebate (`loss_rebate_bps` of its principal), capped to what is left of the reward pool
+pub fn calculate_loss_rebate(amount: u64, loss_rebate_bps: u16, reward_pool: u64) -> Result<u64> {
+    Ok(calculate_fee_share(amount, loss_rebate_bps)?.min(reward_pool))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction_totals_round_trip() {
+        let mut totals = DirectionTotals::default();
+
+        add_to_direction_totals(
+            &mut totals,
+            &BetDirection::PercentageChangeBps(-50),
+            100,
+            150,
+        )
+        .unwrap();
+        assert_eq!(totals.amounts[3], 100);
+        assert_eq!(totals.weights[3], 150);
+
+        sub_from_direction_totals(&mut totals, &BetDirection::PercentageChangeBps(-50), 40, 60)
+            .unwrap();
+        assert_eq!(totals.amounts[3], 60);
+        assert_eq!(totals.weights[3], 90);
+
+        // cannot remove more than was added
+        assert!(sub_from_direction_totals(&mut totals, &BetDirection::Up, 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_side_amount() {
+        let totals = DirectionTotals {
+            amounts: [10, 20, 30, 40, 50, 60],
+            weights: [0; DIRECTION_BUCKETS],
+        };
+        assert_eq!(calculate_side_amount(&totals, true).unwrap(), 40);
+        assert_eq!(calculate_side_amount(&totals, false).unwrap(), 60);
+    }
+
+    #[test]
+    fn test_side_weight() {
+        let totals = DirectionTotals {
+            amounts: [0; DIRECTION_BUCKETS],
+            weights: [10, 20, 30, 40, 50, 60],
+        };
+        assert_eq!(calculate_side_weight(&totals, true).unwrap(), 40);
+        assert_eq!(calculate_side_weight(&totals, false).unwrap(), 60);
+    }
+
+    #[test]
+    fn test_implied_multiplier_bps() {
+        // 1000 reward pool over 400 staked = 2.5x
+        assert_eq!(
+            calculate_implied_multiplier_bps(1_000, 400).unwrap(),
+            25_000
+        );
+        // nobody on this side yet
+        assert_eq!(calculate_implied_multiplier_bps(1_000, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fee_share() {
+        // 25% of a 1000 fee
+        assert_eq!(calculate_fee_share(1_000, 2_500).unwrap(), 250);
+        // disabled and full routing
+        assert_eq!(calculate_fee_share(1_000, 0).unwrap(), 0);
+        assert_eq!(calculate_fee_share(1_000, 10_000).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_split_fee() {
+        let fee_schedule = FeeSchedule {
+            treasury_bps: 7_000,
+            keeper_pool_bps: 2_000,
+            burn_bps: 1_000,
+        };
+        assert_eq!(split_fee(1_000, &fee_schedule).unwrap(), (700, 200, 100));
+        // rounding dust goes to the treasury
+        assert_eq!(split_fee(999, &fee_schedule).unwrap(), (701, 199, 99));
+    }
+
+    #[test]
+    fn test_referral_fee_and_shares() {
+        // 10% of the 5% fee on 3_000 referred stake
+        let referral_fee = calculate_referral_fee(3_000, 500, 1_000).unwrap();
+        assert_eq!(referral_fee, 15);
+
+        // shares never add up to more than the referral fee
+        let shares = [1_000, 1_000, 1_000]
+            .iter()
+            .map(|amount| calculate_referral_share(*amount, referral_fee, 0, 3_000).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(shares, vec![5, 5, 5]);
+        assert_eq!(calculate_referral_share(1_000, 16, 0, 3_000).unwrap(), 5);
+        assert_eq!(calculate_referral_share(1_000, 15, 0, 0).unwrap(), 0);
+        // never more than what is left of the referral fee
+        assert_eq!(calculate_referral_share(1_000, 15, 12, 3_000).unwrap(), 3);
+        assert_eq!(calculate_referral_share(1_000, 15, 15, 3_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_loss_rebate() {
+        // 20% of a 1_000 losing stake
+        assert_eq!(calculate_loss_rebate(1_000, 2_000, 5_000).unwrap(), 200);
+        // disabled
+        assert_eq!(calculate_loss_rebate(1_000, 0, 5_000).unwrap(), 0);
+        // never more than the reward pool left
+        assert_eq!(calculate_loss_rebate(1_000, 2_000, 150).unwrap(), 150);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{constants::*, error::GoldRushError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+/// Account size of a `UserRoundPosition` listing `bet_count` bet ids
+pub fn user_round_position_space(bet_count: usize) -> Result<usize> {
+    bet_count
+        .checked_mul(std::mem::size_of::<u64>())
+        .and_then(|x| x.checked_add(DISRIMINATOR_SIZE as usize + UserRoundPosition::INIT_SPACE))
+        .ok_or(GoldRushError::Overflow.into())
+}
+
+/// Current size of a position account, or the initial size before it is created; `init_if_needed`
+/// requires the space to match an existing account that has since grown
+pub fn user_round_position_account_space(position_info: &AccountInfo) -> Result<usize> {
+    Ok(position_info.data_len().max(user_round_position_space(0)?))
+}
+
+/// Adds a newly placed bet to its bettor's position, initializing the position on the first bet
+pub fn add_bet_to_position<'info>(
+    user_round_position: &mut Account<'info, UserRoundPosition>,
+    bet: &Bet,
+    bump: u8,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if user_round_position.bettor == Pubkey::default() {
+        user_round_position.round = bet.round;
+        user_round_position.bettor = bet.bettor;
+        user_round_position.created_at = bet.created_at;
+        user_round_position.bump = bump;
+    }
+    user_round_position.total_stake = user_round_position
+        .total_stake
+        .checked_add(bet.amount)
+        .ok_or(GoldRushError::Overflow)?;
+    user_round_position.total_weight = user_round_position
+        .total_weight
+        .checked_add(bet.weight)
+        .ok_or(GoldRushError::Overflow)?;
+    push_position_bet_id(user_round_position, bet.id, payer, system_program)
+}
+
+/// Appends `bet_id` to the position's unbounded `bet_ids`, growing the account when it is
+/// full; `payer` covers the extra rent. Ids removed by withdrawals leave their room behind.
+pub fn push_position_bet_id<'info>(
+    user_round_position: &mut Account<'info, UserRoundPosition>,
+    bet_id: u64,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    user_round_position.bet_ids.push(bet_id);
+
+    let position_info = user_round_position.to_account_info();
+    let space = user_round_position_space(user_round_position.bet_ids.len())?;
+    if position_info.data_len() >= space {
+        return Ok(());
+    }
+
+    let rent_due = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(position_info.lamports());
+    if rent_due > 0 {
+        let transfer_accounts = Transfer {
+            from: payer.to_account_info(),
+            to: position_info.clone(),
+        };
+        let transfer_ctx = CpiContext::new(system_program.to_account_info(), transfer_accounts);
+        transfer(transfer_ctx, rent_due)?;
+    }
+    position_info.resize(space)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_round_position_space() {
+        let empty = user_round_position_space(0).unwrap();
+        assert_eq!(empty, 8 + UserRoundPosition::INIT_SPACE);
+        // every listed bet id adds one u64
+        assert_eq!(user_round_position_space(3).unwrap(), empty + 24);
+    }
+}
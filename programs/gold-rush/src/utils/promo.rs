@@ -0,0 +1,39 @@
+use crate::error::GoldRushError;
+use anchor_lang::prelude::*;
+
+/// Splits a payout into (bettor, promo vault); a promotional bet's stake goes back to the promo vault
+pub fn split_promotional_payout(
+    payout: u64,
+    amount: u64,
+    is_promotional: bool,
+) -> Result<(u64, u64)> {
+    if !is_promotional {
+        return Ok((payout, 0));
+    }
+
+    let promo_amount = payout.min(amount);
+    let bettor_amount = payout
+        .checked_sub(promo_amount)
+        .ok_or(GoldRushError::Underflow)?;
+
+    Ok((bettor_amount, promo_amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_promotional_payout() {
+        // regular bets keep the whole payout
+        assert_eq!(split_promotional_payout(250, 100, false).unwrap(), (250, 0));
+        // a promotional win pays only the profit
+        assert_eq!(
+            split_promotional_payout(250, 100, true).unwrap(),
+            (150, 100)
+        );
+        // a promotional draw or thin win returns everything to the promo vault
+        assert_eq!(split_promotional_payout(100, 100, true).unwrap(), (0, 100));
+        assert_eq!(split_promotional_payout(90, 100, true).unwrap(), (0, 90));
+    }
+}
@@ -0,0 +1,362 @@
+use crate::{constants::*, error::GoldRushError, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer};
+
+/// Token accounts the settlement fee legs move between, borrowed from the settle instruction
+pub struct SettlementAccounts<'a, 'info> {
+    pub round_vault: &'a Account<'info, TokenAccount>,
+    pub treasury_token_account: &'a Account<'info, TokenAccount>,
+    pub keeper_reward_pool: Option<&'a Account<'info, TokenAccount>>,
+    pub jackpot: Option<&'a mut Account<'info, Jackpot>>,
+    pub jackpot_vault: Option<&'a Account<'info, TokenAccount>>,
+    pub referral_vault: Option<&'a Account<'info, TokenAccount>>,
+    pub mint: &'a Account<'info, Mint>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+impl<'info> SettlementAccounts<'_, 'info> {
+    /// Moves `amount` out of the round vault, signed by the round PDA
+    fn transfer_from_round_vault(
+        &self,
+        round: &Account<'info, Round>,
+        to: AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let round_id = round.id.to_le_bytes();
+        let seeds = &[ROUND_SEED.as_bytes(), &round_id, &[round.bump]];
+        let signer = &[&seeds[..]];
+        let transfer_accounts = Transfer {
+            from: self.round_vault.to_account_info(),
+            to,
+            authority: round.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            signer,
+        );
+        transfer(transfer_ctx, amount)
+    }
+}
+
+/// Computes and locks the round's fee and reward pool on the first settlement batch,
+/// then routes the fee legs out of the round vault. Later batches leave the round untouched.
+pub fn lock_round_fees<'info>(
+    round: &mut Account<'info, Round>,
+    config: &Config,
+    fee_bps: u16,
+    is_full_draw: bool,
+    accounts: &mut SettlementAccounts<'_, 'info>,
+) -> Result<()> {
+    if round.fees_locked {
+        return Ok(());
+    }
+    round.fees_locked = true;
+
+    if is_full_draw {
+        // Full draw: no fee collected, reward pool equals total pool
+        round.total_fee_collected = 0;
+        round.total_reward_pool = round.total_pool;
+        return Ok(());
+    }
+
+    let fee_amount = round
+        .total_pool
+        .checked_mul(fee_bps as u64)
+        .and_then(|x| x.checked_div(HUNDRED_PERCENT_BPS as u64))
+        .ok_or(GoldRushError::Overflow)?;
+    round.total_fee_collected = fee_amount;
+    round.total_reward_pool = round
+        .total_pool
+        .checked_sub(fee_amount)
+        .ok_or(GoldRushError::Underflow)?;
+
+    // Staking rebates stay in the vault until claimed; the rest of the fee is split
+    let fee_rebate = calculate_fee_share(round.discounted_stake, fee_bps)?.min(fee_amount);
+    let net_fee = fee_amount
+        .checked_sub(fee_rebate)
+        .ok_or(GoldRushError::Underflow)?;
+    round.fee_rebate = fee_rebate;
+
+    // Split the fee: the jackpot and referral slices first, the rest by the fee schedule
+    let jackpot_fee = if config.jackpot_enabled {
+        calculate_fee_share(net_fee, config.jackpot_fee_bps)?
+    } else {
+        0
+    };
+    // Rebates also shrink the referred fee, so its slice is capped to the net fee
+    let referral_fee =
+        calculate_referral_fee(round.referred_stake, fee_bps, config.referral_fee_bps)?
+            .min(calculate_fee_share(net_fee, config.referral_fee_bps)?);
+    let (treasury_fee, keeper_pool_fee, burn_fee) = split_fee(
+        net_fee
+            .checked_sub(jackpot_fee)
+            .and_then(|x| x.checked_sub(referral_fee))
+            .ok_or(GoldRushError::Underflow)?,
+        &config.fee_schedule,
+    )?;
+
+    // A jackpot round pays out the jackpot as it stood before this round's slice lands in it
+    let jackpot_amount = if round.is_jackpot_round {
+        accounts
+            .jackpot_vault
+            .ok_or(GoldRushError::MissingJackpotAccount)?
+            .amount
+    } else {
+        0
+    };
+
+    // Transfer fee to treasury
+    if treasury_fee > 0 {
+        accounts.transfer_from_round_vault(
+            round,
+            accounts.treasury_token_account.to_account_info(),
+            treasury_fee,
+        )?;
+    }
+
+    // Transfer fee leg to keeper reward pool
+    if keeper_pool_fee > 0 {
+        let keeper_reward_pool = accounts
+            .keeper_reward_pool
+            .ok_or(GoldRushError::InvalidKeeperRewardPool)?;
+        accounts.transfer_from_round_vault(
+            round,
+            keeper_reward_pool.to_account_info(),
+            keeper_pool_fee,
+        )?;
+    }
+
+    // Burn fee leg from the round vault
+    if burn_fee > 0 {
+        let round_id = round.id.to_le_bytes();
+        let seeds = &[ROUND_SEED.as_bytes(), &round_id, &[round.bump]];
+        let signer = &[&seeds[..]];
+        let burn_accounts = Burn {
+            mint: accounts.mint.to_account_info(),
+            from: accounts.round_vault.to_account_info(),
+            authority: round.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            burn_accounts,
+            signer,
+        );
+        burn(burn_ctx, burn_fee)?;
+        round.fee_burned = burn_fee;
+
+        // emit event
+        emit!(FeeBurned {
+            round: round.key(),
+            mint: accounts.mint.key(),
+            amount: burn_fee,
+        });
+    }
+
+    // Transfer referrers' slice to the referral vault; settled bets accrue it
+    if referral_fee > 0 {
+        let referral_vault = accounts
+            .referral_vault
+            .ok_or(GoldRushError::MissingReferralAccount)?;
+        accounts.transfer_from_round_vault(
+            round,
+            referral_vault.to_account_info(),
+            referral_fee,
+        )?;
+        round.referral_fee = referral_fee;
+    }
+
+    // Transfer fee slice to jackpot
+    if jackpot_fee > 0 {
+        let jackpot_vault = accounts
+            .jackpot_vault
+            .ok_or(GoldRushError::MissingJackpotAccount)?;
+        accounts.transfer_from_round_vault(round, jackpot_vault.to_account_info(), jackpot_fee)?;
+
+        let jackpot = accounts
+            .jackpot
+            .as_mut()
+            .ok_or(GoldRushError::MissingJackpotAccount)?;
+        jackpot.total_funded = jackpot
+            .total_funded
+            .checked_add(jackpot_fee)
+            .ok_or(GoldRushError::Overflow)?;
+        round.jackpot_contribution = jackpot_fee;
+    }
+
+    // Jackpot rounds take the pre-slice jackpot into the reward pool
+    if jackpot_amount > 0 {
+        let jackpot_vault = accounts
+            .jackpot_vault
+            .ok_or(GoldRushError::MissingJackpotAccount)?;
+        let jackpot = accounts
+            .jackpot
+            .as_mut()
+            .ok_or(GoldRushError::MissingJackpotAccount)?;
+
+        let transfer_accounts = Transfer {
+            from: jackpot_vault.to_account_info(),
+            to: accounts.round_vault.to_account_info(),
+            authority: jackpot.to_account_info(),
+        };
+        let jackpot_bump = jackpot.bump;
+        let jackpot_seeds = &[JACKPOT_SEED.as_bytes(), &[jackpot_bump]];
+        let jackpot_signer = &[&jackpot_seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            transfer_accounts,
+            jackpot_signer,
+        );
+        transfer(transfer_ctx, jackpot_amount)?;
+
+        jackpot.total_paid_out = jackpot
+            .total_paid_out
+            .checked_add(jackpot_amount)
+            .ok_or(GoldRushError::Overflow)?;
+        round.jackpot_payout = jackpot_amount;
+        round.total_reward_pool = round
+            .total_reward_pool
+            .checked_add(jackpot_amount)
+            .ok_or(GoldRushError::Overflow)?;
+    }
+
+    Ok(())
+}
+
+/// Rolls the reward pool into the jackpot once every bet is settled and nobody won
+pub fn roll_over_to_jackpot<'info>(
+    round: &mut Account<'info, Round>,
+    config: &Config,
+    is_full_draw: bool,
+    accounts: &mut SettlementAccounts<'_, 'info>,
+) -> Result<()> {
+    if !config.jackpot_enabled
+        || is_full_draw
+        || round.winners_weight != 0
+        || round.total_reward_pool == 0
+    {
+        return Ok(());
+    }
+
+    let rollover_amount = round.total_reward_pool;
+    let jackpot_vault = accounts
+        .jackpot_vault
+        .ok_or(GoldRushError::MissingJackpotAccount)?;
+    accounts.transfer_from_round_vault(round, jackpot_vault.to_account_info(), rollover_amount)?;
+
+    let jackpot = accounts
+        .jackpot
+        .as_mut()
+        .ok_or(GoldRushError::MissingJackpotAccount)?;
+    jackpot.total_funded = jackpot
+        .total_funded
+        .checked_add(rollover_amount)
+        .ok_or(GoldRushError::Overflow)?;
+    round.jackpot_contribution = round
+        .jackpot_contribution
+        .checked_add(rollover_amount)
+        .ok_or(GoldRushError::Overflow)?;
+
+    Ok(())
+}
+
+/// Marks a cashed-out bet as counted by this settlement and writes it back; returns whether
+/// the bet was cashed out, in which case it already left the pool and is otherwise skipped
+pub fn skip_cashed_out_bet(bet: &mut Bet, data: &mut [u8]) -> Result<bool> {
+    if bet.status != BetStatus::CashedOut {
+        return Ok(false);
+    }
+
+    bet.claimed = true;
+    let serialized = bet
+        .try_to_vec()
+        .map_err(|_| GoldRushError::SerializeError)?;
+    if serialized.len() > data[8..].len() {
+        return Err(GoldRushError::AccountDataTooSmall.into());
+    }
+    data[8..8 + serialized.len()].copy_from_slice(&serialized);
+
+    Ok(true)
+}
+
+/// Sets aside a Lost bet's loss rebate from the reward pool; promotional stakes get none
+pub fn set_aside_loss_rebate(round: &mut Round, bet: &mut Bet) -> Result<()> {
+    if bet.status != BetStatus::Lost || bet.is_promotional {
+        return Ok(());
+    }
+
+    bet.loss_rebate =
+        calculate_loss_rebate(bet.amount, round.loss_rebate_bps, round.total_reward_pool)?;
+    round.total_reward_pool = round
+        .total_reward_pool
+        .checked_sub(bet.loss_rebate)
+        .ok_or(GoldRushError::Underflow)?;
+    round.loss_rebate = round
+        .loss_rebate
+        .checked_add(bet.loss_rebate)
+        .ok_or(GoldRushError::Overflow)?;
+
+    Ok(())
+}
+
+/// Validates the Referrer PDA following a referred bet and accrues the referrer's share of
+/// the round's referral fee to it
+pub fn accrue_referral_share(
+    round: &mut Round,
+    bet: &Bet,
+    referrer_key: Pubkey,
+    referrer_ai: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(
+        *referrer_ai.owner,
+        *program_id,
+        GoldRushError::InvalidReferrerAccount
+    );
+    let mut referrer_data = referrer_ai.try_borrow_mut_data()?;
+    let mut referrer: Referrer = Referrer::try_deserialize(&mut &referrer_data[..])
+        .map_err(|_| GoldRushError::InvalidReferrerAccount)?;
+    let expected_referrer_pda = Pubkey::find_program_address(
+        &[REFERRER_SEED.as_bytes(), referrer_key.as_ref()],
+        program_id,
+    )
+    .0;
+    require_keys_eq!(
+        *referrer_ai.key,
+        expected_referrer_pda,
+        GoldRushError::InvalidReferrerAccount
+    );
+
+    let referral_share = calculate_referral_share(
+        bet.amount,
+        round.referral_fee,
+        round.referral_accrued,
+        round.referred_stake,
+    )?;
+    round.referral_accrued = round
+        .referral_accrued
+        .checked_add(referral_share)
+        .ok_or(GoldRushError::Overflow)?;
+    referrer.referred_bets = referrer
+        .referred_bets
+        .checked_add(1)
+        .ok_or(GoldRushError::Overflow)?;
+    referrer.claimable = referrer
+        .claimable
+        .checked_add(referral_share)
+        .ok_or(GoldRushError::Overflow)?;
+    referrer.total_earned = referrer
+        .total_earned
+        .checked_add(referral_share)
+        .ok_or(GoldRushError::Overflow)?;
+
+    let serialized = referrer
+        .try_to_vec()
+        .map_err(|_| GoldRushError::SerializeError)?;
+    if serialized.len() > referrer_data[8..].len() {
+        return Err(GoldRushError::AccountDataTooSmall.into());
+    }
+    referrer_data[8..8 + serialized.len()].copy_from_slice(&serialized);
+
+    Ok(())
+}
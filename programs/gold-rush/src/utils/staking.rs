@@ -0,0 +1,128 @@
+use crate::{constants::*, error::GoldRushError, state::*, utils::calculate_claim_amount};
+use anchor_lang::prelude::*;
+
+/// Discount of the highest tier reached by `staked_amount`; 0 below the first tier
+pub fn calculate_fee_discount_bps(tiers: &[FeeDiscountTier], staked_amount: u64) -> u16 {
+    tiers
+        .iter()
+        .filter(|tier| staked_amount >= tier.min_stake)
+        .map(|tier| tier.discount_bps)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Part of a bet's stake whose fee is rebated (`amount × fee_discount_bps / 10_000`)
+pub fn calculate_discounted_stake(amount: u64, fee_discount_bps: u16) -> Result<u64> {
+    let discounted_stake = (amount as u128)
+        .checked_mul(fee_discount_bps as u128)
+        .ok_or(GoldRushError::Overflow)?
+        .checked_div(HUNDRED_PERCENT_BPS as u128)
+        .ok_or(GoldRushError::Underflow)?;
+
+    u64::try_from(discounted_stake).map_err(|_| GoldRushError::Overflow.into())
+}
+
+/// A bet's pro-rata share of the round's fee rebate, by discounted stake
+pub fn calculate_fee_rebate(
+    amount: u64,
+    fee_discount_bps: u16,
+    fee_rebate: u64,
+    discounted_stake: u64,
+) -> Result<u64> {
+    if discounted_stake == 0 {
+        return Ok(0);
+    }
+
+    let rebate = (calculate_discounted_stake(amount, fee_discount_bps)? as u128)
+        .checked_mul(fee_rebate as u128)
+        .ok_or(GoldRushError::Overflow)?
+        .checked_div(discounted_stake as u128)
+        .ok_or(GoldRushError::Underflow)?;
+
+    u64::try_from(rebate).map_err(|_| GoldRushError::Overflow.into())
+}
+
+/// Whether a settled bet pays out at claim: Won/Draw bets, and Lost bets with a loss or staking fee rebate
+pub fn is_bet_claimable(bet: &Bet, round: &Round) -> Result<bool> {
+    Ok(match bet.status {
+        BetStatus::Won | BetStatus::Draw => true,
+        BetStatus::Lost => {
+            bet.loss_rebate > 0
+                || calculate_fee_rebate(
+                    bet.amount,
+                    bet.fee_discount_bps,
+                    round.fee_rebate,
+                    round.discounted_stake,
+                )? > 0
+        }
+        BetStatus::Pending | BetStatus::CashedOut => false,
+    })
+}
+
+/// A settled bet's payout: its reward or refund plus its staking fee rebate (Lost bets get their loss rebate in place of a reward)
+pub fn calculate_claim_amount_with_rebate(bet: &Bet, round: &Round) -> Result<u64> {
+    let claim_amount = match bet.status {
+        BetStatus::Lost => bet.loss_rebate,
+        _ => calculate_claim_amount(
+            &bet.status,
+            bet.amount,
+            bet.weight,
+            round.total_reward_pool,
+            round.winners_weight,
+            round.total_stake,
+        )?,
+    };
+    let fee_rebate = calculate_fee_rebate(
+        bet.amount,
+        bet.fee_discount_bps,
+        round.fee_rebate,
+        round.discounted_stake,
+    )?;
+
+    claim_amount
+        .checked_add(fee_rebate)
+        .ok_or(GoldRushError::Overflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_discount_and_rebate() {
+        let tiers = vec![
+            FeeDiscountTier {
+                min_stake: 1_000,
+                discount_bps: 1_000,
+            },
+            FeeDiscountTier {
+                min_stake: 10_000,
+                discount_bps: 2_500,
+            },
+        ];
+
+        assert_eq!(calculate_fee_discount_bps(&tiers, 999), 0);
+        assert_eq!(calculate_fee_discount_bps(&tiers, 1_000), 1_000);
+        assert_eq!(calculate_fee_discount_bps(&tiers, 50_000), 2_500);
+        assert_eq!(calculate_fee_discount_bps(&[], 50_000), 0);
+
+        // 1_000 at 10% and 3_000 at 25%: 100 + 750 discounted stake
+        let discounted_stake = calculate_discounted_stake(1_000, 1_000).unwrap()
+            + calculate_discounted_stake(3_000, 2_500).unwrap();
+        assert_eq!(discounted_stake, 850);
+
+        // 1% fee on the discounted stake is rebated pro-rata
+        let fee_rebate = 8;
+        let rebates = calculate_fee_rebate(1_000, 1_000, fee_rebate, discounted_stake).unwrap()
+            + calculate_fee_rebate(3_000, 2_500, fee_rebate, discounted_stake).unwrap();
+        assert!(rebates <= fee_rebate);
+        assert_eq!(
+            calculate_fee_rebate(1_000, 0, fee_rebate, discounted_stake).unwrap(),
+            0
+        );
+        assert_eq!(
+            calculate_fee_rebate(1_000, 1_000, fee_rebate, 0).unwrap(),
+            0
+        );
+    }
+}
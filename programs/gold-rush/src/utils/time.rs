@@ -14,7 +14,7 @@ pub fn calculate_time_factor(
 
     let factor_bps = match market_type {
         // Linear Decay
-        MarketType::GroupBattle => {
+        MarketType::GroupBattle | MarketType::AssetBattle => {
             let reduction = (time_elapsed as u128)
                 .checked_mul(max_time_factor_bps as u128)
                 .ok_or(GoldRushError::Overflow)?
@@ -58,6 +58,20 @@ pub fn calculate_time_factor(
     Ok(factor_bps.max(min_time_factor_bps))
 }
 
+/// Whether the claim window after settlement has passed; a window of 0 never expires
+pub fn is_claim_expired(settled_at: Option<i64>, claim_window_secs: i64, now: i64) -> Result<bool> {
+    let settled_at = match settled_at {
+        Some(settled_at) if claim_window_secs > 0 => settled_at,
+        _ => return Ok(false),
+    };
+
+    let claim_deadline = settled_at
+        .checked_add(claim_window_secs)
+        .ok_or(GoldRushError::Overflow)?;
+
+    Ok(now >= claim_deadline)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,4 +124,15 @@ mod tests {
         let result = calculate_time_factor(&MarketType::GroupBattle, 50, 500, 10_000, -10);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_is_claim_expired() {
+        // no deadline configured or not settled yet
+        assert!(!is_claim_expired(Some(100), 0, 1_000_000).unwrap());
+        assert!(!is_claim_expired(None, 50, 1_000_000).unwrap());
+
+        // deadline = settled_at + window
+        assert!(!is_claim_expired(Some(100), 50, 149).unwrap());
+        assert!(is_claim_expired(Some(100), 50, 150).unwrap());
+    }
 }
@@ -1,8 +1,337 @@
-import { getProviderAndProgram } from "./helpers/env";
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  initializePromotions,
+  mintVoucher,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+} from "./helpers/config";
+import {
+  GroupRoundAccounts,
+  RoundAccounts,
+  cancelRound,
+  cashOutBet,
+  createGroupRound,
+  createRound,
+  placeBet,
+  placeBetWithVoucher,
+  startGroupRound,
+  startSingleRound,
+  waitForChainTime,
+  writable,
+} from "./helpers/round";
+import { mintAmount } from "./helpers/token";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
 
 describe("cancelRound", () => {
-  getProviderAndProgram();
+  const BET_AMOUNT = 10_000_000;
 
-  it("happy path");
-  it("fails unauthorized");
+  let env: TestEnv;
+  let alice: Bettor;
+  let bob: Bettor;
+  let carol: Bettor;
+
+  async function accountClosed(pubkey: PublicKey) {
+    return (await env.provider.connection.getAccountInfo(pubkey)) === null;
+  }
+
+  before(async () => {
+    env = await setupEnv();
+    alice = await newBettor(env);
+    bob = await newBettor(env);
+    carol = await newBettor(env);
+  });
+
+  it("fails unauthorized", async () => {
+    const round = await createRound(env);
+
+    await expectAnchorError(
+      cancelRound(env, round, [], alice.user),
+      "Unauthorized"
+    );
+  });
+
+  it("happy path closes a round without bets", async () => {
+    const round = await createRound(env);
+    const treasuryBefore = await env.provider.connection.getBalance(
+      env.treasury.publicKey
+    );
+
+    await cancelRound(env, round);
+
+    expect(await accountClosed(round.roundPda)).to.be.true;
+    expect(await accountClosed(round.vaultPda)).to.be.true;
+    // the round and vault rent goes to the treasury
+    const treasuryAfter = await env.provider.connection.getBalance(
+      env.treasury.publicKey
+    );
+    expect(treasuryAfter).to.be.gt(treasuryBefore);
+  });
+
+  it("refunds the bets in batches and closes the round after the last one", async () => {
+    const round = await createRound(env);
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    const aliceBet = await placeBet(env, round, alice, BET_AMOUNT, {
+      up: {},
+    });
+    const bobBet = await placeBet(env, round, bob, 2 * BET_AMOUNT, {
+      down: {},
+    });
+    const aliceBefore = await tokenBalance(env, alice.tokenAccount);
+    const bobBefore = await tokenBalance(env, bob.tokenAccount);
+
+    await cancelRound(env, round, [
+      writable(aliceBet),
+      writable(alice.tokenAccount),
+    ]);
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.status).to.deep.equal({ cancelling: {} });
+    expect(r.cancelledBets.toNumber()).to.eq(1);
+    expect(r.totalPool.toNumber()).to.eq(2 * BET_AMOUNT);
+    expect(r.directionTotals.amounts[0].toNumber()).to.eq(0);
+    expect(r.directionTotals.weights[0].toNumber()).to.eq(0);
+    expect(await accountClosed(aliceBet)).to.be.true;
+    const aliceAfter = await tokenBalance(env, alice.tokenAccount);
+    expect(aliceAfter.sub(aliceBefore).toNumber()).to.eq(BET_AMOUNT);
+
+    await cancelRound(env, round, [
+      writable(bobBet),
+      writable(bob.tokenAccount),
+    ]);
+
+    const bobAfter = await tokenBalance(env, bob.tokenAccount);
+    expect(bobAfter.sub(bobBefore).toNumber()).to.eq(2 * BET_AMOUNT);
+    expect(await accountClosed(bobBet)).to.be.true;
+    expect(await accountClosed(round.vaultPda)).to.be.true;
+    expect(await accountClosed(round.roundPda)).to.be.true;
+  });
+
+  describe("GroupBattle", () => {
+    const { solStart } = PRICE_FIXTURES;
+    const startPrices = [[solStart], [solStart]];
+
+    let round: GroupRoundAccounts;
+    // alice and bob bet on group 1, carol on group 2
+    let aliceBet: PublicKey;
+    let bobBet: PublicKey;
+    let carolBet: PublicKey;
+
+    before(async () => {
+      round = await createGroupRound(env, startPrices, { durationSecs: 60 });
+      await startGroupRound(env, round, startPrices);
+      const [group1, group2] = round.groupAssetPdas;
+      aliceBet = await placeBet(
+        env,
+        round,
+        alice,
+        BET_AMOUNT,
+        { up: {} },
+        { groupAsset: group1 }
+      );
+      bobBet = await placeBet(
+        env,
+        round,
+        bob,
+        BET_AMOUNT,
+        { down: {} },
+        { groupAsset: group1 }
+      );
+      carolBet = await placeBet(
+        env,
+        round,
+        carol,
+        BET_AMOUNT,
+        { up: {} },
+        { groupAsset: group2 }
+      );
+    });
+
+    it("fails with a bet paired with another group's GroupAsset", async () => {
+      await expectAnchorError(
+        cancelRound(env, round, [
+          writable(aliceBet),
+          writable(alice.tokenAccount),
+          writable(round.groupAssetPdas[1]),
+        ]),
+        "InvalidGroupAssetAccount"
+      );
+    });
+
+    it("decrements the bet's GroupAsset totals", async () => {
+      const before = await env.program.account.groupAsset.fetch(
+        round.groupAssetPdas[0]
+      );
+      expect(before.directionTotals.amounts[0].toNumber()).to.eq(BET_AMOUNT);
+
+      await cancelRound(env, round, [
+        writable(aliceBet),
+        writable(alice.tokenAccount),
+        writable(round.groupAssetPdas[0]),
+      ]);
+
+      // only alice's Up bucket is emptied; bob's Down bet stays in the group
+      const group = await env.program.account.groupAsset.fetch(
+        round.groupAssetPdas[0]
+      );
+      expect(group.directionTotals.amounts[0].toNumber()).to.eq(0);
+      expect(group.directionTotals.weights[0].toNumber()).to.eq(0);
+      expect(group.directionTotals.amounts[1].toString()).to.eq(
+        before.directionTotals.amounts[1].toString()
+      );
+      expect(group.directionTotals.weights[1].toString()).to.eq(
+        before.directionTotals.weights[1].toString()
+      );
+      const other = await env.program.account.groupAsset.fetch(
+        round.groupAssetPdas[1]
+      );
+      expect(other.directionTotals.amounts[0].toNumber()).to.eq(BET_AMOUNT);
+    });
+
+    it("closes the round once every group's bets are refunded", async () => {
+      await cancelRound(env, round, [
+        writable(bobBet),
+        writable(bob.tokenAccount),
+        writable(round.groupAssetPdas[0]),
+        writable(carolBet),
+        writable(carol.tokenAccount),
+        writable(round.groupAssetPdas[1]),
+      ]);
+
+      expect(await accountClosed(round.roundPda)).to.be.true;
+      // the GroupAssets outlive the round with their totals emptied
+      for (const groupAsset of round.groupAssetPdas) {
+        const group = await env.program.account.groupAsset.fetch(groupAsset);
+        expect(group.directionTotals.amounts.every((a) => a.isZero())).to.be
+          .true;
+        expect(group.directionTotals.weights.every((w) => w.isZero())).to.be
+          .true;
+      }
+    });
+  });
+
+  describe("promotional bets", () => {
+    const VOUCHER_AMOUNT = 10_000_000;
+
+    let promoVault: PublicKey;
+    let promoBettor: Bettor;
+    let round: RoundAccounts;
+    let promoBet: PublicKey;
+
+    before(async () => {
+      promoBettor = await newBettor(env, 0);
+      promoVault = await initializePromotions(env);
+      await mintAmount(
+        env.provider.connection,
+        env.admin,
+        env.tokenMint,
+        promoVault,
+        VOUCHER_AMOUNT
+      );
+      const connection = env.provider.connection;
+      const now = (await connection.getBlockTime(await connection.getSlot()))!;
+      const voucher = await mintVoucher(
+        env,
+        promoBettor.user.publicKey,
+        VOUCHER_AMOUNT,
+        now + 3_600
+      );
+
+      round = await createRound(env);
+      await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+      promoBet = await placeBetWithVoucher(env, round, promoBettor, voucher, {
+        up: {},
+      });
+    });
+
+    it("fails refunding a promotional bet to the bettor ATA", async () => {
+      await expectAnchorError(
+        cancelRound(env, round, [
+          writable(promoBet),
+          writable(promoBettor.tokenAccount),
+        ]),
+        "MissingPromoVault"
+      );
+    });
+
+    it("refunds a promotional bet to the promo vault", async () => {
+      await cancelRound(env, round, [writable(promoBet), writable(promoVault)]);
+
+      expect((await tokenBalance(env, promoVault)).toNumber()).to.eq(
+        VOUCHER_AMOUNT
+      );
+      expect(
+        (await tokenBalance(env, promoBettor.tokenAccount)).toNumber()
+      ).to.eq(0);
+      expect(await accountClosed(round.roundPda)).to.be.true;
+    });
+  });
+
+  describe("after a cash-out", () => {
+    let round: RoundAccounts;
+    let cashedOutBet: PublicKey;
+    let downBet: PublicKey;
+    let upBet: PublicKey;
+
+    before(async () => {
+      await updateConfig(env, { newCashOutHaircutBps: 1_000 });
+
+      // cutoff window is 10 secs: cash-out is open for the last 10 of 25 secs
+      round = await createRound(env, { durationSecs: 25 });
+      await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+      cashedOutBet = await placeBet(env, round, alice, BET_AMOUNT, {
+        up: {},
+      });
+      downBet = await placeBet(env, round, bob, BET_AMOUNT, { down: {} });
+      upBet = await placeBet(env, round, carol, 2 * BET_AMOUNT, { up: {} });
+
+      const r = await env.program.account.round.fetch(round.roundPda);
+      await waitForChainTime(env, r.betCutoffTime.toNumber());
+      await cashOutBet(env, round, cashedOutBet, alice, PRICE_FIXTURES.goldUp);
+    });
+
+    it("skips the cashed-out bet and shares the cash-out pro-rata", async () => {
+      const before = await env.program.account.round.fetch(round.roundPda);
+      // the cash-out moved the pool off the stake, so refunds are pro-rata
+      expect(before.totalPool.eq(before.totalStake)).to.be.false;
+      const aliceBefore = await tokenBalance(env, alice.tokenAccount);
+      const bobBefore = await tokenBalance(env, bob.tokenAccount);
+      const carolBefore = await tokenBalance(env, carol.tokenAccount);
+
+      await cancelRound(env, round, [
+        writable(cashedOutBet),
+        writable(alice.tokenAccount),
+        writable(downBet),
+        writable(bob.tokenAccount),
+        writable(upBet),
+        writable(carol.tokenAccount),
+      ]);
+
+      // refunds follow the totals as each bet leaves them
+      const bobRefund = new anchor.BN(BET_AMOUNT)
+        .mul(before.totalPool)
+        .div(before.totalStake);
+      const carolRefund = before.totalPool.sub(bobRefund);
+
+      const aliceAfter = await tokenBalance(env, alice.tokenAccount);
+      expect(aliceAfter.toString()).to.eq(aliceBefore.toString());
+      const bobAfter = await tokenBalance(env, bob.tokenAccount);
+      expect(bobAfter.sub(bobBefore).toString()).to.eq(bobRefund.toString());
+      const carolAfter = await tokenBalance(env, carol.tokenAccount);
+      expect(carolAfter.sub(carolBefore).toString()).to.eq(
+        carolRefund.toString()
+      );
+
+      // the vault is drained and closed with the round
+      expect(await accountClosed(cashedOutBet)).to.be.true;
+      expect(await accountClosed(round.vaultPda)).to.be.true;
+      expect(await accountClosed(round.roundPda)).to.be.true;
+    });
+  });
 });
@@ -108,7 +108,10 @@ describe("captureEndPrice", () => {
       .createRound(
         { groupBattle: {} },
         new anchor.BN(start),
-        new anchor.BN(end)
+        new anchor.BN(end),
+        { mean: {} },
+        false,
+        false
       )
       .accounts({
         signer: admin.publicKey,
@@ -164,7 +167,7 @@ describe("captureEndPrice", () => {
         );
 
         await program.methods
-          .insertAsset(stringToBytes(`S${i}`))
+          .insertAsset(stringToBytes(`S${i}`), 0)
           .accounts({
             signer: admin.publicKey,
             config: configPda,
@@ -339,7 +342,7 @@ describe("captureEndPrice", () => {
     }
     try {
       await program.methods
-        .placeBet(new anchor.BN(10_000_000), { down: {} })
+        .placeBet(new anchor.BN(10_000_000), { down: {} }, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -373,7 +376,7 @@ describe("captureEndPrice", () => {
 
     try {
       await program.methods
-        .placeBet(new anchor.BN(20_000_000), { percentageChangeBps: { 0: 10 } })
+        .placeBet(new anchor.BN(20_000_000), { percentageChangeBps: { 0: 10 } }, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -95,7 +95,10 @@ describe("captureStartPrice", () => {
       .createRound(
         { groupBattle: {} },
         new anchor.BN(start),
-        new anchor.BN(end)
+        new anchor.BN(end),
+        { mean: {} },
+        false,
+        false
       )
       .accounts({
         signer: admin.publicKey,
@@ -151,7 +154,7 @@ describe("captureStartPrice", () => {
         );
 
         await program.methods
-          .insertAsset(stringToBytes(`S${i}`))
+          .insertAsset(stringToBytes(`S${i}`), 0)
           .accounts({
             signer: admin.publicKey,
             config: configPda,
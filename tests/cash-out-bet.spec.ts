@@ -0,0 +1,169 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  cashOutBet,
+  claimReward,
+  createRound,
+  placeBet,
+  settleSingleRound,
+  startSingleRound,
+  waitForChainTime,
+  writable,
+} from "./helpers/round";
+import { deriveUserRoundPositionPda } from "./helpers/pda";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
+
+describe("cashOutBet", () => {
+  const HAIRCUT_BPS = 1_000;
+  const FEE_BPS = 2_000; // fee_single_asset_bps from setupEnv
+
+  let env: TestEnv;
+  let round: RoundAccounts;
+  let cashOutBettor: Bettor;
+  let downBettor: Bettor;
+  let remainingBettor: Bettor;
+  let upBet: PublicKey;
+  let downBet: PublicKey;
+  let remainingBet: PublicKey;
+
+  before(async () => {
+    env = await setupEnv();
+    cashOutBettor = await newBettor(env);
+    downBettor = await newBettor(env);
+    remainingBettor = await newBettor(env);
+    await updateConfig(env, { newCashOutHaircutBps: HAIRCUT_BPS });
+
+    // cutoff window is 10 secs: cash-out is open for the last 10 of 25 secs
+    round = await createRound(env, { durationSecs: 25 });
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    upBet = await placeBet(env, round, cashOutBettor, 10_000_000, {
+      up: {},
+    });
+    downBet = await placeBet(env, round, downBettor, 10_000_000, {
+      down: {},
+    });
+    remainingBet = await placeBet(env, round, remainingBettor, 20_000_000, {
+      up: {},
+    });
+  });
+
+  it("fails before cutoff", async () => {
+    await expectAnchorError(
+      cashOutBet(env, round, upBet, cashOutBettor, PRICE_FIXTURES.goldUp),
+      "CashOutWindowClosed"
+    );
+  });
+
+  it("fails when cash-out is disabled", async () => {
+    await updateConfig(env, { newCashOutHaircutBps: 0 });
+    await expectAnchorError(
+      cashOutBet(env, round, upBet, cashOutBettor, PRICE_FIXTURES.goldUp),
+      "CashOutDisabled"
+    );
+    await updateConfig(env, { newCashOutHaircutBps: HAIRCUT_BPS });
+  });
+
+  it("happy path in the money between cutoff and end", async () => {
+    const before = await env.program.account.round.fetch(round.roundPda);
+    await waitForChainTime(env, before.betCutoffTime.toNumber());
+
+    const bet = await env.program.account.bet.fetch(upBet);
+    const remaining = await env.program.account.bet.fetch(remainingBet);
+    const bettorBefore = await tokenBalance(env, cashOutBettor.tokenAccount);
+
+    await cashOutBet(env, round, upBet, cashOutBettor, PRICE_FIXTURES.goldUp);
+
+    // mark = weight share of the Up side in the pool net of the settlement fee, minus the haircut
+    const rewardPool = before.totalPool.sub(
+      before.totalPool.muln(FEE_BPS).divn(10_000)
+    );
+    const mark = bet.weight
+      .mul(rewardPool)
+      .div(bet.weight.add(remaining.weight));
+    const expected = mark.muln(10_000 - HAIRCUT_BPS).divn(10_000);
+
+    const bettorAfter = await tokenBalance(env, cashOutBettor.tokenAccount);
+    expect(bettorAfter.sub(bettorBefore).toString()).to.eq(
+      expected.toString()
+    );
+
+    const cashedOut = await env.program.account.bet.fetch(upBet);
+    expect(cashedOut.status).to.deep.equal({ cashedOut: {} });
+    expect(cashedOut.cashOutAmount.toString()).to.eq(expected.toString());
+    expect(cashedOut.weight.toString()).to.eq("0");
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.totalPool.toString()).to.eq(
+      before.totalPool.sub(expected).toString()
+    );
+    expect(r.totalStake.toString()).to.eq(
+      before.totalStake.sub(bet.amount).toString()
+    );
+
+    const position = await env.program.account.userRoundPosition.fetch(
+      deriveUserRoundPositionPda(
+        env.program.programId,
+        round.roundPda,
+        cashOutBettor.user.publicKey
+      )
+    );
+    expect(position.totalStake.toString()).to.eq("0");
+    expect(position.totalWeight.toString()).to.eq("0");
+  });
+
+  it("fails out of the money", async () => {
+    await expectAnchorError(
+      cashOutBet(env, round, downBet, downBettor, PRICE_FIXTURES.goldUp),
+      "CashOutNotAvailable"
+    );
+  });
+
+  it("fails cashing out the same bet twice", async () => {
+    await expectAnchorError(
+      cashOutBet(env, round, upBet, cashOutBettor, PRICE_FIXTURES.goldUp),
+      "InvalidBetStatus"
+    );
+  });
+
+  it("leaves the haircut in the pool for remaining bettors", async () => {
+    const before = await env.program.account.round.fetch(round.roundPda);
+    await settleSingleRound(env, round, PRICE_FIXTURES.goldUp, [
+      writable(upBet),
+      writable(downBet),
+      writable(remainingBet),
+    ]);
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.status).to.deep.equal({ ended: {} });
+    expect(r.settledBets.toString()).to.eq("3");
+    const cashedOut = await env.program.account.bet.fetch(upBet);
+    expect(cashedOut.status).to.deep.equal({ cashedOut: {} });
+
+    // the remaining Up bet is the only winner and takes the pool left after the cash-out
+    const fee = before.totalPool.muln(FEE_BPS).divn(10_000);
+    expect(r.totalRewardPool.toString()).to.eq(
+      before.totalPool.sub(fee).toString()
+    );
+
+    const bettorBefore = await tokenBalance(env, remainingBettor.tokenAccount);
+    await claimReward(env, round, remainingBet, remainingBettor);
+    const bettorAfter = await tokenBalance(env, remainingBettor.tokenAccount);
+    expect(bettorAfter.sub(bettorBefore).toString()).to.eq(
+      r.totalRewardPool.toString()
+    );
+    // it gets more than its stake net of the fee share, thanks to the haircut left behind
+    expect(bettorAfter.sub(bettorBefore).gt(new anchor.BN(20_000_000))).to.be
+      .true;
+  });
+});
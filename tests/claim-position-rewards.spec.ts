@@ -0,0 +1,152 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  claimPositionRewards,
+  claimReward,
+  createRound,
+  placeBet,
+  settleSingleRound,
+  startSingleRound,
+  writable,
+} from "./helpers/round";
+import { deriveUserRoundPositionPda } from "./helpers/pda";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
+
+describe("claimPositionRewards", () => {
+  const MANY_BETS = 12;
+
+  let env: TestEnv;
+  let round: RoundAccounts;
+  // mixedBettor holds Up, Up, Down; upBettor holds Up, Up; manyBettor holds MANY_BETS Ups
+  let mixedBettor: Bettor;
+  let upBettor: Bettor;
+  let manyBettor: Bettor;
+  let mixedBets: PublicKey[];
+  let upBets: PublicKey[];
+  let manyBets: PublicKey[];
+
+  // The bet's share of the reward pool by weight, as claim_reward pays it
+  async function wonPayout(bet: PublicKey): Promise<anchor.BN> {
+    const r = await env.program.account.round.fetch(round.roundPda);
+    const b = await env.program.account.bet.fetch(bet);
+    return b.weight.mul(r.totalRewardPool).div(r.winnersWeight);
+  }
+
+  before(async () => {
+    env = await setupEnv();
+    mixedBettor = await newBettor(env);
+    upBettor = await newBettor(env);
+    manyBettor = await newBettor(env, MANY_BETS * 10_000_000);
+
+    // long enough to place every bet before the cutoff
+    round = await createRound(env, { durationSecs: 40 });
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    mixedBets = [
+      await placeBet(env, round, mixedBettor, 10_000_000, { up: {} }),
+      await placeBet(env, round, mixedBettor, 20_000_000, { up: {} }),
+      await placeBet(env, round, mixedBettor, 10_000_000, { down: {} }),
+    ];
+    upBets = [
+      await placeBet(env, round, upBettor, 10_000_000, { up: {} }),
+      await placeBet(env, round, upBettor, 10_000_000, { up: {} }),
+    ];
+    manyBets = [];
+    for (let i = 0; i < MANY_BETS; i++) {
+      manyBets.push(
+        await placeBet(env, round, manyBettor, 10_000_000, { up: {} })
+      );
+    }
+    await settleSingleRound(
+      env,
+      round,
+      PRICE_FIXTURES.goldUp,
+      [...mixedBets, ...upBets, ...manyBets].map(writable)
+    );
+  });
+
+  it("fails when bets are not passed in bet_ids order", async () => {
+    await expectAnchorError(
+      claimPositionRewards(env, round, upBettor, [...upBets].reverse()),
+      "InvalidBetAccount"
+    );
+  });
+
+  it("happy path claims every winning bet in one transfer", async () => {
+    const expected = (await wonPayout(mixedBets[0])).add(
+      await wonPayout(mixedBets[1])
+    );
+    const before = await tokenBalance(env, mixedBettor.tokenAccount);
+
+    await claimPositionRewards(env, round, mixedBettor, mixedBets);
+
+    const after = await tokenBalance(env, mixedBettor.tokenAccount);
+    expect(after.sub(before).toString()).to.eq(expected.toString());
+
+    const bets = await Promise.all(
+      mixedBets.map((bet) => env.program.account.bet.fetch(bet))
+    );
+    expect(bets.map((b) => b.claimed)).to.deep.equal([true, true, false]);
+    expect(bets[2].status).to.deep.equal({ lost: {} });
+  });
+
+  it("claims a position that grew past its initial bet id capacity", async () => {
+    const positionPda = deriveUserRoundPositionPda(
+      env.program.programId,
+      round.roundPda,
+      manyBettor.user.publicKey
+    );
+    const position = await env.program.account.userRoundPosition.fetch(
+      positionPda
+    );
+    expect(position.betIds.length).to.eq(MANY_BETS);
+
+    let expected = new anchor.BN(0);
+    for (const bet of manyBets) {
+      expected = expected.add(await wonPayout(bet));
+    }
+    const before = await tokenBalance(env, manyBettor.tokenAccount);
+
+    await claimPositionRewards(env, round, manyBettor, manyBets);
+
+    const after = await tokenBalance(env, manyBettor.tokenAccount);
+    expect(after.sub(before).toString()).to.eq(expected.toString());
+  });
+
+  it("skips lost and already claimed bets", async () => {
+    const claimedPayout = await wonPayout(upBets[0]);
+    const expected = await wonPayout(upBets[1]);
+
+    const before = await tokenBalance(env, upBettor.tokenAccount);
+    await claimReward(env, round, upBets[0], upBettor);
+    const afterSingle = await tokenBalance(env, upBettor.tokenAccount);
+    expect(afterSingle.sub(before).toString()).to.eq(claimedPayout.toString());
+
+    // the bet claimed through claimReward is not paid a second time
+    await claimPositionRewards(env, round, upBettor, upBets);
+    const after = await tokenBalance(env, upBettor.tokenAccount);
+    expect(after.sub(afterSingle).toString()).to.eq(expected.toString());
+
+    // every winner has claimed: only rounding dust is left in the vault
+    const r = await env.program.account.round.fetch(round.roundPda);
+    const vault = await tokenBalance(env, round.vaultPda);
+    expect(vault.lt(new anchor.BN(r.totalBets.toNumber()))).to.be.true;
+  });
+
+  it("fails when nothing is left to claim", async () => {
+    // the Lost bet has no rebate and both Won bets are already claimed
+    await expectAnchorError(
+      claimPositionRewards(env, round, mixedBettor, mixedBets),
+      "NothingToClaim"
+    );
+  });
+});
@@ -115,7 +115,10 @@ describe("claimRewardGroupRound", () => {
       .createRound(
         { groupBattle: {} },
         new anchor.BN(start),
-        new anchor.BN(end)
+        new anchor.BN(end),
+        { mean: {} },
+        false,
+        false
       )
       .accounts({
         signer: admin.publicKey,
@@ -171,7 +174,7 @@ describe("claimRewardGroupRound", () => {
         );
 
         await program.methods
-          .insertAsset(stringToBytes(`S${i}`))
+          .insertAsset(stringToBytes(`S${i}`), 0)
           .accounts({
             signer: admin.publicKey,
             config: configPda,
@@ -346,7 +349,7 @@ describe("claimRewardGroupRound", () => {
     }
     try {
       await program.methods
-        .placeBet(new anchor.BN(10_000_000), { down: {} })
+        .placeBet(new anchor.BN(10_000_000), { down: {} }, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -380,7 +383,7 @@ describe("claimRewardGroupRound", () => {
 
     try {
       await program.methods
-        .placeBet(new anchor.BN(20_000_000), { percentageChangeBps: { 0: 10 } })
+        .placeBet(new anchor.BN(20_000_000), { percentageChangeBps: { 0: 10 } }, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
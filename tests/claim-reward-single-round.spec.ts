@@ -105,7 +105,10 @@ describe("claimRewardSingleRound", () => {
       .createRound(
         { singleAsset: {} },
         new anchor.BN(start),
-        new anchor.BN(end)
+        new anchor.BN(end),
+        { mean: {} },
+        false,
+        false
       )
       .accounts({
         signer: admin.publicKey,
@@ -170,7 +173,7 @@ describe("claimRewardSingleRound", () => {
     const nextBetId = r.totalBets.addn(1);
     betPda = deriveBetPda(program.programId, roundPda, nextBetId);
     await program.methods
-      .placeBet(amount, direction)
+      .placeBet(amount, direction, null, null, null)
       .accounts({
         signer: user.publicKey,
         config: configPda,
@@ -230,6 +233,21 @@ describe("claimRewardSingleRound", () => {
     }
   });
 
+  it("previews the reward", async () => {
+    const preview = await program.methods
+      .previewReward()
+      .accounts({
+        round: roundPda,
+        bet: betPda,
+      } as any)
+      .view();
+    const bet = await program.account.bet.fetch(betPda);
+    expect(preview.status).to.deep.equal(bet.status);
+    expect(preview.claimed).to.be.false;
+    // draw refunds the stake
+    expect(preview.rewardAmount.toString()).to.eq(bet.amount.toString());
+  });
+
   it("happy path", async () => {
     try {
       await program.methods
@@ -0,0 +1,135 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  claimRewardsBatch,
+  claimTriple,
+  createRound,
+  placeBet,
+  readonly,
+  settleSingleRound,
+  startSingleRound,
+  writable,
+} from "./helpers/round";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
+
+describe("claimRewardsBatch", () => {
+  let env: TestEnv;
+  let winner: Bettor;
+  let loser: Bettor;
+  // winner holds two Up bets in the first round and one in the second; loser one Down bet in each
+  let rounds: RoundAccounts[];
+  let winningBets: PublicKey[][];
+
+  // Plays a round with `upAmounts` Up bets for the winner and one Down bet for the loser, settled Up
+  async function playRound(upAmounts: number[]) {
+    const round = await createRound(env);
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    const bets: PublicKey[] = [];
+    for (const amount of upAmounts) {
+      bets.push(await placeBet(env, round, winner, amount, { up: {} }));
+    }
+    const downBet = await placeBet(env, round, loser, 10_000_000, {
+      down: {},
+    });
+    await settleSingleRound(env, round, PRICE_FIXTURES.goldUp, [
+      ...bets.map(writable),
+      writable(downBet),
+    ]);
+    return { round, bets };
+  }
+
+  // The bet's share of the round's reward pool by weight, as claim_reward pays it
+  async function wonPayout(
+    round: RoundAccounts,
+    bet: PublicKey
+  ): Promise<anchor.BN> {
+    const r = await env.program.account.round.fetch(round.roundPda);
+    const b = await env.program.account.bet.fetch(bet);
+    return b.weight.mul(r.totalRewardPool).div(r.winnersWeight);
+  }
+
+  before(async () => {
+    env = await setupEnv();
+    winner = await newBettor(env);
+    loser = await newBettor(env);
+
+    const first = await playRound([10_000_000, 20_000_000]);
+    const second = await playRound([10_000_000]);
+    rounds = [first.round, second.round];
+    winningBets = [first.bets, second.bets];
+  });
+
+  it("fails when remaining accounts are not [round, vault, bet] triples", async () => {
+    await expectAnchorError(
+      claimRewardsBatch(env, winner, [
+        readonly(rounds[0].roundPda),
+        writable(rounds[0].vaultPda),
+      ]),
+      "InvalidRemainingAccountsLength"
+    );
+  });
+
+  it("fails when a bet belongs to another bettor", async () => {
+    await expectAnchorError(
+      claimRewardsBatch(env, loser, claimTriple(rounds[0], winningBets[0][0])),
+      "Unauthorized"
+    );
+  });
+
+  it("fails when the same bet is listed twice", async () => {
+    await expectAnchorError(
+      claimRewardsBatch(env, winner, [
+        ...claimTriple(rounds[0], winningBets[0][0]),
+        ...claimTriple(rounds[0], winningBets[0][0]),
+      ]),
+      "AlreadyClaimed"
+    );
+  });
+
+  it("happy path claims bets across two rounds with one transfer per round", async () => {
+    let expected = new anchor.BN(0);
+    for (let i = 0; i < rounds.length; i++) {
+      for (const bet of winningBets[i]) {
+        expected = expected.add(await wonPayout(rounds[i], bet));
+      }
+    }
+    const vaultsBefore = await Promise.all(
+      rounds.map((round) => tokenBalance(env, round.vaultPda))
+    );
+    const before = await tokenBalance(env, winner.tokenAccount);
+
+    await claimRewardsBatch(
+      env,
+      winner,
+      rounds.flatMap((round, i) =>
+        winningBets[i].flatMap((bet) => claimTriple(round, bet))
+      )
+    );
+
+    const after = await tokenBalance(env, winner.tokenAccount);
+    expect(after.sub(before).toString()).to.eq(expected.toString());
+
+    // each round's vault pays exactly its own winners' share
+    for (let i = 0; i < rounds.length; i++) {
+      let roundPayout = new anchor.BN(0);
+      for (const bet of winningBets[i]) {
+        roundPayout = roundPayout.add(await wonPayout(rounds[i], bet));
+        expect((await env.program.account.bet.fetch(bet)).claimed).to.be.true;
+      }
+      const vaultAfter = await tokenBalance(env, rounds[i].vaultPda);
+      expect(vaultsBefore[i].sub(vaultAfter).toString()).to.eq(
+        roundPayout.toString()
+      );
+    }
+  });
+});
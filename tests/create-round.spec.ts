@@ -77,7 +77,10 @@ describe("createRound", () => {
         .createRound(
           { groupBattle: {} },
           new anchor.BN(start),
-          new anchor.BN(end)
+          new anchor.BN(end),
+          { mean: {} },
+          false,
+          false
         )
         .accounts({
           signer: admin.publicKey,
@@ -97,6 +100,8 @@ describe("createRound", () => {
     const round = await program.account.round.fetch(roundPda);
     expect(round.id.toString()).to.eq(nextId.toString());
     expect(round.marketType).to.deep.equal({ groupBattle: {} });
+    expect(round.aggregation).to.deep.equal({ mean: {} });
+    expect(round.autoDistribute).to.eq(false);
     expect(round.status).to.deep.equal({ scheduled: {} });
     expect(round.vault.toString()).to.eq(vaultPda.toString());
   });
@@ -119,7 +124,10 @@ describe("createRound", () => {
         .createRound(
           { singleAsset: {} },
           new anchor.BN(start),
-          new anchor.BN(end)
+          new anchor.BN(end),
+          { mean: {} },
+          false,
+          false
         )
         .accounts({
           signer: admin.publicKey,
@@ -161,7 +169,10 @@ describe("createRound", () => {
         .createRound(
           { groupBattle: {} },
           new anchor.BN(start),
-          new anchor.BN(end)
+          new anchor.BN(end),
+          { mean: {} },
+          false,
+          false
         )
         .accounts({
           signer: admin.publicKey,
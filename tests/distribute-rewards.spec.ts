@@ -0,0 +1,191 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  createRound,
+  distributePair,
+  distributeRewards,
+  placeBet,
+  settleSingleRound,
+  startSingleRound,
+  writable,
+} from "./helpers/round";
+import { airdropMany } from "./helpers/env";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
+
+describe("distributeRewards", () => {
+  const BET_AMOUNT = 10_000_000;
+
+  let env: TestEnv;
+  let alice: Bettor;
+  let bob: Bettor;
+  let carol: Bettor;
+  // wonRound: alice and bob Up, carol Down, settled Up
+  let wonRound: RoundAccounts;
+  let aliceWon: PublicKey;
+  let bobWon: PublicKey;
+  let carolLost: PublicKey;
+  // drawRound: alice Up, carol Down, settled flat
+  let drawRound: RoundAccounts;
+  let aliceDraw: PublicKey;
+  let carolDraw: PublicKey;
+  // manualRound: created without auto_distribute
+  let manualRound: RoundAccounts;
+  let manualBet: PublicKey;
+
+  // The bet's share of the round's reward pool by weight, as claim_reward pays it
+  async function wonPayout(
+    round: RoundAccounts,
+    bet: PublicKey
+  ): Promise<anchor.BN> {
+    const r = await env.program.account.round.fetch(round.roundPda);
+    const b = await env.program.account.bet.fetch(bet);
+    return b.weight.mul(r.totalRewardPool).div(r.winnersWeight);
+  }
+
+  before(async () => {
+    env = await setupEnv();
+    alice = await newBettor(env);
+    bob = await newBettor(env);
+    carol = await newBettor(env);
+
+    wonRound = await createRound(env, { autoDistribute: true });
+    drawRound = await createRound(env, { autoDistribute: true });
+    manualRound = await createRound(env);
+    for (const round of [wonRound, drawRound, manualRound]) {
+      await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    }
+
+    aliceWon = await placeBet(env, wonRound, alice, BET_AMOUNT, { up: {} });
+    bobWon = await placeBet(env, wonRound, bob, 2 * BET_AMOUNT, { up: {} });
+    carolLost = await placeBet(env, wonRound, carol, BET_AMOUNT, {
+      down: {},
+    });
+    aliceDraw = await placeBet(env, drawRound, alice, BET_AMOUNT, { up: {} });
+    carolDraw = await placeBet(env, drawRound, carol, BET_AMOUNT, {
+      down: {},
+    });
+    manualBet = await placeBet(env, manualRound, alice, BET_AMOUNT, {
+      up: {},
+    });
+
+    await settleSingleRound(env, wonRound, PRICE_FIXTURES.goldUp, [
+      writable(aliceWon),
+      writable(bobWon),
+      writable(carolLost),
+    ]);
+    await settleSingleRound(env, drawRound, PRICE_FIXTURES.goldStart, [
+      writable(aliceDraw),
+      writable(carolDraw),
+    ]);
+    await settleSingleRound(env, manualRound, PRICE_FIXTURES.goldUp, [
+      writable(manualBet),
+    ]);
+  });
+
+  it("fails when the signer is not a keeper", async () => {
+    const stranger = Keypair.generate();
+    await airdropMany(env.provider.connection, [stranger.publicKey]);
+    await expectAnchorError(
+      distributeRewards(
+        env,
+        wonRound,
+        distributePair(aliceWon, alice.tokenAccount),
+        stranger
+      ),
+      "UnauthorizedKeeper"
+    );
+  });
+
+  it("fails when an ATA does not belong to bet.bettor", async () => {
+    await expectAnchorError(
+      distributeRewards(
+        env,
+        wonRound,
+        distributePair(aliceWon, bob.tokenAccount)
+      ),
+      "InvalidTokenAccount"
+    );
+  });
+
+  it("fails when the round was created without auto_distribute", async () => {
+    await expectAnchorError(
+      distributeRewards(
+        env,
+        manualRound,
+        distributePair(manualBet, alice.tokenAccount)
+      ),
+      "AutoDistributeDisabled"
+    );
+  });
+
+  it("happy path pushes won and draw payouts to bettor ATAs", async () => {
+    const aliceExpected = await wonPayout(wonRound, aliceWon);
+    const bobExpected = await wonPayout(wonRound, bobWon);
+    const aliceBefore = await tokenBalance(env, alice.tokenAccount);
+    const bobBefore = await tokenBalance(env, bob.tokenAccount);
+
+    await distributeRewards(env, wonRound, [
+      ...distributePair(aliceWon, alice.tokenAccount),
+      ...distributePair(bobWon, bob.tokenAccount),
+    ]);
+
+    const aliceAfterWon = await tokenBalance(env, alice.tokenAccount);
+    const bobAfter = await tokenBalance(env, bob.tokenAccount);
+    expect(aliceAfterWon.sub(aliceBefore).toString()).to.eq(
+      aliceExpected.toString()
+    );
+    expect(bobAfter.sub(bobBefore).toString()).to.eq(bobExpected.toString());
+
+    const carolBefore = await tokenBalance(env, carol.tokenAccount);
+    await distributeRewards(env, drawRound, [
+      ...distributePair(aliceDraw, alice.tokenAccount),
+      ...distributePair(carolDraw, carol.tokenAccount),
+    ]);
+
+    // draws are refunded in full: no fee is collected on a flat round
+    const aliceAfterDraw = await tokenBalance(env, alice.tokenAccount);
+    const carolAfter = await tokenBalance(env, carol.tokenAccount);
+    expect(aliceAfterDraw.sub(aliceAfterWon).toString()).to.eq(
+      BET_AMOUNT.toString()
+    );
+    expect(carolAfter.sub(carolBefore).toString()).to.eq(
+      BET_AMOUNT.toString()
+    );
+
+    for (const bet of [aliceWon, bobWon, aliceDraw, carolDraw]) {
+      expect((await env.program.account.bet.fetch(bet)).claimed).to.be.true;
+    }
+  });
+
+  it("skips lost and already claimed bets", async () => {
+    const aliceBefore = await tokenBalance(env, alice.tokenAccount);
+    const carolBefore = await tokenBalance(env, carol.tokenAccount);
+    const vaultBefore = await tokenBalance(env, wonRound.vaultPda);
+
+    await distributeRewards(env, wonRound, [
+      ...distributePair(aliceWon, alice.tokenAccount),
+      ...distributePair(carolLost, carol.tokenAccount),
+    ]);
+
+    const aliceAfter = await tokenBalance(env, alice.tokenAccount);
+    const carolAfter = await tokenBalance(env, carol.tokenAccount);
+    const vaultAfter = await tokenBalance(env, wonRound.vaultPda);
+    expect(aliceAfter.toString()).to.eq(aliceBefore.toString());
+    expect(carolAfter.toString()).to.eq(carolBefore.toString());
+    expect(vaultAfter.toString()).to.eq(vaultBefore.toString());
+
+    const lost = await env.program.account.bet.fetch(carolLost);
+    expect(lost.status).to.deep.equal({ lost: {} });
+    expect(lost.claimed).to.be.false;
+  });
+});
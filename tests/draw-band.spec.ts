@@ -0,0 +1,154 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  claimReward,
+  createRound,
+  placeBet,
+  settleSingleRound,
+  startSingleRound,
+  writable,
+} from "./helpers/round";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
+
+describe("draw band", () => {
+  const BET_AMOUNT = 10_000_000;
+
+  let env: TestEnv;
+  let upBettor: Bettor;
+  let downBettor: Bettor;
+
+  // Plays a round with one Up and one Down bet, settled at `finalPrice`
+  async function playRound(finalPrice: anchor.web3.PublicKey) {
+    const round = await createRound(env);
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    const upBet = await placeBet(env, round, upBettor, BET_AMOUNT, { up: {} });
+    const downBet = await placeBet(env, round, downBettor, BET_AMOUNT, {
+      down: {},
+    });
+    await settleSingleRound(env, round, finalPrice, [
+      writable(upBet),
+      writable(downBet),
+    ]);
+    return { round, upBet, downBet };
+  }
+
+  before(async () => {
+    env = await setupEnv();
+    upBettor = await newBettor(env);
+    downBettor = await newBettor(env);
+    // 10 bps: gold-flat (+5 bps) is inside the band, gold-up (+500 bps) outside
+    await updateConfig(env, { newDrawBandBps: 10 });
+  });
+
+  it("createRound snapshots the config's draw band", async () => {
+    const round = await createRound(env);
+    await updateConfig(env, { newDrawBandBps: 20 });
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.drawBandBps).to.eq(10);
+
+    await updateConfig(env, { newDrawBandBps: 10 });
+  });
+
+  describe("move inside the band", () => {
+    let round: RoundAccounts;
+    let upBet: anchor.web3.PublicKey;
+    let downBet: anchor.web3.PublicKey;
+    let treasuryBefore: anchor.BN;
+
+    before(async () => {
+      treasuryBefore = await tokenBalance(env, env.treasuryTokenAccount);
+      ({ round, upBet, downBet } = await playRound(PRICE_FIXTURES.goldFlat));
+    });
+
+    it("settles every bet as a draw without collecting a fee", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      expect(r.status).to.deep.equal({ ended: {} });
+      expect(r.winnersWeight.toString()).to.eq("0");
+      expect(r.totalFeeCollected.toString()).to.eq("0");
+      expect(r.totalRewardPool.toString()).to.eq(r.totalPool.toString());
+
+      expect((await env.program.account.bet.fetch(upBet)).status).to.deep.equal(
+        { draw: {} }
+      );
+      expect(
+        (await env.program.account.bet.fetch(downBet)).status
+      ).to.deep.equal({ draw: {} });
+
+      const treasuryAfter = await tokenBalance(env, env.treasuryTokenAccount);
+      expect(treasuryAfter.toString()).to.eq(treasuryBefore.toString());
+    });
+
+    it("claimReward refunds both stakes in full", async () => {
+      for (const [bettor, bet] of [
+        [upBettor, upBet],
+        [downBettor, downBet],
+      ] as [Bettor, anchor.web3.PublicKey][]) {
+        const before = await tokenBalance(env, bettor.tokenAccount);
+        await claimReward(env, round, bet, bettor);
+        const after = await tokenBalance(env, bettor.tokenAccount);
+        expect(after.sub(before).toString()).to.eq(BET_AMOUNT.toString());
+      }
+
+      const vault = await tokenBalance(env, round.vaultPda);
+      expect(vault.toString()).to.eq("0");
+    });
+  });
+
+  describe("move outside the band", () => {
+    let round: RoundAccounts;
+    let upBet: anchor.web3.PublicKey;
+    let downBet: anchor.web3.PublicKey;
+    let treasuryBefore: anchor.BN;
+
+    before(async () => {
+      treasuryBefore = await tokenBalance(env, env.treasuryTokenAccount);
+      ({ round, upBet, downBet } = await playRound(PRICE_FIXTURES.goldUp));
+    });
+
+    it("settles the Up bet as won and the Down bet as lost", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      const up = await env.program.account.bet.fetch(upBet);
+      expect(up.status).to.deep.equal({ won: {} });
+      expect(
+        (await env.program.account.bet.fetch(downBet)).status
+      ).to.deep.equal({ lost: {} });
+      expect(r.winnersWeight.toString()).to.eq(up.weight.toString());
+
+      // fee_single_asset_bps = 2000, all of it to the treasury
+      const fee = new anchor.BN(2 * BET_AMOUNT).muln(2_000).divn(10_000);
+      expect(r.totalFeeCollected.toString()).to.eq(fee.toString());
+      const treasuryAfter = await tokenBalance(env, env.treasuryTokenAccount);
+      expect(treasuryAfter.sub(treasuryBefore).toString()).to.eq(
+        fee.toString()
+      );
+    });
+
+    it("claimReward pays the winner the whole reward pool", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      const before = await tokenBalance(env, upBettor.tokenAccount);
+      await claimReward(env, round, upBet, upBettor);
+      const after = await tokenBalance(env, upBettor.tokenAccount);
+      expect(after.sub(before).toString()).to.eq(
+        r.totalRewardPool.toString()
+      );
+    });
+
+    it("fails claimReward on the losing bet", async () => {
+      await expectAnchorError(
+        claimReward(env, round, downBet, downBettor),
+        "BetNotWonOrDraw"
+      );
+    });
+  });
+});
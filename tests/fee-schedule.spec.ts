@@ -0,0 +1,190 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  getMint,
+} from "@solana/spl-token";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestEnv,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  transactionEvents,
+  updateConfig,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  createRound,
+  placeBet,
+  settleSingleRound,
+  startSingleRound,
+  writable,
+} from "./helpers/round";
+import { createMintToken } from "./helpers/token";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
+
+describe("feeSchedule", () => {
+  const FEE_BPS = 2_000; // fee_single_asset_bps from setupEnv
+  const SCHEDULE = { treasuryBps: 7_000, keeperPoolBps: 2_000, burnBps: 1_000 };
+  // a 20_000_035 pool charges a 4_000_007 fee, which no leg divides evenly
+  const UP_AMOUNT = 10_000_035;
+  const DOWN_AMOUNT = 10_000_000;
+
+  let env: TestEnv;
+  let keeperPool: PublicKey;
+  let round: RoundAccounts;
+  let bets: PublicKey[];
+
+  before(async () => {
+    env = await setupEnv();
+    const upBettor = await newBettor(env);
+    const downBettor = await newBettor(env);
+    keeperPool = (await newBettor(env, 0)).tokenAccount;
+    await updateConfig(env, {
+      newKeeperRewardPool: keeperPool,
+      newFeeSchedule: SCHEDULE,
+    });
+
+    round = await createRound(env);
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    bets = [
+      await placeBet(env, round, upBettor, UP_AMOUNT, { up: {} }),
+      await placeBet(env, round, downBettor, DOWN_AMOUNT, { down: {} }),
+    ];
+  });
+
+  it("fails settlement without a keeper reward pool when keeper_pool_bps > 0", async () => {
+    await expectAnchorError(
+      settleSingleRound(env, round, PRICE_FIXTURES.goldUp, bets.map(writable)),
+      "InvalidKeeperRewardPool"
+    );
+  });
+
+  it("fails settlement with a keeper reward pool other than config.keeper_reward_pool", async () => {
+    await expectAnchorError(
+      settleSingleRound(
+        env,
+        round,
+        PRICE_FIXTURES.goldUp,
+        bets.map(writable),
+        { keeperRewardPool: env.treasuryTokenAccount }
+      ),
+      "InvalidKeeperRewardPool"
+    );
+  });
+
+  it("fails settlement with a mint other than config.token_mint when burn_bps > 0", async () => {
+    const { mint: otherMint } = await createMintToken(
+      env.provider.connection,
+      env.admin,
+      9
+    );
+    await expectAnchorError(
+      env.program.methods
+        .settleSingleRound()
+        .accounts({
+          signer: env.keeper.publicKey,
+          config: env.configPda,
+          round: round.roundPda,
+          roundVault: round.vaultPda,
+          priceUpdate: PRICE_FIXTURES.goldUp,
+          treasury: env.treasury.publicKey,
+          treasuryTokenAccount: getAssociatedTokenAddressSync(
+            otherMint,
+            env.treasury.publicKey
+          ),
+          keeperRewardPool: keeperPool,
+          jackpot: null,
+          jackpotVault: null,
+          referralVault: null,
+          mint: otherMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .remainingAccounts(bets.map(writable))
+        .signers([env.keeper])
+        .rpc(),
+      "InvalidMint"
+    );
+  });
+
+  // the settlement runs after the failures above: root tests run before nested hooks
+  describe("settlement", () => {
+    const fee = new anchor.BN(UP_AMOUNT + DOWN_AMOUNT)
+      .muln(FEE_BPS)
+      .divn(10_000);
+    const keeperPoolFee = fee.muln(SCHEDULE.keeperPoolBps).divn(10_000);
+    const burnFee = fee.muln(SCHEDULE.burnBps).divn(10_000);
+
+    let signature: string;
+    let treasuryDelta: anchor.BN;
+    let keeperPoolDelta: anchor.BN;
+    let supplyBefore: bigint;
+
+    before(async () => {
+      const treasuryBefore = await tokenBalance(env, env.treasuryTokenAccount);
+      const keeperPoolBefore = await tokenBalance(env, keeperPool);
+      supplyBefore = (await getMint(env.provider.connection, env.tokenMint))
+        .supply;
+
+      signature = await settleSingleRound(
+        env,
+        round,
+        PRICE_FIXTURES.goldUp,
+        bets.map(writable),
+        { keeperRewardPool: keeperPool }
+      );
+
+      treasuryDelta = (
+        await tokenBalance(env, env.treasuryTokenAccount)
+      ).sub(treasuryBefore);
+      keeperPoolDelta = (await tokenBalance(env, keeperPool)).sub(
+        keeperPoolBefore
+      );
+    });
+
+    it("settlement splits the fee between treasury, keeper pool and burn", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      expect(r.totalFeeCollected.toString()).to.eq(fee.toString());
+      expect(keeperPoolDelta.toString()).to.eq(keeperPoolFee.toString());
+      expect(r.feeBurned.toString()).to.eq(burnFee.toString());
+      expect(
+        treasuryDelta.add(keeperPoolDelta).add(r.feeBurned).toString()
+      ).to.eq(fee.toString());
+    });
+
+    it("settlement burns the burn_bps leg from the round vault and emits FeeBurned", async () => {
+      const mint = await getMint(env.provider.connection, env.tokenMint);
+      const supplyAfter = mint.supply;
+      expect((supplyBefore - supplyAfter).toString()).to.eq(burnFee.toString());
+
+      // the vault keeps only the reward pool for the winner
+      const r = await env.program.account.round.fetch(round.roundPda);
+      expect((await tokenBalance(env, round.vaultPda)).toString()).to.eq(
+        r.totalRewardPool.toString()
+      );
+
+      const burned = (await transactionEvents(env, signature)).filter(
+        (event) => event.name === "feeBurned"
+      );
+      expect(burned).to.have.length(1);
+      expect(burned[0].data.round.toBase58()).to.eq(round.roundPda.toBase58());
+      expect(burned[0].data.mint.toBase58()).to.eq(env.tokenMint.toBase58());
+      expect(burned[0].data.amount.toString()).to.eq(burnFee.toString());
+    });
+
+    it("fee schedule dust goes to the treasury", async () => {
+      // 70% of 4_000_007 rounds down to 2_800_004; the legs' rounding dust lands here too
+      const treasuryShare = fee.muln(SCHEDULE.treasuryBps).divn(10_000);
+      expect(treasuryDelta.toString()).to.eq(
+        fee.sub(keeperPoolFee).sub(burnFee).toString()
+      );
+      expect(treasuryDelta.gt(treasuryShare)).to.be.true;
+    });
+  });
+});
@@ -114,7 +114,10 @@ describe("finalizeEndGroupAsset", () => {
       .createRound(
         { groupBattle: {} },
         new anchor.BN(start),
-        new anchor.BN(end)
+        new anchor.BN(end),
+        { mean: {} },
+        false,
+        false
       )
       .accounts({
         signer: admin.publicKey,
@@ -170,7 +173,7 @@ describe("finalizeEndGroupAsset", () => {
         );
 
         await program.methods
-          .insertAsset(stringToBytes(`S${i}`))
+          .insertAsset(stringToBytes(`S${i}`), 0)
           .accounts({
             signer: admin.publicKey,
             config: configPda,
@@ -345,7 +348,7 @@ describe("finalizeEndGroupAsset", () => {
     }
     try {
       await program.methods
-        .placeBet(new anchor.BN(10_000_000), { down: {} })
+        .placeBet(new anchor.BN(10_000_000), { down: {} }, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -379,7 +382,7 @@ describe("finalizeEndGroupAsset", () => {
 
     try {
       await program.methods
-        .placeBet(new anchor.BN(20_000_000), { percentageChangeBps: { 0: 10 } })
+        .placeBet(new anchor.BN(20_000_000), { percentageChangeBps: { 0: 10 } }, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
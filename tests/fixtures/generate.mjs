@@ -0,0 +1,106 @@
+// Generates the PriceUpdateV2 accounts the local validator loads (see Anchor.toml).
+// Prices are fixed so specs can settle rounds as wins, losses or draws on demand.
+//
+//   node tests/fixtures/generate.mjs
+import { createHash } from "crypto";
+import { writeFileSync } from "fs";
+import { dirname, join } from "path";
+import { fileURLToPath } from "url";
+
+const PYTH_RECEIVER_PROGRAM_ID = "rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ";
+const GOLD_PRICE_FEED_ID =
+  "765d2ba906dbc32ca17cc11f5310a89e9ee1f6420508c63861f2f8ba4ee34bb2";
+const SOL_PRICE_FEED_ID =
+  "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+
+const EXPONENT = -8;
+// 2100-01-01, so the updates never exceed max_price_update_age_secs
+const PUBLISH_TIME = 4_102_444_800n;
+const ACCOUNT_SIZE = 134; // PriceUpdateV2::LEN
+const RENT_EXEMPT_LAMPORTS = 1_823_520;
+
+const FIXTURES = [
+  { name: "gold-start", feedId: GOLD_PRICE_FEED_ID, price: 2_000n },
+  { name: "gold-up", feedId: GOLD_PRICE_FEED_ID, price: 2_100n },
+  { name: "gold-down", feedId: GOLD_PRICE_FEED_ID, price: 1_900n },
+  // +5 bps over gold-start, inside a draw band of 10 bps
+  { name: "gold-flat", feedId: GOLD_PRICE_FEED_ID, price: 2_001n },
+  { name: "sol-start", feedId: SOL_PRICE_FEED_ID, price: 150n },
+  { name: "sol-up", feedId: SOL_PRICE_FEED_ID, price: 165n },
+  { name: "sol-down", feedId: SOL_PRICE_FEED_ID, price: 135n },
+];
+
+const BASE58_ALPHABET =
+  "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+function base58Encode(bytes) {
+  let n = BigInt("0x" + Buffer.from(bytes).toString("hex"));
+  let out = "";
+  while (n > 0n) {
+    out = BASE58_ALPHABET[Number(n % 58n)] + out;
+    n /= 58n;
+  }
+  for (const b of bytes) {
+    if (b !== 0) break;
+    out = "1" + out;
+  }
+  return out;
+}
+
+function sha256(data) {
+  return createHash("sha256").update(data).digest();
+}
+
+function fixtureAddress(name) {
+  return base58Encode(sha256(`gold-rush-fixture:${name}`));
+}
+
+function priceUpdateData(feedId, price) {
+  const data = Buffer.alloc(ACCOUNT_SIZE);
+  let offset = 0;
+  sha256("account:PriceUpdateV2").copy(data, offset, 0, 8);
+  offset += 8;
+  offset += 32; // write_authority
+  data.writeUInt8(1, offset); // VerificationLevel::Full
+  offset += 1;
+  Buffer.from(feedId, "hex").copy(data, offset);
+  offset += 32;
+  const scaled = price * 10n ** BigInt(-EXPONENT);
+  data.writeBigInt64LE(scaled, offset); // price
+  offset += 8;
+  data.writeBigUInt64LE(0n, offset); // conf
+  offset += 8;
+  data.writeInt32LE(EXPONENT, offset);
+  offset += 4;
+  data.writeBigInt64LE(PUBLISH_TIME, offset); // publish_time
+  offset += 8;
+  data.writeBigInt64LE(PUBLISH_TIME, offset); // prev_publish_time
+  offset += 8;
+  data.writeBigInt64LE(scaled, offset); // ema_price
+  offset += 8;
+  data.writeBigUInt64LE(0n, offset); // ema_conf
+  offset += 8;
+  data.writeBigUInt64LE(0n, offset); // posted_slot
+  return data;
+}
+
+const dir = dirname(fileURLToPath(import.meta.url));
+for (const { name, feedId, price } of FIXTURES) {
+  const pubkey = fixtureAddress(name);
+  const account = {
+    pubkey,
+    account: {
+      lamports: RENT_EXEMPT_LAMPORTS,
+      data: [priceUpdateData(feedId, price).toString("base64"), "base64"],
+      owner: PYTH_RECEIVER_PROGRAM_ID,
+      executable: false,
+      rentEpoch: 0,
+      space: ACCOUNT_SIZE,
+    },
+  };
+  writeFileSync(
+    join(dir, `${name}.json`),
+    JSON.stringify(account, null, 2) + "\n"
+  );
+  console.log(`${name}: ${pubkey}`);
+}
@@ -0,0 +1,469 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { GoldRush } from "../../target/types/gold_rush";
+import { airdropMany, getProviderAndProgram } from "./env";
+import { createAta, createMintToken, mintAmount } from "./token";
+import {
+  deriveConfigPda,
+  deriveJackpotPda,
+  deriveJackpotVaultPda,
+  deriveKeeperRewardVaultPda,
+  deriveKeeperRewardsPda,
+  deriveKeeperStatsPda,
+  derivePromoVaultPda,
+  deriveReferralVaultPda,
+  deriveReferrerPda,
+  deriveStakeAccountPda,
+  deriveStakeVaultPda,
+  deriveVoucherPda,
+} from "./pda";
+import { hex32ToBytes } from "./bytes";
+import { GOLD_PRICE_FEED_ID } from "./pyth";
+
+export type TestEnv = {
+  provider: anchor.AnchorProvider;
+  program: Program<GoldRush>;
+  admin: Keypair;
+  treasury: Keypair;
+  keeper: Keypair;
+  tokenMint: PublicKey;
+  configPda: PublicKey;
+  treasuryTokenAccount: PublicKey;
+};
+
+export type Bettor = {
+  user: Keypair;
+  tokenAccount: PublicKey;
+};
+
+// Creates the GRT mint and initializes the config with the values the specs share
+export async function setupEnv(): Promise<TestEnv> {
+  const { provider, program } = getProviderAndProgram();
+  const admin = (provider.wallet as any).payer as Keypair;
+  const treasury = Keypair.generate();
+  const keeper = Keypair.generate();
+
+  await airdropMany(provider.connection, [
+    admin.publicKey,
+    treasury.publicKey,
+    keeper.publicKey,
+  ]);
+
+  const { mint: tokenMint } = await createMintToken(
+    provider.connection,
+    admin,
+    9
+  );
+  await createAta(provider.connection, tokenMint, admin);
+  const treasuryTokenAccount = await createAta(
+    provider.connection,
+    tokenMint,
+    treasury
+  );
+
+  const configPda = deriveConfigPda(program.programId);
+  await program.methods
+    .initialize(
+      [keeper.publicKey],
+      tokenMint,
+      treasury.publicKey,
+      hex32ToBytes(GOLD_PRICE_FEED_ID),
+      new anchor.BN(120),
+      2_000,
+      2_500,
+      new anchor.BN(10_000_000),
+      new anchor.BN(10),
+      1_000,
+      2_000,
+      1_000
+    )
+    .accounts({
+      signer: admin.publicKey,
+      config: configPda,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([admin])
+    .rpc();
+
+  return {
+    provider,
+    program,
+    admin,
+    treasury,
+    keeper,
+    tokenMint,
+    configPda,
+    treasuryTokenAccount,
+  };
+}
+
+// Funds a new wallet with SOL and `amount` GRT
+export async function newBettor(
+  env: TestEnv,
+  amount: number = 100_000_000
+): Promise<Bettor> {
+  const user = Keypair.generate();
+  await airdropMany(env.provider.connection, [user.publicKey]);
+  const tokenAccount = await createAta(
+    env.provider.connection,
+    env.tokenMint,
+    user
+  );
+  if (amount > 0) {
+    await mintAmount(
+      env.provider.connection,
+      env.admin,
+      env.tokenMint,
+      tokenAccount,
+      amount
+    );
+  }
+  return { user, tokenAccount };
+}
+
+const EMPTY_UPDATE_CONFIG_PARAMS = {
+  newAdmin: null,
+  newKeeperAuthorities: null,
+  newTokenMint: null,
+  newTreasury: null,
+  newSingleAssetFeedId: null,
+  newMaxPriceUpdateAgeSecs: null,
+  newFeeSingleAssetBps: null,
+  newFeeGroupBattleBps: null,
+  newMinBetAmount: null,
+  newBetCutoffWindowSecs: null,
+  newTieBreakRule: null,
+  newDrawBandBps: null,
+  newEarlyExitFeeBps: null,
+  newCashOutHaircutBps: null,
+  newMaxBetAmount: null,
+  newMaxUserStakePerRound: null,
+  newMaxTotalPool: null,
+  newClaimWindowSecs: null,
+  newJackpotFeeBps: null,
+  newFeeSchedule: null,
+  newKeeperRewardPool: null,
+  newKeeperBountyPerUnit: null,
+  newReferralFeeBps: null,
+  newFeeDiscountTiers: null,
+  newUnstakeCooldownSecs: null,
+  newLossRebateBps: null,
+};
+
+// Builds UpdateConfigParams leaving every knob not in `params` unchanged
+export function updateConfigParams(
+  params: Partial<typeof EMPTY_UPDATE_CONFIG_PARAMS> | Record<string, any>
+) {
+  return { ...EMPTY_UPDATE_CONFIG_PARAMS, ...params } as any;
+}
+
+export async function updateConfig(
+  env: TestEnv,
+  params: Record<string, any>,
+  signer: Keypair = env.admin
+) {
+  await env.program.methods
+    .updateConfig(updateConfigParams(params))
+    .accounts({
+      signer: signer.publicKey,
+      config: env.configPda,
+    } as any)
+    .signers([signer])
+    .rpc();
+}
+
+export type JackpotAccounts = {
+  jackpotPda: PublicKey;
+  jackpotVaultPda: PublicKey;
+};
+
+export function jackpotAccounts(env: TestEnv): JackpotAccounts {
+  return {
+    jackpotPda: deriveJackpotPda(env.program.programId),
+    jackpotVaultPda: deriveJackpotVaultPda(env.program.programId),
+  };
+}
+
+export async function initializeJackpot(
+  env: TestEnv
+): Promise<JackpotAccounts> {
+  const accounts = jackpotAccounts(env);
+  await env.program.methods
+    .initializeJackpot()
+    .accounts({
+      signer: env.admin.publicKey,
+      config: env.configPda,
+      jackpot: accounts.jackpotPda,
+      jackpotVault: accounts.jackpotVaultPda,
+      mint: env.tokenMint,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    } as any)
+    .signers([env.admin])
+    .rpc();
+  return accounts;
+}
+
+export type KeeperRewardsAccounts = {
+  keeperRewardsPda: PublicKey;
+  keeperRewardVaultPda: PublicKey;
+};
+
+export function keeperRewardsAccounts(env: TestEnv): KeeperRewardsAccounts {
+  return {
+    keeperRewardsPda: deriveKeeperRewardsPda(env.program.programId),
+    keeperRewardVaultPda: deriveKeeperRewardVaultPda(env.program.programId),
+  };
+}
+
+export async function initializeKeeperRewards(
+  env: TestEnv
+): Promise<KeeperRewardsAccounts> {
+  const accounts = keeperRewardsAccounts(env);
+  await env.program.methods
+    .initializeKeeperRewards()
+    .accounts({
+      signer: env.admin.publicKey,
+      config: env.configPda,
+      keeperRewards: accounts.keeperRewardsPda,
+      keeperRewardVault: accounts.keeperRewardVaultPda,
+      mint: env.tokenMint,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    } as any)
+    .signers([env.admin])
+    .rpc();
+  return accounts;
+}
+
+// Pays `keeper` what it earned cranking, up to the keeper reward vault balance
+export async function claimKeeperRewards(
+  env: TestEnv,
+  keeper: Keypair,
+  keeperTokenAccount: PublicKey
+) {
+  const accounts = keeperRewardsAccounts(env);
+  await env.program.methods
+    .claimKeeperRewards()
+    .accounts({
+      signer: keeper.publicKey,
+      config: env.configPda,
+      keeperRewards: accounts.keeperRewardsPda,
+      keeperRewardVault: accounts.keeperRewardVaultPda,
+      keeperStats: deriveKeeperStatsPda(
+        env.program.programId,
+        keeper.publicKey
+      ),
+      keeperTokenAccount,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    } as any)
+    .signers([keeper])
+    .rpc();
+}
+
+export async function initializeReferrals(env: TestEnv): Promise<PublicKey> {
+  const referralVaultPda = deriveReferralVaultPda(env.program.programId);
+  await env.program.methods
+    .initializeReferrals()
+    .accounts({
+      signer: env.admin.publicKey,
+      config: env.configPda,
+      referralVault: referralVaultPda,
+      mint: env.tokenMint,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    } as any)
+    .signers([env.admin])
+    .rpc();
+  return referralVaultPda;
+}
+
+// Registers `bettor` as a referrer and returns its Referrer PDA
+export async function registerReferrer(
+  env: TestEnv,
+  bettor: Bettor
+): Promise<PublicKey> {
+  const referrerPda = deriveReferrerPda(
+    env.program.programId,
+    bettor.user.publicKey
+  );
+  await env.program.methods
+    .registerReferrer()
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      referrer: referrerPda,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([bettor.user])
+    .rpc();
+  return referrerPda;
+}
+
+export async function withdrawReferralRewards(env: TestEnv, bettor: Bettor) {
+  await env.program.methods
+    .withdrawReferralRewards()
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      referrer: deriveReferrerPda(env.program.programId, bettor.user.publicKey),
+      referralVault: deriveReferralVaultPda(env.program.programId),
+      referrerTokenAccount: bettor.tokenAccount,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    } as any)
+    .signers([bettor.user])
+    .rpc();
+}
+
+export async function initializeStaking(env: TestEnv): Promise<PublicKey> {
+  const stakeVaultPda = deriveStakeVaultPda(env.program.programId);
+  await env.program.methods
+    .initializeStaking()
+    .accounts({
+      signer: env.admin.publicKey,
+      config: env.configPda,
+      stakeVault: stakeVaultPda,
+      mint: env.tokenMint,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    } as any)
+    .signers([env.admin])
+    .rpc();
+  return stakeVaultPda;
+}
+
+// Stakes `amount` GRT of `bettor` and returns its stake account PDA
+export async function stake(
+  env: TestEnv,
+  bettor: Bettor,
+  amount: number
+): Promise<PublicKey> {
+  const stakeAccountPda = deriveStakeAccountPda(
+    env.program.programId,
+    bettor.user.publicKey
+  );
+  await env.program.methods
+    .stake(new anchor.BN(amount))
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      stakeAccount: stakeAccountPda,
+      stakeVault: deriveStakeVaultPda(env.program.programId),
+      tokenAccount: bettor.tokenAccount,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([bettor.user])
+    .rpc();
+  return stakeAccountPda;
+}
+
+export async function unstake(env: TestEnv, bettor: Bettor, amount: number) {
+  await env.program.methods
+    .unstake(new anchor.BN(amount))
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      stakeAccount: deriveStakeAccountPda(
+        env.program.programId,
+        bettor.user.publicKey
+      ),
+    } as any)
+    .signers([bettor.user])
+    .rpc();
+}
+
+export async function withdrawStake(env: TestEnv, bettor: Bettor) {
+  await env.program.methods
+    .withdrawStake()
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      stakeAccount: deriveStakeAccountPda(
+        env.program.programId,
+        bettor.user.publicKey
+      ),
+      stakeVault: deriveStakeVaultPda(env.program.programId),
+      tokenAccount: bettor.tokenAccount,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    } as any)
+    .signers([bettor.user])
+    .rpc();
+}
+
+export async function initializePromotions(
+  env: TestEnv
+): Promise<PublicKey> {
+  const promoVaultPda = derivePromoVaultPda(env.program.programId);
+  await env.program.methods
+    .initializePromotions()
+    .accounts({
+      signer: env.admin.publicKey,
+      config: env.configPda,
+      promoVault: promoVaultPda,
+      mint: env.tokenMint,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    } as any)
+    .signers([env.admin])
+    .rpc();
+  return promoVaultPda;
+}
+
+// Mints a voucher of `amount` GRT for `user` and returns its PDA
+export async function mintVoucher(
+  env: TestEnv,
+  user: PublicKey,
+  amount: number,
+  expiresAt: number,
+  signer: Keypair = env.admin
+): Promise<PublicKey> {
+  const cfg = await env.program.account.config.fetch(env.configPda);
+  const voucherPda = deriveVoucherPda(
+    env.program.programId,
+    cfg.currentVoucherCounter.addn(1)
+  );
+  await env.program.methods
+    .mintVoucher(user, new anchor.BN(amount), new anchor.BN(expiresAt))
+    .accounts({
+      signer: signer.publicKey,
+      config: env.configPda,
+      voucher: voucherPda,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([signer])
+    .rpc();
+  return voucherPda;
+}
+
+export async function tokenBalance(
+  env: TestEnv,
+  tokenAccount: PublicKey
+): Promise<anchor.BN> {
+  const balance = await env.provider.connection.getTokenAccountBalance(
+    tokenAccount
+  );
+  return new anchor.BN(balance.value.amount);
+}
+
+// Decodes the program events emitted by the transaction `signature`
+export async function transactionEvents(
+  env: TestEnv,
+  signature: string
+): Promise<anchor.Event[]> {
+  const tx = await env.provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const parser = new anchor.EventParser(
+    env.program.programId,
+    env.program.coder
+  );
+  return [...parser.parseLogs(tx?.meta?.logMessages ?? [])];
+}
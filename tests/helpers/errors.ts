@@ -0,0 +1,27 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+// Asserts that `tx` fails with the program error `code`; a success or any other error fails the test
+export async function expectAnchorError(tx: Promise<unknown>, code: string) {
+  let error: any;
+  try {
+    await tx;
+  } catch (e: any) {
+    error = e;
+  }
+  expect(error, `expected ${code}, but the transaction succeeded`).to.exist;
+  const parsed = anchor.AnchorError.parse(error?.logs ?? []);
+  expect(parsed, `expected ${code}, got ${error}`).to.not.be.null;
+  expect(parsed!.error.errorCode.code).to.eq(code);
+}
+
+// Asserts that `tx` fails, for rejections raised outside the program (e.g. the token program)
+export async function expectFailure(tx: Promise<unknown>) {
+  let error: any;
+  try {
+    await tx;
+  } catch (e: any) {
+    error = e;
+  }
+  expect(error, "expected the transaction to fail").to.exist;
+}
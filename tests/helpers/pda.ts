@@ -62,3 +62,91 @@ export function deriveBetPda(
     programId
   )[0];
 }
+
+export function deriveUserRoundPositionPda(
+  programId: PublicKey,
+  round: PublicKey,
+  bettor: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("user_round_position"), round.toBuffer(), bettor.toBuffer()],
+    programId
+  )[0];
+}
+
+export function deriveJackpotPda(programId: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("jackpot")],
+    programId
+  )[0];
+}
+
+export function deriveJackpotVaultPda(programId: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("jackpot_vault")],
+    programId
+  )[0];
+}
+
+export function deriveKeeperRewardsPda(programId: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("keeper_rewards")],
+    programId
+  )[0];
+}
+
+export function deriveKeeperRewardVaultPda(programId: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("keeper_reward_vault")],
+    programId
+  )[0];
+}
+
+export function deriveKeeperStatsPda(programId: PublicKey, keeper: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("keeper_stats"), keeper.toBuffer()],
+    programId
+  )[0];
+}
+
+export function deriveReferrerPda(programId: PublicKey, authority: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("referrer"), authority.toBuffer()],
+    programId
+  )[0];
+}
+
+export function deriveReferralVaultPda(programId: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("referral_vault")],
+    programId
+  )[0];
+}
+
+export function deriveStakeVaultPda(programId: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("stake_vault")],
+    programId
+  )[0];
+}
+
+export function deriveStakeAccountPda(programId: PublicKey, owner: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("stake_account"), owner.toBuffer()],
+    programId
+  )[0];
+}
+
+export function derivePromoVaultPda(programId: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("promo_vault")],
+    programId
+  )[0];
+}
+
+export function deriveVoucherPda(programId: PublicKey, id: anchor.BN) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("voucher"), id.toArrayLike(Buffer, "le", 8)],
+    programId
+  )[0];
+}
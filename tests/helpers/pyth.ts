@@ -1,4 +1,17 @@
+import { PublicKey } from "@solana/web3.js";
+
 export const SOL_PRICE_FEED_ID =
   "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
 export const GOLD_PRICE_FEED_ID =
   "0x765d2ba906dbc32ca17cc11f5310a89e9ee1f6420508c63861f2f8ba4ee34bb2";
+
+// PriceUpdateV2 fixtures loaded by the local validator (tests/fixtures/generate.mjs)
+export const PRICE_FIXTURES = {
+  goldStart: new PublicKey("FbpFQu9AAW3EaP6G1nupgbyunmXz5hWvBrC6E8kGAmVb"), // 2000.00
+  goldUp: new PublicKey("EpWhAubfmybpRGbLMEUB6nsDddQzxz3Xttpn4wbCf96E"), // 2100.00
+  goldDown: new PublicKey("3fjQpZXZkvCCUK8tHjLuXZWPueG8W4nk1sdHFYsek9yc"), // 1900.00
+  goldFlat: new PublicKey("BZd4iGaskEw57k3vEZhngiTT6XWWNvhVF4PEbuMXmEYe"), // 2001.00, +5 bps
+  solStart: new PublicKey("BswtC7GRZcRtsUfMihcWPhAS3vismhaARppD4bfdUxr1"), // 150.00
+  solUp: new PublicKey("BRyKYaVCRRger4Caxyh9f1337ADB9evWfKTCAa4HwJPL"), // 165.00
+  solDown: new PublicKey("2ezwxpKB8vysjf8gbc7pxfxB54S3sZ74qyJ1p7rhWNcp"), // 135.00
+};
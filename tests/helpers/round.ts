@@ -0,0 +1,780 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Bettor, TestEnv } from "./config";
+import {
+  deriveAssetPda,
+  deriveBetPda,
+  deriveGroupAssetPda,
+  derivePromoVaultPda,
+  deriveRoundPda,
+  deriveUserRoundPositionPda,
+  deriveVaultPda,
+} from "./pda";
+import { stringToBytes } from "./bytes";
+import { sleep } from "./time";
+
+export type RoundAccounts = {
+  roundPda: PublicKey;
+  vaultPda: PublicKey;
+};
+
+export type GroupRoundAccounts = RoundAccounts & {
+  groupAssetPdas: PublicKey[];
+  assetPdas: PublicKey[][];
+};
+
+// Optional settlement accounts, all passed as None unless set
+export type SettlementExtras = {
+  keeperRewardPool?: PublicKey | null;
+  jackpot?: PublicKey | null;
+  jackpotVault?: PublicKey | null;
+  referralVault?: PublicKey | null;
+};
+
+export function writable(pubkey: PublicKey): AccountMeta {
+  return { pubkey, isSigner: false, isWritable: true };
+}
+
+export function readonly(pubkey: PublicKey): AccountMeta {
+  return { pubkey, isSigner: false, isWritable: false };
+}
+
+function errorCode(e: any): string | undefined {
+  return anchor.AnchorError.parse(e?.logs ?? [])?.error.errorCode.code;
+}
+
+// Retries `fn` while it fails with one of `codes`, e.g. until the round reaches its start or end time
+export async function retryWhile<T>(
+  fn: () => Promise<T>,
+  codes: string[],
+  timeoutMs: number = 60_000
+): Promise<T> {
+  const deadline = Date.now() + timeoutMs;
+  while (true) {
+    try {
+      return await fn();
+    } catch (e: any) {
+      if (!codes.includes(errorCode(e)) || Date.now() > deadline) {
+        throw e;
+      }
+      await sleep(500);
+    }
+  }
+}
+
+// Waits until the validator clock reaches `timestamp`
+export async function waitForChainTime(env: TestEnv, timestamp: number) {
+  const connection = env.provider.connection;
+  while (true) {
+    const blockTime = await connection.getBlockTime(await connection.getSlot());
+    if (blockTime !== null && blockTime >= timestamp) {
+      return;
+    }
+    await sleep(500);
+  }
+}
+
+export async function createRound(
+  env: TestEnv,
+  {
+    marketType = { singleAsset: {} } as any,
+    startInSecs = 3,
+    durationSecs = 20,
+    aggregation = { mean: {} } as any,
+    autoDistribute = false,
+    isJackpotRound = false,
+  } = {}
+): Promise<RoundAccounts> {
+  const { program } = env;
+  const now = Math.floor(Date.now() / 1000);
+  const start = now + startInSecs;
+  const end = start + durationSecs;
+  const cfg = await program.account.config.fetch(env.configPda);
+  const roundPda = deriveRoundPda(
+    program.programId,
+    cfg.currentRoundCounter.addn(1)
+  );
+  const vaultPda = deriveVaultPda(program.programId, roundPda);
+
+  await program.methods
+    .createRound(
+      marketType,
+      new anchor.BN(start),
+      new anchor.BN(end),
+      aggregation,
+      autoDistribute,
+      isJackpotRound
+    )
+    .accounts({
+      signer: env.admin.publicKey,
+      config: env.configPda,
+      round: roundPda,
+      vault: vaultPda,
+      mint: env.tokenMint,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    } as any)
+    .signers([env.admin])
+    .rpc();
+
+  return { roundPda, vaultPda };
+}
+
+// Starts a single-asset round at the price of `priceUpdate` once its start time is reached
+export async function startSingleRound(
+  env: TestEnv,
+  round: RoundAccounts,
+  priceUpdate: PublicKey
+) {
+  await retryWhile(
+    () =>
+      env.program.methods
+        .startRound()
+        .accounts({
+          signer: env.keeper.publicKey,
+          config: env.configPda,
+          round: round.roundPda,
+          priceUpdate,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([env.keeper])
+        .rpc(),
+    ["RoundNotReadyForStart"]
+  );
+}
+
+export async function placeBet(
+  env: TestEnv,
+  round: RoundAccounts,
+  bettor: Bettor,
+  amount: number | anchor.BN,
+  direction: any,
+  {
+    groupAsset = null as PublicKey | null,
+    asset = null as PublicKey | null,
+    referrer = null as PublicKey | null,
+    referrerAccount = null as PublicKey | null,
+    stakeAccount = null as PublicKey | null,
+    minWeight = null as anchor.BN | null,
+    maxTimestamp = null as anchor.BN | null,
+  } = {}
+): Promise<PublicKey> {
+  const { program } = env;
+  const r = await program.account.round.fetch(round.roundPda);
+  const betPda = deriveBetPda(
+    program.programId,
+    round.roundPda,
+    r.totalBets.addn(1)
+  );
+
+  await program.methods
+    .placeBet(
+      new anchor.BN(amount),
+      direction,
+      minWeight,
+      maxTimestamp,
+      referrer
+    )
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      groupAsset,
+      asset,
+      referrerAccount,
+      stakeAccount,
+      bet: betPda,
+      userRoundPosition: deriveUserRoundPositionPda(
+        program.programId,
+        round.roundPda,
+        bettor.user.publicKey
+      ),
+      vault: round.vaultPda,
+      tokenAccount: bettor.tokenAccount,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([bettor.user])
+    .rpc();
+
+  return betPda;
+}
+
+// Places a promotional bet staking the voucher amount from the promo vault
+export async function placeBetWithVoucher(
+  env: TestEnv,
+  round: RoundAccounts,
+  bettor: Bettor,
+  voucher: PublicKey,
+  direction: any,
+  {
+    groupAsset = null as PublicKey | null,
+    asset = null as PublicKey | null,
+  } = {}
+): Promise<PublicKey> {
+  const { program } = env;
+  const r = await program.account.round.fetch(round.roundPda);
+  const betPda = deriveBetPda(
+    program.programId,
+    round.roundPda,
+    r.totalBets.addn(1)
+  );
+
+  await program.methods
+    .placeBetWithVoucher(direction, null, null)
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      groupAsset,
+      asset,
+      voucher,
+      bet: betPda,
+      userRoundPosition: deriveUserRoundPositionPda(
+        program.programId,
+        round.roundPda,
+        bettor.user.publicKey
+      ),
+      vault: round.vaultPda,
+      promoVault: derivePromoVaultPda(program.programId),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([bettor.user])
+    .rpc();
+
+  return betPda;
+}
+
+// Settles a batch of single-asset bets at the price of `priceUpdate` once the round ended
+export async function settleSingleRound(
+  env: TestEnv,
+  round: RoundAccounts,
+  priceUpdate: PublicKey,
+  remainingAccounts: AccountMeta[],
+  extras: SettlementExtras = {}
+): Promise<string> {
+  return retryWhile(
+    () =>
+      env.program.methods
+        .settleSingleRound()
+        .accounts({
+          signer: env.keeper.publicKey,
+          config: env.configPda,
+          round: round.roundPda,
+          roundVault: round.vaultPda,
+          priceUpdate,
+          treasury: env.treasury.publicKey,
+          treasuryTokenAccount: env.treasuryTokenAccount,
+          keeperRewardPool: null,
+          jackpot: null,
+          jackpotVault: null,
+          referralVault: null,
+          ...extras,
+          mint: env.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .remainingAccounts(remainingAccounts)
+        .signers([env.keeper])
+        .rpc(),
+    ["RoundNotReadyForSettlement"]
+  );
+}
+
+export async function claimReward(
+  env: TestEnv,
+  round: RoundAccounts,
+  betPda: PublicKey,
+  bettor: Bettor,
+  promoVault: PublicKey | null = null
+) {
+  await env.program.methods
+    .claimReward()
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      roundVault: round.vaultPda,
+      bet: betPda,
+      bettorTokenAccount: bettor.tokenAccount,
+      promoVault,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([bettor.user])
+    .rpc();
+}
+
+// Claims every claimable bet of the bettor's position; `bets` must follow the position's bet_ids order
+export async function claimPositionRewards(
+  env: TestEnv,
+  round: RoundAccounts,
+  bettor: Bettor,
+  bets: PublicKey[],
+  promoVault: PublicKey | null = null
+) {
+  await env.program.methods
+    .claimPositionRewards()
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      roundVault: round.vaultPda,
+      userRoundPosition: deriveUserRoundPositionPda(
+        env.program.programId,
+        round.roundPda,
+        bettor.user.publicKey
+      ),
+      bettorTokenAccount: bettor.tokenAccount,
+      promoVault,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .remainingAccounts(bets.map(writable))
+    .signers([bettor.user])
+    .rpc();
+}
+
+// Claims bets across rounds; `remainingAccounts` holds [round, vault, bet] triples grouped by round
+export async function claimRewardsBatch(
+  env: TestEnv,
+  bettor: Bettor,
+  remainingAccounts: AccountMeta[],
+  promoVault: PublicKey | null = null
+) {
+  await env.program.methods
+    .claimRewardsBatch()
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      bettorTokenAccount: bettor.tokenAccount,
+      promoVault,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .remainingAccounts(remainingAccounts)
+    .signers([bettor.user])
+    .rpc();
+}
+
+// The [round, vault, bet] triple claimRewardsBatch expects for one bet
+export function claimTriple(round: RoundAccounts, betPda: PublicKey) {
+  return [
+    readonly(round.roundPda),
+    writable(round.vaultPda),
+    writable(betPda),
+  ];
+}
+
+// Pushes payouts of an auto-distribute round; `remainingAccounts` holds [bet, bettor ATA] pairs
+export async function distributeRewards(
+  env: TestEnv,
+  round: RoundAccounts,
+  remainingAccounts: AccountMeta[],
+  signer: Keypair = env.keeper
+) {
+  await env.program.methods
+    .distributeRewards()
+    .accounts({
+      signer: signer.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      roundVault: round.vaultPda,
+      promoVault: null,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    } as any)
+    .remainingAccounts(remainingAccounts)
+    .signers([signer])
+    .rpc();
+}
+
+// The [bet, bettor ATA] pair distributeRewards expects for one bet
+export function distributePair(betPda: PublicKey, tokenAccount: PublicKey) {
+  return [writable(betPda), writable(tokenAccount)];
+}
+
+// Sweeps what is left in the round vault to the treasury once the claim window expired
+export async function sweepUnclaimedRewards(
+  env: TestEnv,
+  round: RoundAccounts,
+  signer: Keypair = env.keeper
+) {
+  await env.program.methods
+    .sweepUnclaimedRewards()
+    .accounts({
+      signer: signer.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      roundVault: round.vaultPda,
+      treasury: env.treasury.publicKey,
+      treasuryTokenAccount: env.treasuryTokenAccount,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([signer])
+    .rpc();
+}
+
+// Cancels a round as admin; `remainingAccounts` holds [bet, bettor ATA] pairs ([bet, bettor ATA, GroupAsset] triples for GroupBattle)
+export async function cancelRound(
+  env: TestEnv,
+  round: RoundAccounts,
+  remainingAccounts: AccountMeta[] = [],
+  signer: Keypair = env.admin
+) {
+  await env.program.methods
+    .cancelRound()
+    .accounts({
+      signer: signer.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      roundVault: round.vaultPda,
+      treasury: env.treasury.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .remainingAccounts(remainingAccounts)
+    .signers([signer])
+    .rpc();
+}
+
+// Withdraws `amount` of a pending bet before the cutoff; null withdraws the whole bet
+export async function withdrawBet(
+  env: TestEnv,
+  round: RoundAccounts,
+  betPda: PublicKey,
+  bettor: Bettor,
+  amount: number | null,
+  {
+    groupAsset = null as PublicKey | null,
+    promoVault = null as PublicKey | null,
+    treasuryTokenAccount = env.treasuryTokenAccount as PublicKey | null,
+  } = {}
+) {
+  await env.program.methods
+    .withdrawBet(amount === null ? null : new anchor.BN(amount))
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      bet: betPda,
+      groupAsset,
+      userRoundPosition: deriveUserRoundPositionPda(
+        env.program.programId,
+        round.roundPda,
+        bettor.user.publicKey
+      ),
+      roundVault: round.vaultPda,
+      bettorTokenAccount: bettor.tokenAccount,
+      promoVault,
+      treasury: env.treasury.publicKey,
+      treasuryTokenAccount,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([bettor.user])
+    .rpc();
+}
+
+// Cashes out a pending single-asset bet between the cutoff and end time at the price of `priceUpdate`
+export async function cashOutBet(
+  env: TestEnv,
+  round: RoundAccounts,
+  betPda: PublicKey,
+  bettor: Bettor,
+  priceUpdate: PublicKey
+) {
+  await env.program.methods
+    .cashOutBet()
+    .accounts({
+      signer: bettor.user.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      bet: betPda,
+      userRoundPosition: deriveUserRoundPositionPda(
+        env.program.programId,
+        round.roundPda,
+        bettor.user.publicKey
+      ),
+      roundVault: round.vaultPda,
+      bettorTokenAccount: bettor.tokenAccount,
+      priceUpdate,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([bettor.user])
+    .rpc();
+}
+
+// Creates a Group Battle (or Asset Battle) round; `groups` lists the price update each asset is inserted with
+export async function createGroupRound(
+  env: TestEnv,
+  groups: PublicKey[][],
+  options: Parameters<typeof createRound>[1] = {}
+): Promise<GroupRoundAccounts> {
+  const { program } = env;
+  const round = await createRound(env, {
+    marketType: { groupBattle: {} },
+    ...options,
+  });
+
+  const groupAssetPdas: PublicKey[] = [];
+  const assetPdas: PublicKey[][] = [];
+  for (let g = 0; g < groups.length; g++) {
+    const groupAssetPda = deriveGroupAssetPda(
+      program.programId,
+      round.roundPda,
+      new anchor.BN(g + 1)
+    );
+    await program.methods
+      .insertGroupAsset(stringToBytes(`GRP ${g + 1}`))
+      .accounts({
+        signer: env.admin.publicKey,
+        config: env.configPda,
+        round: round.roundPda,
+        groupAsset: groupAssetPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([env.admin])
+      .rpc();
+    groupAssetPdas.push(groupAssetPda);
+
+    const groupAssets: PublicKey[] = [];
+    for (let a = 0; a < groups[g].length; a++) {
+      const assetPda = deriveAssetPda(
+        program.programId,
+        groupAssetPda,
+        new anchor.BN(a + 1)
+      );
+      await program.methods
+        .insertAsset(stringToBytes(`A${g + 1}${a + 1}`), 0)
+        .accounts({
+          signer: env.admin.publicKey,
+          config: env.configPda,
+          round: round.roundPda,
+          groupAsset: groupAssetPda,
+          asset: assetPda,
+          feedPriceAccount: groups[g][a],
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([env.admin])
+        .rpc();
+      groupAssets.push(assetPda);
+    }
+    assetPdas.push(groupAssets);
+  }
+
+  return { ...round, groupAssetPdas, assetPdas };
+}
+
+// Captures the start price of every asset of group `g` from `prices` once the round started
+export async function captureStartPrice(
+  env: TestEnv,
+  round: GroupRoundAccounts,
+  g: number,
+  prices: PublicKey[]
+) {
+  await retryWhile(
+    () =>
+      env.program.methods
+        .captureStartPrice()
+        .accounts({
+          signer: env.keeper.publicKey,
+          config: env.configPda,
+          round: round.roundPda,
+          groupAsset: round.groupAssetPdas[g],
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .remainingAccounts(
+          round.assetPdas[g].flatMap((assetPda, a) => [
+            writable(assetPda),
+            readonly(prices[a]),
+          ])
+        )
+        .signers([env.keeper])
+        .rpc(),
+    ["RoundNotReadyForStart"]
+  );
+}
+
+export async function finalizeStartGroupAsset(
+  env: TestEnv,
+  round: GroupRoundAccounts,
+  g: number
+) {
+  await env.program.methods
+    .finalizeStartGroupAsset()
+    .accounts({
+      signer: env.keeper.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      groupAsset: round.groupAssetPdas[g],
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .remainingAccounts(round.assetPdas[g].map(writable))
+    .signers([env.keeper])
+    .rpc();
+}
+
+// Captures every asset's start price from `prices`, finalizes the groups and starts the round
+export async function startGroupRound(
+  env: TestEnv,
+  round: GroupRoundAccounts,
+  prices: PublicKey[][]
+) {
+  const { program, keeper } = env;
+  for (let g = 0; g < round.groupAssetPdas.length; g++) {
+    await captureStartPrice(env, round, g, prices[g]);
+    await finalizeStartGroupAsset(env, round, g);
+  }
+
+  await program.methods
+    .finalizeStartGroups()
+    .accounts({
+      signer: keeper.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .remainingAccounts(round.groupAssetPdas.map(readonly))
+    .signers([keeper])
+    .rpc();
+
+  await program.methods
+    .startRound()
+    .accounts({
+      signer: keeper.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      priceUpdate: null,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .signers([keeper])
+    .rpc();
+}
+
+// Captures every asset's end price from `prices` once the round ended and ranks the groups (or the assets of an AssetBattle)
+export async function endGroupRound(
+  env: TestEnv,
+  round: GroupRoundAccounts,
+  prices: PublicKey[][]
+) {
+  const { program, keeper } = env;
+  for (let g = 0; g < round.groupAssetPdas.length; g++) {
+    await retryWhile(
+      () =>
+        program.methods
+          .captureEndPrice()
+          .accounts({
+            signer: keeper.publicKey,
+            config: env.configPda,
+            round: round.roundPda,
+            groupAsset: round.groupAssetPdas[g],
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .remainingAccounts(
+            round.assetPdas[g].flatMap((assetPda, a) => [
+              writable(assetPda),
+              readonly(prices[g][a]),
+            ])
+          )
+          .signers([keeper])
+          .rpc(),
+      ["RoundNotReadyForSettlement"]
+    );
+    await program.methods
+      .finalizeEndGroupAsset()
+      .accounts({
+        signer: keeper.publicKey,
+        config: env.configPda,
+        round: round.roundPda,
+        groupAsset: round.groupAssetPdas[g],
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(round.assetPdas[g].map(writable))
+      .signers([keeper])
+      .rpc();
+  }
+
+  const r = await program.account.round.fetch(round.roundPda);
+  if (r.marketType.assetBattle) {
+    // ranked in batches of at most MAX_REMAINING_ACCOUNTS assets
+    const assetPdas = round.assetPdas.flat();
+    for (let i = 0; i < assetPdas.length; i += 20) {
+      await program.methods
+        .finalizeEndAssets()
+        .accounts({
+          signer: keeper.publicKey,
+          config: env.configPda,
+          round: round.roundPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .remainingAccounts(assetPdas.slice(i, i + 20).map(writable))
+        .signers([keeper])
+        .rpc();
+    }
+    return;
+  }
+
+  await program.methods
+    .finalizeEndGroups()
+    .accounts({
+      signer: keeper.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .remainingAccounts(round.groupAssetPdas.map(readonly))
+    .signers([keeper])
+    .rpc();
+}
+
+export async function settleGroupRound(
+  env: TestEnv,
+  round: RoundAccounts,
+  remainingAccounts: AccountMeta[],
+  extras: SettlementExtras = {}
+) {
+  await env.program.methods
+    .settleGroupRound()
+    .accounts({
+      signer: env.keeper.publicKey,
+      config: env.configPda,
+      round: round.roundPda,
+      roundVault: round.vaultPda,
+      treasury: env.treasury.publicKey,
+      treasuryTokenAccount: env.treasuryTokenAccount,
+      keeperRewardPool: null,
+      jackpot: null,
+      jackpotVault: null,
+      referralVault: null,
+      ...extras,
+      mint: env.tokenMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    } as any)
+    .remainingAccounts(remainingAccounts)
+    .signers([env.keeper])
+    .rpc();
+}
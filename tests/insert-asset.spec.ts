@@ -88,7 +88,10 @@ describe("insertAsset", () => {
       .createRound(
         { groupBattle: {} },
         new anchor.BN(start),
-        new anchor.BN(end)
+        new anchor.BN(end),
+        { mean: {} },
+        false,
+        false
       )
       .accounts({
         signer: admin.publicKey,
@@ -140,7 +143,7 @@ describe("insertAsset", () => {
     );
 
     await program.methods
-      .insertAsset(symbol)
+      .insertAsset(symbol, 0)
       .accounts({
         signer: admin.publicKey,
         config: configPda,
@@ -159,6 +162,7 @@ describe("insertAsset", () => {
     expect(asset.round.toString()).to.eq(roundPda.toString());
     expect(asset.symbol.toString()).to.eq(symbol.toString());
     expect(asset.feedId.toString()).to.eq(priceFeedAccount.toString());
+    expect(asset.weightBps).to.eq(0);
 
     const updatedGroupAsset = await program.account.groupAsset.fetch(
       groupAssetPda
@@ -167,6 +171,43 @@ describe("insertAsset", () => {
       nextAssetId.toString()
     );
   });
+  it("fails asset weight exceeding 10000 bps", async () => {
+    const priceFeedAccount = pythSolanaReceiver.getPriceFeedAccountAddress(
+      0,
+      SOL_PRICE_FEED_ID
+    );
+    const ga = await program.account.groupAsset.fetch(groupAssetPda);
+    const nextAssetId = ga.totalAssets.addn(1);
+    const assetPda = deriveAssetPda(
+      program.programId,
+      groupAssetPda,
+      nextAssetId
+    );
+
+    try {
+      await program.methods
+        .insertAsset(stringToBytes("W"), 10_001)
+        .accounts({
+          signer: admin.publicKey,
+          config: configPda,
+          round: roundPda,
+          groupAsset: groupAssetPda,
+          asset: assetPda,
+          feedPriceAccount: priceFeedAccount,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([admin])
+        .rpc();
+
+      throw new Error("should fail");
+    } catch (e: any) {
+      const parsed = (anchor as any).AnchorError?.parse?.(e?.logs);
+      if (parsed) {
+        expect(parsed.error.errorCode.code).to.eq("InvalidAssetWeight");
+      }
+    }
+  });
+
   it("fails max assets reached", async () => {
     const priceFeedAccount = pythSolanaReceiver.getPriceFeedAccountAddress(
       0,
@@ -184,7 +225,7 @@ describe("insertAsset", () => {
       );
 
       await program.methods
-        .insertAsset(stringToBytes(`S${i}`))
+        .insertAsset(stringToBytes(`S${i}`), 0)
         .accounts({
           signer: admin.publicKey,
           config: configPda,
@@ -209,7 +250,7 @@ describe("insertAsset", () => {
       );
 
       await program.methods
-        .insertAsset(stringToBytes("X"))
+        .insertAsset(stringToBytes("X"), 0)
         .accounts({
           signer: admin.publicKey,
           config: configPda,
@@ -0,0 +1,265 @@
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  JackpotAccounts,
+  TestEnv,
+  initializeJackpot,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+} from "./helpers/config";
+import {
+  SettlementExtras,
+  claimReward,
+  createRound,
+  placeBet,
+  settleSingleRound,
+  startSingleRound,
+  writable,
+} from "./helpers/round";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError, expectFailure } from "./helpers/errors";
+
+describe("jackpot", () => {
+  const BET_AMOUNT = 10_000_000;
+  const FEE_BPS = 2_000; // fee_single_asset_bps from setupEnv
+  const JACKPOT_FEE_BPS = 1_000;
+
+  let env: TestEnv;
+  let jackpot: JackpotAccounts;
+  let extras: SettlementExtras;
+  let upBettor: Bettor;
+  let downBettor: Bettor;
+
+  // Fee and jackpot slice of a round with `pool` staked and a winner
+  function fees(pool: number) {
+    const fee = new anchor.BN(pool).muln(FEE_BPS).divn(10_000);
+    const jackpotFee = fee.muln(JACKPOT_FEE_BPS).divn(10_000);
+    return { fee, jackpotFee };
+  }
+
+  // Plays a round with one bet per entry of `directions`, each placed by the matching bettor
+  async function playRound(
+    directions: [Bettor, any][],
+    { isJackpotRound = false } = {}
+  ) {
+    const round = await createRound(env, { isJackpotRound });
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    const bets: PublicKey[] = [];
+    for (const [bettor, direction] of directions) {
+      bets.push(await placeBet(env, round, bettor, BET_AMOUNT, direction));
+    }
+    return { round, bets };
+  }
+
+  before(async () => {
+    env = await setupEnv();
+    upBettor = await newBettor(env);
+    downBettor = await newBettor(env);
+  });
+
+  it("fails creating a jackpot round before the jackpot is initialized", async () => {
+    await expectAnchorError(
+      createRound(env, { isJackpotRound: true }),
+      "JackpotNotInitialized"
+    );
+  });
+
+  it("initializeJackpot creates the jackpot and its vault", async () => {
+    jackpot = await initializeJackpot(env);
+    extras = {
+      jackpot: jackpot.jackpotPda,
+      jackpotVault: jackpot.jackpotVaultPda,
+    };
+
+    const cfg = await env.program.account.config.fetch(env.configPda);
+    expect(cfg.jackpotEnabled).to.be.true;
+
+    const j = await env.program.account.jackpot.fetch(jackpot.jackpotPda);
+    expect(j.vault.toBase58()).to.eq(jackpot.jackpotVaultPda.toBase58());
+    expect(j.totalFunded.toString()).to.eq("0");
+    expect(j.totalPaidOut.toString()).to.eq("0");
+
+    const vault = await getAccount(
+      env.provider.connection,
+      jackpot.jackpotVaultPda
+    );
+    expect(vault.mint.toBase58()).to.eq(env.tokenMint.toBase58());
+    expect(vault.owner.toBase58()).to.eq(jackpot.jackpotPda.toBase58());
+    expect(vault.amount.toString()).to.eq("0");
+
+    await updateConfig(env, { newJackpotFeeBps: JACKPOT_FEE_BPS });
+  });
+
+  it("fails initializeJackpot twice", async () => {
+    // the jackpot PDA already exists, so the system program rejects the init
+    await expectFailure(initializeJackpot(env));
+  });
+
+  it("fails settlement without jackpot accounts once the jackpot is enabled", async () => {
+    const { round, bets } = await playRound([
+      [upBettor, { up: {} }],
+      [downBettor, { down: {} }],
+    ]);
+    await expectAnchorError(
+      settleSingleRound(env, round, PRICE_FIXTURES.goldUp, bets.map(writable)),
+      "MissingJackpotAccount"
+    );
+  });
+
+  it("settlement routes jackpot_fee_bps of the fee to the jackpot vault", async () => {
+    const { round, bets } = await playRound([
+      [upBettor, { up: {} }],
+      [downBettor, { down: {} }],
+    ]);
+    const { fee, jackpotFee } = fees(2 * BET_AMOUNT);
+    const jackpotBefore = await env.program.account.jackpot.fetch(
+      jackpot.jackpotPda
+    );
+    const vaultBefore = await tokenBalance(env, jackpot.jackpotVaultPda);
+    const treasuryBefore = await tokenBalance(env, env.treasuryTokenAccount);
+
+    await settleSingleRound(
+      env,
+      round,
+      PRICE_FIXTURES.goldUp,
+      bets.map(writable),
+      extras
+    );
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.totalFeeCollected.toString()).to.eq(fee.toString());
+    // the treasury takes the rest of the fee
+    const treasuryAfter = await tokenBalance(env, env.treasuryTokenAccount);
+    expect(treasuryAfter.sub(treasuryBefore).toString()).to.eq(
+      fee.sub(jackpotFee).toString()
+    );
+    expect(r.jackpotContribution.toString()).to.eq(jackpotFee.toString());
+    expect(r.totalRewardPool.toString()).to.eq(
+      new anchor.BN(2 * BET_AMOUNT).sub(fee).toString()
+    );
+
+    const vaultAfter = await tokenBalance(env, jackpot.jackpotVaultPda);
+    expect(vaultAfter.sub(vaultBefore).toString()).to.eq(
+      jackpotFee.toString()
+    );
+    const j = await env.program.account.jackpot.fetch(jackpot.jackpotPda);
+    expect(j.totalFunded.sub(jackpotBefore.totalFunded).toString()).to.eq(
+      jackpotFee.toString()
+    );
+  });
+
+  it("settlement rolls a no-winner reward pool into the jackpot", async () => {
+    // both bets are Down and the price goes Up: nobody wins
+    const { round, bets } = await playRound([
+      [upBettor, { down: {} }],
+      [downBettor, { down: {} }],
+    ]);
+    const { fee, jackpotFee } = fees(2 * BET_AMOUNT);
+    const rewardPool = new anchor.BN(2 * BET_AMOUNT).sub(fee);
+    const vaultBefore = await tokenBalance(env, jackpot.jackpotVaultPda);
+
+    await settleSingleRound(
+      env,
+      round,
+      PRICE_FIXTURES.goldUp,
+      bets.map(writable),
+      extras
+    );
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.winnersWeight.toString()).to.eq("0");
+    expect(r.jackpotContribution.toString()).to.eq(
+      jackpotFee.add(rewardPool).toString()
+    );
+
+    const vaultAfter = await tokenBalance(env, jackpot.jackpotVaultPda);
+    expect(vaultAfter.sub(vaultBefore).toString()).to.eq(
+      jackpotFee.add(rewardPool).toString()
+    );
+    // nothing is left in the round vault for anyone to claim
+    expect((await tokenBalance(env, round.vaultPda)).toString()).to.eq("0");
+  });
+
+  it("jackpot round receives the jackpot into total_reward_pool", async () => {
+    const { round, bets } = await playRound(
+      [
+        [upBettor, { up: {} }],
+        [downBettor, { down: {} }],
+      ],
+      { isJackpotRound: true }
+    );
+    const { fee, jackpotFee } = fees(2 * BET_AMOUNT);
+    const jackpotAmount = await tokenBalance(env, jackpot.jackpotVaultPda);
+    expect(jackpotAmount.gtn(0)).to.be.true;
+
+    await settleSingleRound(
+      env,
+      round,
+      PRICE_FIXTURES.goldUp,
+      bets.map(writable),
+      extras
+    );
+
+    // the jackpot as it was before this round, plus the pool net of the fee
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.jackpotPayout.toString()).to.eq(jackpotAmount.toString());
+    expect(r.totalRewardPool.toString()).to.eq(
+      new anchor.BN(2 * BET_AMOUNT).sub(fee).add(jackpotAmount).toString()
+    );
+
+    // only this round's own fee slice is left in the jackpot
+    const vaultAfter = await tokenBalance(env, jackpot.jackpotVaultPda);
+    expect(vaultAfter.toString()).to.eq(jackpotFee.toString());
+    const j = await env.program.account.jackpot.fetch(jackpot.jackpotPda);
+    expect(j.totalPaidOut.toString()).to.eq(jackpotAmount.toString());
+
+    const before = await tokenBalance(env, upBettor.tokenAccount);
+    await claimReward(env, round, bets[0], upBettor);
+    const after = await tokenBalance(env, upBettor.tokenAccount);
+    expect(after.sub(before).toString()).to.eq(r.totalRewardPool.toString());
+  });
+
+  it("jackpot round without winners pays out the pre-slice jackpot and rolls it back", async () => {
+    const { round, bets } = await playRound(
+      [
+        [upBettor, { down: {} }],
+        [downBettor, { down: {} }],
+      ],
+      { isJackpotRound: true }
+    );
+    const { fee, jackpotFee } = fees(2 * BET_AMOUNT);
+    const jackpotAmount = await tokenBalance(env, jackpot.jackpotVaultPda);
+    expect(jackpotAmount.gtn(0)).to.be.true;
+
+    await settleSingleRound(
+      env,
+      round,
+      PRICE_FIXTURES.goldUp,
+      bets.map(writable),
+      extras
+    );
+
+    // the payout excludes the slice this same settlement added to the vault
+    const r = await env.program.account.round.fetch(round.roundPda);
+    const rewardPool = new anchor.BN(2 * BET_AMOUNT)
+      .sub(fee)
+      .add(jackpotAmount);
+    expect(r.jackpotPayout.toString()).to.eq(jackpotAmount.toString());
+    expect(r.totalRewardPool.toString()).to.eq(rewardPool.toString());
+    expect(r.jackpotContribution.toString()).to.eq(
+      jackpotFee.add(rewardPool).toString()
+    );
+
+    // nobody won, so the jackpot keeps its balance, the slice and the pool
+    const vaultAfter = await tokenBalance(env, jackpot.jackpotVaultPda);
+    expect(vaultAfter.toString()).to.eq(
+      jackpotFee.add(rewardPool).toString()
+    );
+    expect((await tokenBalance(env, round.vaultPda)).toString()).to.eq("0");
+  });
+});
@@ -0,0 +1,227 @@
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  KeeperRewardsAccounts,
+  TestEnv,
+  claimKeeperRewards,
+  initializeKeeperRewards,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+} from "./helpers/config";
+import {
+  GroupRoundAccounts,
+  captureStartPrice,
+  createGroupRound,
+  createRound,
+  finalizeStartGroupAsset,
+  placeBet,
+  settleSingleRound,
+  startSingleRound,
+  writable,
+} from "./helpers/round";
+import { deriveKeeperStatsPda } from "./helpers/pda";
+import { createAta } from "./helpers/token";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError, expectFailure } from "./helpers/errors";
+
+describe("keeperRewards", () => {
+  const BOUNTY_PER_UNIT = 100_000;
+  const { solStart } = PRICE_FIXTURES;
+
+  let env: TestEnv;
+  let keeperRewards: KeeperRewardsAccounts;
+  let keeperStatsPda: PublicKey;
+  let keeperTokenAccount: PublicKey;
+  let upBettor: Bettor;
+  let downBettor: Bettor;
+  // one group of two SOL assets, cranked step by step
+  let groupRound: GroupRoundAccounts;
+
+  // The keeper's stats, zeroed before its first crank
+  async function keeperStats() {
+    const stats = await env.program.account.keeperStats.fetchNullable(
+      keeperStatsPda
+    );
+    return {
+      unitsProcessed: stats?.unitsProcessed ?? new anchor.BN(0),
+      totalEarned: stats?.totalEarned ?? new anchor.BN(0),
+      totalClaimed: stats?.totalClaimed ?? new anchor.BN(0),
+    };
+  }
+
+  before(async () => {
+    env = await setupEnv();
+    upBettor = await newBettor(env);
+    downBettor = await newBettor(env);
+    keeperStatsPda = deriveKeeperStatsPda(
+      env.program.programId,
+      env.keeper.publicKey
+    );
+    keeperTokenAccount = await createAta(
+      env.provider.connection,
+      env.tokenMint,
+      env.keeper
+    );
+    await updateConfig(env, {
+      newKeeperBountyPerUnit: new anchor.BN(BOUNTY_PER_UNIT),
+    });
+  });
+
+  it("initializeKeeperRewards creates the vault and points keeper_reward_pool at it", async () => {
+    keeperRewards = await initializeKeeperRewards(env);
+
+    const cfg = await env.program.account.config.fetch(env.configPda);
+    expect(cfg.keeperRewardsEnabled).to.be.true;
+    expect(cfg.keeperRewardPool.toBase58()).to.eq(
+      keeperRewards.keeperRewardVaultPda.toBase58()
+    );
+
+    const vault = await getAccount(
+      env.provider.connection,
+      keeperRewards.keeperRewardVaultPda
+    );
+    expect(vault.mint.toBase58()).to.eq(env.tokenMint.toBase58());
+    expect(vault.owner.toBase58()).to.eq(
+      keeperRewards.keeperRewardsPda.toBase58()
+    );
+    expect(vault.amount.toString()).to.eq("0");
+
+    // 20% of every fee funds the vault from now on
+    await updateConfig(env, {
+      newFeeSchedule: { treasuryBps: 8_000, keeperPoolBps: 2_000, burnBps: 0 },
+    });
+  });
+
+  it("fails initializeKeeperRewards twice", async () => {
+    // the keeper rewards PDA already exists, so the system program rejects the init
+    await expectFailure(initializeKeeperRewards(env));
+  });
+
+  it("captureStartPrice accrues keeper_bounty_per_unit per asset to the signer's keeper stats", async () => {
+    groupRound = await createGroupRound(env, [[solStart, solStart]]);
+    const before = await keeperStats();
+
+    await captureStartPrice(env, groupRound, 0, [solStart, solStart]);
+
+    const after = await keeperStats();
+    expect(after.unitsProcessed.sub(before.unitsProcessed).toNumber()).to.eq(
+      2
+    );
+    expect(after.totalEarned.sub(before.totalEarned).toNumber()).to.eq(
+      2 * BOUNTY_PER_UNIT
+    );
+    const stats = await env.program.account.keeperStats.fetch(keeperStatsPda);
+    expect(stats.keeper.toBase58()).to.eq(env.keeper.publicKey.toBase58());
+  });
+
+  it("fails claimKeeperRewards from an empty vault", async () => {
+    // the bounty is accrued, but no fee has funded the vault yet
+    await expectAnchorError(
+      claimKeeperRewards(env, env.keeper, keeperTokenAccount),
+      "KeeperRewardVaultEmpty"
+    );
+  });
+
+  it("settleSingleRound accrues keeper_bounty_per_unit per settled bet", async () => {
+    const round = await createRound(env);
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    const bets = [
+      await placeBet(env, round, upBettor, 10_000_000, { up: {} }),
+      await placeBet(env, round, downBettor, 10_000_000, { down: {} }),
+    ];
+    const before = await keeperStats();
+    const vaultBefore = await tokenBalance(
+      env,
+      keeperRewards.keeperRewardVaultPda
+    );
+
+    await settleSingleRound(
+      env,
+      round,
+      PRICE_FIXTURES.goldUp,
+      bets.map(writable),
+      { keeperRewardPool: keeperRewards.keeperRewardVaultPda }
+    );
+
+    const after = await keeperStats();
+    expect(after.unitsProcessed.sub(before.unitsProcessed).toNumber()).to.eq(
+      2
+    );
+    expect(after.totalEarned.sub(before.totalEarned).toNumber()).to.eq(
+      2 * BOUNTY_PER_UNIT
+    );
+
+    // the keeper pool leg of the 4 GRT fee funds the vault
+    const vaultAfter = await tokenBalance(
+      env,
+      keeperRewards.keeperRewardVaultPda
+    );
+    expect(vaultAfter.sub(vaultBefore).toString()).to.eq("800000");
+  });
+
+  it("claimKeeperRewards pays the accrued bounty from the keeper reward vault", async () => {
+    const before = await keeperStats();
+    const unclaimed = before.totalEarned.sub(before.totalClaimed);
+    const balanceBefore = await tokenBalance(env, keeperTokenAccount);
+
+    await claimKeeperRewards(env, env.keeper, keeperTokenAccount);
+
+    const balanceAfter = await tokenBalance(env, keeperTokenAccount);
+    expect(balanceAfter.sub(balanceBefore).toString()).to.eq(
+      unclaimed.toString()
+    );
+    const after = await keeperStats();
+    expect(after.totalClaimed.toString()).to.eq(after.totalEarned.toString());
+    const rewards = await env.program.account.keeperRewards.fetch(
+      keeperRewards.keeperRewardsPda
+    );
+    expect(rewards.totalPaidOut.toString()).to.eq(unclaimed.toString());
+  });
+
+  it("fails claimKeeperRewards with nothing to claim", async () => {
+    await expectAnchorError(
+      claimKeeperRewards(env, env.keeper, keeperTokenAccount),
+      "NothingToClaim"
+    );
+  });
+
+  it("claimKeeperRewards pays the vault balance when it cannot cover the whole bounty", async () => {
+    // a bounty larger than what is left in the vault, accrued on two assets
+    await updateConfig(env, {
+      newKeeperBountyPerUnit: new anchor.BN(1_000_000),
+    });
+    await finalizeStartGroupAsset(env, groupRound, 0);
+
+    const vaultBalance = await tokenBalance(
+      env,
+      keeperRewards.keeperRewardVaultPda
+    );
+    const before = await keeperStats();
+    const unclaimed = before.totalEarned.sub(before.totalClaimed);
+    expect(unclaimed.gt(vaultBalance)).to.be.true;
+    const balanceBefore = await tokenBalance(env, keeperTokenAccount);
+
+    await claimKeeperRewards(env, env.keeper, keeperTokenAccount);
+
+    const balanceAfter = await tokenBalance(env, keeperTokenAccount);
+    expect(balanceAfter.sub(balanceBefore).toString()).to.eq(
+      vaultBalance.toString()
+    );
+    const vaultAfter = await tokenBalance(
+      env,
+      keeperRewards.keeperRewardVaultPda
+    );
+    expect(vaultAfter.toString()).to.eq("0");
+
+    // the rest stays claimable once fees refill the vault
+    const after = await keeperStats();
+    expect(after.totalEarned.sub(after.totalClaimed).toString()).to.eq(
+      unclaimed.sub(vaultBalance).toString()
+    );
+  });
+});
@@ -0,0 +1,280 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  initializeJackpot,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  claimReward,
+  createGroupRound,
+  createRound,
+  endGroupRound,
+  placeBet,
+  settleGroupRound,
+  settleSingleRound,
+  startGroupRound,
+  startSingleRound,
+  writable,
+} from "./helpers/round";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
+
+describe("loss rebate", () => {
+  const BET_AMOUNT = 10_000_000;
+  const FEE_BPS = 2_000; // fee_single_asset_bps from setupEnv
+  const GROUP_FEE_BPS = 2_500; // fee_group_battle_bps from setupEnv
+  const LOSS_REBATE_BPS = 1_000;
+  const { solStart, solUp, solDown } = PRICE_FIXTURES;
+
+  let env: TestEnv;
+  // winner bets Up, loser and lateLoser bet Down
+  let winner: Bettor;
+  let loser: Bettor;
+  let lateLoser: Bettor;
+  let disabledRound: RoundAccounts;
+  let round: RoundAccounts;
+  let bets: { winner: PublicKey; loser: PublicKey; lateLoser: PublicKey };
+
+  // loss_rebate_bps of a losing stake
+  const lossRebate = new anchor.BN(BET_AMOUNT)
+    .muln(LOSS_REBATE_BPS)
+    .divn(10_000);
+  // Reward pool of a round with `pool` staked, before loss rebates
+  function rewardPool(pool: number, feeBps: number = FEE_BPS) {
+    return new anchor.BN(pool).sub(
+      new anchor.BN(pool).muln(feeBps).divn(10_000)
+    );
+  }
+
+  before(async () => {
+    env = await setupEnv();
+    winner = await newBettor(env);
+    loser = await newBettor(env);
+    lateLoser = await newBettor(env);
+  });
+
+  it("fails claimReward on a losing bet when the loss rebate is disabled", async () => {
+    disabledRound = await createRound(env);
+    await startSingleRound(env, disabledRound, PRICE_FIXTURES.goldStart);
+    const winnerBet = await placeBet(
+      env,
+      disabledRound,
+      winner,
+      BET_AMOUNT,
+      { up: {} }
+    );
+    const loserBet = await placeBet(
+      env,
+      disabledRound,
+      loser,
+      BET_AMOUNT,
+      { down: {} }
+    );
+    await settleSingleRound(env, disabledRound, PRICE_FIXTURES.goldUp, [
+      writable(winnerBet),
+      writable(loserBet),
+    ]);
+
+    const bet = await env.program.account.bet.fetch(loserBet);
+    expect(bet.lossRebate.toString()).to.eq("0");
+    await expectAnchorError(
+      claimReward(env, disabledRound, loserBet, loser),
+      "BetNotWonOrDraw"
+    );
+  });
+
+  it("createRound snapshots the config's loss rebate bps", async () => {
+    await updateConfig(env, { newLossRebateBps: LOSS_REBATE_BPS });
+
+    round = await createRound(env);
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.lossRebateBps).to.eq(LOSS_REBATE_BPS);
+    // rounds created before the change keep their own snapshot
+    const earlier = await env.program.account.round.fetch(
+      disabledRound.roundPda
+    );
+    expect(earlier.lossRebateBps).to.eq(0);
+  });
+
+  it("settleSingleRound sets aside losing bets' rebates from the reward pool", async () => {
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    bets = {
+      winner: await placeBet(env, round, winner, BET_AMOUNT, { up: {} }),
+      loser: await placeBet(env, round, loser, BET_AMOUNT, { down: {} }),
+      lateLoser: await placeBet(env, round, lateLoser, BET_AMOUNT, {
+        down: {},
+      }),
+    };
+
+    // the first batch leaves lateLoser for a second one
+    await settleSingleRound(env, round, PRICE_FIXTURES.goldUp, [
+      writable(bets.winner),
+      writable(bets.loser),
+    ]);
+
+    const bet = await env.program.account.bet.fetch(bets.loser);
+    expect(bet.status).to.deep.equal({ lost: {} });
+    expect(bet.lossRebate.toString()).to.eq(lossRebate.toString());
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.status).to.deep.equal({ pendingSettlement: {} });
+    expect(r.lossRebate.toString()).to.eq(lossRebate.toString());
+    expect(r.totalRewardPool.toString()).to.eq(
+      rewardPool(3 * BET_AMOUNT).sub(lossRebate).toString()
+    );
+  });
+
+  it("fails settleSingleRound with a bet already settled in an earlier batch", async () => {
+    await expectAnchorError(
+      settleSingleRound(env, round, PRICE_FIXTURES.goldUp, [
+        writable(bets.loser),
+      ]),
+      "InvalidBetStatus"
+    );
+
+    // the resubmitted bet's rebate is not set aside twice
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.lossRebate.toString()).to.eq(lossRebate.toString());
+  });
+
+  it("settleSingleRound sets aside the rest of the losing rebates in a later batch", async () => {
+    await settleSingleRound(env, round, PRICE_FIXTURES.goldUp, [
+      writable(bets.lateLoser),
+    ]);
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.status).to.deep.equal({ ended: {} });
+    expect(r.lossRebate.toString()).to.eq(lossRebate.muln(2).toString());
+    expect(r.totalRewardPool.toString()).to.eq(
+      rewardPool(3 * BET_AMOUNT).sub(lossRebate.muln(2)).toString()
+    );
+    // the rebates stay in the vault next to the reward pool
+    expect((await tokenBalance(env, round.vaultPda)).toString()).to.eq(
+      r.totalRewardPool.add(r.lossRebate).toString()
+    );
+  });
+
+  it("claimReward pays a losing bet its loss rebate", async () => {
+    for (const [bettor, betPda] of [
+      [loser, bets.loser],
+      [lateLoser, bets.lateLoser],
+    ] as [Bettor, PublicKey][]) {
+      const before = await tokenBalance(env, bettor.tokenAccount);
+
+      await claimReward(env, round, betPda, bettor);
+
+      const after = await tokenBalance(env, bettor.tokenAccount);
+      expect(after.sub(before).toString()).to.eq(lossRebate.toString());
+    }
+  });
+
+  it("claimReward pays winners from the reduced reward pool", async () => {
+    const r = await env.program.account.round.fetch(round.roundPda);
+    const before = await tokenBalance(env, winner.tokenAccount);
+
+    await claimReward(env, round, bets.winner, winner);
+
+    // the only winner takes the whole reward pool net of the rebates
+    const after = await tokenBalance(env, winner.tokenAccount);
+    expect(after.sub(before).toString()).to.eq(r.totalRewardPool.toString());
+    expect((await tokenBalance(env, round.vaultPda)).toString()).to.eq("0");
+  });
+
+  it("settleGroupRound sets aside losing bets' rebates from the reward pool", async () => {
+    // one SOL asset per group: group 1 goes up and wins, group 2 goes down
+    const startPrices = [[solStart], [solStart]];
+    const groupRound = await createGroupRound(env, startPrices, {
+      durationSecs: 30,
+    });
+    await startGroupRound(env, groupRound, startPrices);
+    const winnerBet = await placeBet(
+      env,
+      groupRound,
+      winner,
+      BET_AMOUNT,
+      { up: {} },
+      { groupAsset: groupRound.groupAssetPdas[0] }
+    );
+    const loserBet = await placeBet(
+      env,
+      groupRound,
+      loser,
+      BET_AMOUNT,
+      { up: {} },
+      { groupAsset: groupRound.groupAssetPdas[1] }
+    );
+    await endGroupRound(env, groupRound, [[solUp], [solDown]]);
+
+    await settleGroupRound(env, groupRound, [
+      writable(winnerBet),
+      writable(loserBet),
+    ]);
+
+    const bet = await env.program.account.bet.fetch(loserBet);
+    expect(bet.status).to.deep.equal({ lost: {} });
+    expect(bet.lossRebate.toString()).to.eq(lossRebate.toString());
+    const r = await env.program.account.round.fetch(groupRound.roundPda);
+    expect(r.lossRebate.toString()).to.eq(lossRebate.toString());
+    expect(r.totalRewardPool.toString()).to.eq(
+      rewardPool(2 * BET_AMOUNT, GROUP_FEE_BPS).sub(lossRebate).toString()
+    );
+
+    const before = await tokenBalance(env, loser.tokenAccount);
+    await claimReward(env, groupRound, loserBet, loser);
+    const after = await tokenBalance(env, loser.tokenAccount);
+    expect(after.sub(before).toString()).to.eq(lossRebate.toString());
+  });
+
+  it("no-winner rounds roll over only the reward pool left after loss rebates", async () => {
+    const jackpot = await initializeJackpot(env);
+    const noWinnerRound = await createRound(env);
+    await startSingleRound(env, noWinnerRound, PRICE_FIXTURES.goldStart);
+    // both bets are Down and the price goes Up: nobody wins
+    const loserBet = await placeBet(env, noWinnerRound, loser, BET_AMOUNT, {
+      down: {},
+    });
+    const lateLoserBet = await placeBet(
+      env,
+      noWinnerRound,
+      lateLoser,
+      BET_AMOUNT,
+      { down: {} }
+    );
+    const jackpotBefore = await tokenBalance(env, jackpot.jackpotVaultPda);
+
+    await settleSingleRound(
+      env,
+      noWinnerRound,
+      PRICE_FIXTURES.goldUp,
+      [writable(loserBet), writable(lateLoserBet)],
+      { jackpot: jackpot.jackpotPda, jackpotVault: jackpot.jackpotVaultPda }
+    );
+
+    // jackpot_fee_bps is 0, so the jackpot only takes the rollover
+    const rollover = rewardPool(2 * BET_AMOUNT).sub(lossRebate.muln(2));
+    const r = await env.program.account.round.fetch(noWinnerRound.roundPda);
+    expect(r.winnersWeight.toString()).to.eq("0");
+    expect(r.jackpotContribution.toString()).to.eq(rollover.toString());
+    const jackpotAfter = await tokenBalance(env, jackpot.jackpotVaultPda);
+    expect(jackpotAfter.sub(jackpotBefore).toString()).to.eq(
+      rollover.toString()
+    );
+
+    // the rebates stay behind for the losers to claim
+    expect((await tokenBalance(env, noWinnerRound.vaultPda)).toString()).to.eq(
+      lossRebate.muln(2).toString()
+    );
+    const before = await tokenBalance(env, loser.tokenAccount);
+    await claimReward(env, noWinnerRound, loserBet, loser);
+    const after = await tokenBalance(env, loser.tokenAccount);
+    expect(after.sub(before).toString()).to.eq(lossRebate.toString());
+  });
+});
@@ -109,7 +109,10 @@ describe("placeBetGroupRound", () => {
       .createRound(
         { groupBattle: {} },
         new anchor.BN(start),
-        new anchor.BN(end)
+        new anchor.BN(end),
+        { mean: {} },
+        false,
+        false
       )
       .accounts({
         signer: admin.publicKey,
@@ -165,7 +168,7 @@ describe("placeBetGroupRound", () => {
         );
 
         await program.methods
-          .insertAsset(stringToBytes(`S${i}`))
+          .insertAsset(stringToBytes(`S${i}`), 0)
           .accounts({
             signer: admin.publicKey,
             config: configPda,
@@ -344,7 +347,7 @@ describe("placeBetGroupRound", () => {
 
     try {
       await program.methods
-        .placeBet(amount, direction)
+        .placeBet(amount, direction, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -388,7 +391,7 @@ describe("placeBetGroupRound", () => {
 
     try {
       await program.methods
-        .placeBet(amount, direction)
+        .placeBet(amount, direction, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -432,7 +435,7 @@ describe("placeBetGroupRound", () => {
 
     try {
       await program.methods
-        .placeBet(amount, direction)
+        .placeBet(amount, direction, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -458,6 +461,54 @@ describe("placeBetGroupRound", () => {
     expect(bet.direction).to.deep.equal(direction);
   });
 
+  it("happy path underperform", async () => {
+    const amount = new anchor.BN(10_000_000); // 10 GRT
+    const direction = { underperform: {} };
+    const r = await program.account.round.fetch(roundPda);
+    const nextBetId = r.totalBets.addn(1);
+    const betPda = deriveBetPda(program.programId, roundPda, nextBetId);
+    let groupAssetPda: PublicKey;
+    for (let groupId = 1; groupId <= r.totalGroups.toNumber(); groupId++) {
+      groupAssetPda = deriveGroupAssetPda(
+        program.programId,
+        roundPda,
+        new anchor.BN(groupId)
+      );
+      break;
+    }
+
+    try {
+      await program.methods
+        .placeBet(amount, direction, null, null, null)
+        .accounts({
+          signer: user.publicKey,
+          config: configPda,
+          round: roundPda,
+          groupAsset: groupAssetPda,
+          bet: betPda,
+          vault: vaultPda,
+          tokenAccount: userTokenAccount,
+          mint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([user])
+        .rpc();
+    } catch (e: any) {
+      throw e;
+    }
+    const round = await program.account.round.fetch(roundPda);
+    const bet = await program.account.bet.fetch(betPda);
+    expect(round.totalBets.toString()).to.eq(bet.id.toString());
+    expect(bet.status).to.deep.equal({ pending: {} });
+    expect(bet.amount.toNumber()).to.eq(amount.toNumber());
+    expect(bet.direction).to.deep.equal(direction);
+    const groupAsset = await program.account.groupAsset.fetch(groupAssetPda);
+    expect(groupAsset.directionTotals.amounts[5].toNumber()).to.be.gte(
+      amount.toNumber()
+    );
+  });
+
   it("fails below min amount", async () => {
     const cfg = await program.account.config.fetch(configPda);
     const amount = cfg.minBetAmount.subn(1);
@@ -477,7 +528,7 @@ describe("placeBetGroupRound", () => {
 
     try {
       await program.methods
-        .placeBet(amount, direction)
+        .placeBet(amount, direction, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
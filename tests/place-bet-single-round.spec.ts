@@ -6,6 +6,7 @@ import {
   deriveBetPda,
   deriveConfigPda,
   deriveRoundPda,
+  deriveUserRoundPositionPda,
   deriveVaultPda,
 } from "./helpers/pda";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
@@ -98,7 +99,10 @@ describe("placeBetSingleRound", () => {
       .createRound(
         { singleAsset: {} },
         new anchor.BN(start),
-        new anchor.BN(end)
+        new anchor.BN(end),
+        { mean: {} },
+        false,
+        false
       )
       .accounts({
         signer: admin.publicKey,
@@ -166,7 +170,7 @@ describe("placeBetSingleRound", () => {
     const betPda = deriveBetPda(program.programId, roundPda, nextBetId);
     try {
       await program.methods
-        .placeBet(amount, direction)
+        .placeBet(amount, direction, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -190,6 +194,18 @@ describe("placeBetSingleRound", () => {
     expect(bet.status).to.deep.equal({ pending: {} });
     expect(bet.amount.toNumber()).to.eq(amount.toNumber());
     expect(bet.direction).to.deep.equal(direction);
+    expect(round.directionTotals.amounts[0].toNumber()).to.be.gte(
+      amount.toNumber()
+    );
+    expect(round.totalStake.toString()).to.eq(round.totalPool.toString());
+    const position = await program.account.userRoundPosition.fetch(
+      deriveUserRoundPositionPda(program.programId, roundPda, user.publicKey)
+    );
+    expect(position.bettor.toBase58()).to.eq(user.publicKey.toBase58());
+    expect(position.totalStake.toNumber()).to.be.gte(amount.toNumber());
+    expect(position.betIds.map((id) => id.toString())).to.include(
+      bet.id.toString()
+    );
   });
   it("happy path down", async () => {
     const amount = new anchor.BN(10_000_000); // 10 GRT
@@ -199,7 +215,7 @@ describe("placeBetSingleRound", () => {
     const betPda = deriveBetPda(program.programId, roundPda, nextBetId);
     try {
       await program.methods
-        .placeBet(amount, direction)
+        .placeBet(amount, direction, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -233,7 +249,7 @@ describe("placeBetSingleRound", () => {
     const betPda = deriveBetPda(program.programId, roundPda, nextBetId);
     try {
       await program.methods
-        .placeBet(amount, direction)
+        .placeBet(amount, direction, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -259,6 +275,38 @@ describe("placeBetSingleRound", () => {
     expect(bet.direction).to.deep.equal(direction);
   });
 
+  it("previews bet weight", async () => {
+    const amount = new anchor.BN(10_000_000); // 10 GRT
+    const preview = await program.methods
+      .previewBet(amount, { up: {} })
+      .accounts({
+        config: configPda,
+        round: roundPda,
+      } as any)
+      .view();
+    expect(preview.directionFactor.toNumber()).to.be.gt(0);
+    expect(preview.timeFactor.toNumber()).to.be.gt(0);
+    expect(preview.weight.toNumber()).to.be.gt(0);
+  });
+
+  it("returns implied odds", async () => {
+    const odds = await program.methods
+      .getImpliedOdds()
+      .accounts({
+        config: configPda,
+        round: roundPda,
+        groupAsset: null,
+      } as any)
+      .view();
+    const round = await program.account.round.fetch(roundPda);
+    expect(odds.totalPool.toString()).to.eq(round.totalPool.toString());
+    // up and percentage-up share the up side
+    expect(odds.directionMultipliersBps[0].toString()).to.eq(
+      odds.directionMultipliersBps[2].toString()
+    );
+    expect(odds.directionMultipliersBps[0].toNumber()).to.be.gt(0);
+  });
+
   it("fails below min amount", async () => {
     const cfg = await program.account.config.fetch(configPda);
     const amount = cfg.minBetAmount.subn(1);
@@ -268,7 +316,7 @@ describe("placeBetSingleRound", () => {
     const betPda = deriveBetPda(program.programId, roundPda, nextBetId);
     try {
       await program.methods
-        .placeBet(amount, direction)
+        .placeBet(amount, direction, null, null, null)
         .accounts({
           signer: user.publicKey,
           config: configPda,
@@ -292,4 +340,103 @@ describe("placeBetSingleRound", () => {
       }
     }
   });
+
+  it("fails when weight is below min_weight", async () => {
+    const amount = new anchor.BN(10_000_000); // 10 GRT
+    const direction = { up: {} };
+    const r = await program.account.round.fetch(roundPda);
+    const nextBetId = r.totalBets.addn(1);
+    const betPda = deriveBetPda(program.programId, roundPda, nextBetId);
+    try {
+      await program.methods
+        .placeBet(amount, direction, new anchor.BN("18446744073709551615"), null, null)
+        .accounts({
+          signer: user.publicKey,
+          config: configPda,
+          round: roundPda,
+          groupAsset: null,
+          bet: betPda,
+          vault: vaultPda,
+          tokenAccount: userTokenAccount,
+          mint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([user])
+        .rpc();
+
+      throw new Error("should fail");
+    } catch (e: any) {
+      const parsed = (anchor as any).AnchorError?.parse?.(e?.logs);
+      if (parsed) {
+        expect(parsed.error.errorCode.code).to.eq("BetWeightBelowMinimum");
+      }
+    }
+  });
+
+  it("fails after max_timestamp", async () => {
+    const amount = new anchor.BN(10_000_000); // 10 GRT
+    const direction = { up: {} };
+    const r = await program.account.round.fetch(roundPda);
+    const nextBetId = r.totalBets.addn(1);
+    const betPda = deriveBetPda(program.programId, roundPda, nextBetId);
+    try {
+      await program.methods
+        .placeBet(amount, direction, null, new anchor.BN(1), null)
+        .accounts({
+          signer: user.publicKey,
+          config: configPda,
+          round: roundPda,
+          groupAsset: null,
+          bet: betPda,
+          vault: vaultPda,
+          tokenAccount: userTokenAccount,
+          mint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([user])
+        .rpc();
+
+      throw new Error("should fail");
+    } catch (e: any) {
+      const parsed = (anchor as any).AnchorError?.parse?.(e?.logs);
+      if (parsed) {
+        expect(parsed.error.errorCode.code).to.eq("BetDeadlineExceeded");
+      }
+    }
+  });
+
+  it("fails underperform direction", async () => {
+    const amount = new anchor.BN(10_000_000); // 10 GRT
+    const direction = { underperform: {} };
+    const r = await program.account.round.fetch(roundPda);
+    const nextBetId = r.totalBets.addn(1);
+    const betPda = deriveBetPda(program.programId, roundPda, nextBetId);
+    try {
+      await program.methods
+        .placeBet(amount, direction, null, null, null)
+        .accounts({
+          signer: user.publicKey,
+          config: configPda,
+          round: roundPda,
+          groupAsset: null,
+          bet: betPda,
+          vault: vaultPda,
+          tokenAccount: userTokenAccount,
+          mint: tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([user])
+        .rpc();
+
+      throw new Error("should fail");
+    } catch (e: any) {
+      const parsed = (anchor as any).AnchorError?.parse?.(e?.logs);
+      if (parsed) {
+        expect(parsed.error.errorCode.code).to.eq("InvalidBetDirection");
+      }
+    }
+  });
 });
@@ -0,0 +1,198 @@
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  initializeReferrals,
+  newBettor,
+  registerReferrer,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+  withdrawReferralRewards,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  createRound,
+  placeBet,
+  settleSingleRound,
+  startSingleRound,
+  writable,
+} from "./helpers/round";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError, expectFailure } from "./helpers/errors";
+
+describe("referrals", () => {
+  const BET_AMOUNT = 10_000_000;
+  const FEE_BPS = 2_000; // fee_single_asset_bps from setupEnv
+  const REFERRAL_FEE_BPS = 1_000;
+
+  let env: TestEnv;
+  let referralVault: PublicKey;
+  let referrer: Bettor;
+  let referrerPda: PublicKey;
+  // alice and bob are referred, carol is not
+  let alice: Bettor;
+  let bob: Bettor;
+  let carol: Bettor;
+  let round: RoundAccounts;
+  let bets: { alice: PublicKey; bob: PublicKey; carol: PublicKey };
+
+  // referral_fee_bps of the fee charged on the two referred bets
+  const referralFee = new anchor.BN(2 * BET_AMOUNT)
+    .muln(FEE_BPS)
+    .divn(10_000)
+    .muln(REFERRAL_FEE_BPS)
+    .divn(10_000);
+
+  before(async () => {
+    env = await setupEnv();
+    referrer = await newBettor(env);
+    alice = await newBettor(env);
+    bob = await newBettor(env);
+    carol = await newBettor(env);
+  });
+
+  it("fails registerReferrer before referrals are initialized", async () => {
+    await expectAnchorError(
+      registerReferrer(env, referrer),
+      "ReferralsNotInitialized"
+    );
+  });
+
+  it("initializeReferrals creates the referral vault and enables referrals", async () => {
+    referralVault = await initializeReferrals(env);
+
+    const cfg = await env.program.account.config.fetch(env.configPda);
+    expect(cfg.referralsEnabled).to.be.true;
+
+    const vault = await getAccount(env.provider.connection, referralVault);
+    expect(vault.mint.toBase58()).to.eq(env.tokenMint.toBase58());
+    expect(vault.owner.toBase58()).to.eq(env.configPda.toBase58());
+    expect(vault.amount.toString()).to.eq("0");
+
+    await updateConfig(env, { newReferralFeeBps: REFERRAL_FEE_BPS });
+  });
+
+  it("fails initializeReferrals twice", async () => {
+    // the referral vault PDA already exists, so the system program rejects the init
+    await expectFailure(initializeReferrals(env));
+  });
+
+  it("registerReferrer creates the referrer account for the signer", async () => {
+    referrerPda = await registerReferrer(env, referrer);
+
+    const r = await env.program.account.referrer.fetch(referrerPda);
+    expect(r.authority.toBase58()).to.eq(referrer.user.publicKey.toBase58());
+    expect(r.referredBets.toString()).to.eq("0");
+    expect(r.claimable.toString()).to.eq("0");
+    expect(r.totalEarned.toString()).to.eq("0");
+  });
+
+  it("placeBet with a referrer records it on the bet and adds to the round's referred stake", async () => {
+    round = await createRound(env);
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+
+    const ref = {
+      referrer: referrer.user.publicKey,
+      referrerAccount: referrerPda,
+    };
+    bets = {
+      alice: await placeBet(env, round, alice, BET_AMOUNT, { up: {} }, ref),
+      bob: await placeBet(env, round, bob, BET_AMOUNT, { down: {} }, ref),
+      carol: await placeBet(env, round, carol, BET_AMOUNT, { up: {} }),
+    };
+
+    const aliceBet = await env.program.account.bet.fetch(bets.alice);
+    expect(aliceBet.referrer.toBase58()).to.eq(
+      referrer.user.publicKey.toBase58()
+    );
+    const carolBet = await env.program.account.bet.fetch(bets.carol);
+    expect(carolBet.referrer).to.be.null;
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.referredStake.toString()).to.eq((2 * BET_AMOUNT).toString());
+    expect(r.totalStake.toString()).to.eq((3 * BET_AMOUNT).toString());
+  });
+
+  it("fails placeBet referring the signer", async () => {
+    await expectAnchorError(
+      placeBet(env, round, referrer, BET_AMOUNT, { up: {} }, {
+        referrer: referrer.user.publicKey,
+        referrerAccount: referrerPda,
+      }),
+      "SelfReferral"
+    );
+  });
+
+  it("fails placeBet with an unregistered referrer", async () => {
+    await expectAnchorError(
+      placeBet(env, round, carol, BET_AMOUNT, { up: {} }, {
+        referrer: Keypair.generate().publicKey,
+      }),
+      "InvalidReferrerAccount"
+    );
+  });
+
+  describe("settlement", () => {
+    let vaultDelta: anchor.BN;
+
+    before(async () => {
+      const vaultBefore = await tokenBalance(env, referralVault);
+      // referred bets are followed by their Referrer PDA
+      await settleSingleRound(
+        env,
+        round,
+        PRICE_FIXTURES.goldUp,
+        [
+          writable(bets.alice),
+          writable(referrerPda),
+          writable(bets.bob),
+          writable(referrerPda),
+          writable(bets.carol),
+        ],
+        { referralVault }
+      );
+      vaultDelta = (await tokenBalance(env, referralVault)).sub(vaultBefore);
+    });
+
+    it("settleSingleRound moves referral_fee_bps of the referred fee into the referral vault", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      expect(r.referralFee.toString()).to.eq(referralFee.toString());
+      expect(vaultDelta.toString()).to.eq(referralFee.toString());
+    });
+
+    it("settleSingleRound accrues each referred bet's share to its referrer", async () => {
+      // both referred bets have the same stake, so each accrues half
+      const r = await env.program.account.referrer.fetch(referrerPda);
+      expect(r.referredBets.toString()).to.eq("2");
+      expect(r.claimable.toString()).to.eq(referralFee.toString());
+      expect(r.totalEarned.toString()).to.eq(referralFee.toString());
+
+      const settled = await env.program.account.round.fetch(round.roundPda);
+      expect(settled.referralAccrued.toString()).to.eq(referralFee.toString());
+    });
+
+    it("withdrawReferralRewards pays the claimable balance to the referrer", async () => {
+      const before = await tokenBalance(env, referrer.tokenAccount);
+
+      await withdrawReferralRewards(env, referrer);
+
+      const after = await tokenBalance(env, referrer.tokenAccount);
+      expect(after.sub(before).toString()).to.eq(referralFee.toString());
+      const r = await env.program.account.referrer.fetch(referrerPda);
+      expect(r.claimable.toString()).to.eq("0");
+      expect(r.totalWithdrawn.toString()).to.eq(referralFee.toString());
+      expect((await tokenBalance(env, referralVault)).toString()).to.eq("0");
+    });
+
+    it("fails withdrawReferralRewards with nothing to claim", async () => {
+      await expectAnchorError(
+        withdrawReferralRewards(env, referrer),
+        "NothingToClaim"
+      );
+    });
+  });
+});
@@ -105,7 +105,10 @@ describe("settleSingleRound", () => {
       .createRound(
         { singleAsset: {} },
         new anchor.BN(start),
-        new anchor.BN(end)
+        new anchor.BN(end),
+        { mean: {} },
+        false,
+        false
       )
       .accounts({
         signer: admin.publicKey,
@@ -170,7 +173,7 @@ describe("settleSingleRound", () => {
     const nextBetId = r.totalBets.addn(1);
     betPda = deriveBetPda(program.programId, roundPda, nextBetId);
     await program.methods
-      .placeBet(amount, direction)
+      .placeBet(amount, direction, null, null, null)
       .accounts({
         signer: user.publicKey,
         config: configPda,
@@ -0,0 +1,229 @@
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  initializeStaking,
+  newBettor,
+  setupEnv,
+  stake,
+  tokenBalance,
+  unstake,
+  updateConfig,
+  withdrawStake,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  claimReward,
+  createRound,
+  placeBet,
+  settleSingleRound,
+  startSingleRound,
+  waitForChainTime,
+  writable,
+} from "./helpers/round";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError, expectFailure } from "./helpers/errors";
+
+describe("staking", () => {
+  const BET_AMOUNT = 10_000_000;
+  const FEE_BPS = 2_000; // fee_single_asset_bps from setupEnv
+  const DISCOUNT_BPS = 5_000;
+  const UNSTAKE_COOLDOWN_SECS = 5;
+
+  let env: TestEnv;
+  let stakeVault: PublicKey;
+  // winner bets Up and loser Down, both staked above the discount tier
+  let winner: Bettor;
+  let loser: Bettor;
+  let winnerStake: PublicKey;
+  let loserStake: PublicKey;
+  let round: RoundAccounts;
+  let winnerBet: PublicKey;
+  let loserBet: PublicKey;
+
+  // each bet is discounted on half its stake, so the rebate is the fee on 10 GRT, shared evenly
+  const feeRebate = new anchor.BN(BET_AMOUNT).muln(FEE_BPS).divn(10_000);
+  const rebatePerBet = feeRebate.divn(2);
+
+  before(async () => {
+    env = await setupEnv();
+    winner = await newBettor(env, 200_000_000);
+    loser = await newBettor(env, 200_000_000);
+  });
+
+  it("fails stake before staking is initialized", async () => {
+    // the stake vault does not exist yet, so the account check rejects the stake
+    await expectAnchorError(
+      stake(env, winner, 100_000_000),
+      "AccountNotInitialized"
+    );
+  });
+
+  it("initializeStaking creates the stake vault and enables staking", async () => {
+    stakeVault = await initializeStaking(env);
+
+    const cfg = await env.program.account.config.fetch(env.configPda);
+    expect(cfg.stakingEnabled).to.be.true;
+
+    const vault = await getAccount(env.provider.connection, stakeVault);
+    expect(vault.mint.toBase58()).to.eq(env.tokenMint.toBase58());
+    expect(vault.owner.toBase58()).to.eq(env.configPda.toBase58());
+    expect(vault.amount.toString()).to.eq("0");
+
+    await updateConfig(env, {
+      newFeeDiscountTiers: [
+        { minStake: new anchor.BN(50_000_000), discountBps: DISCOUNT_BPS },
+      ],
+      newUnstakeCooldownSecs: new anchor.BN(UNSTAKE_COOLDOWN_SECS),
+    });
+  });
+
+  it("fails initializeStaking twice", async () => {
+    // the stake vault PDA already exists, so the system program rejects the init
+    await expectFailure(initializeStaking(env));
+  });
+
+  it("stake moves GRT into the stake vault and creates the stake account", async () => {
+    const before = await tokenBalance(env, winner.tokenAccount);
+
+    winnerStake = await stake(env, winner, 100_000_000);
+
+    const after = await tokenBalance(env, winner.tokenAccount);
+    expect(before.sub(after).toString()).to.eq("100000000");
+    expect((await tokenBalance(env, stakeVault)).toString()).to.eq(
+      "100000000"
+    );
+
+    const account = await env.program.account.stakeAccount.fetch(winnerStake);
+    expect(account.owner.toBase58()).to.eq(winner.user.publicKey.toBase58());
+    expect(account.stakedAmount.toString()).to.eq("100000000");
+    expect(account.unstakingAmount.toString()).to.eq("0");
+  });
+
+  it("unstake moves GRT to unstaking and starts the cooldown", async () => {
+    const connection = env.provider.connection;
+    const chainTime = await connection.getBlockTime(await connection.getSlot());
+
+    await unstake(env, winner, 20_000_000);
+
+    const account = await env.program.account.stakeAccount.fetch(winnerStake);
+    expect(account.stakedAmount.toString()).to.eq("80000000");
+    expect(account.unstakingAmount.toString()).to.eq("20000000");
+    expect(account.unstakeAvailableAt.toNumber()).to.be.at.least(
+      chainTime! + UNSTAKE_COOLDOWN_SECS
+    );
+    // the GRT stays in the vault until it is withdrawn
+    expect((await tokenBalance(env, stakeVault)).toString()).to.eq(
+      "100000000"
+    );
+  });
+
+  it("fails unstake above the staked amount", async () => {
+    await expectAnchorError(
+      unstake(env, winner, 80_000_001),
+      "InsufficientStake"
+    );
+  });
+
+  it("fails withdrawStake during the cooldown", async () => {
+    await expectAnchorError(
+      withdrawStake(env, winner),
+      "UnstakeCooldownActive"
+    );
+  });
+
+  it("withdrawStake returns the unstaked GRT after the cooldown", async () => {
+    const account = await env.program.account.stakeAccount.fetch(winnerStake);
+    await waitForChainTime(env, account.unstakeAvailableAt.toNumber());
+    const before = await tokenBalance(env, winner.tokenAccount);
+
+    await withdrawStake(env, winner);
+
+    const after = await tokenBalance(env, winner.tokenAccount);
+    expect(after.sub(before).toString()).to.eq("20000000");
+    const withdrawn = await env.program.account.stakeAccount.fetch(
+      winnerStake
+    );
+    expect(withdrawn.unstakingAmount.toString()).to.eq("0");
+    expect(withdrawn.stakedAmount.toString()).to.eq("80000000");
+  });
+
+  it("placeBet with a stake account snapshots the tier's fee discount on the bet", async () => {
+    loserStake = await stake(env, loser, 60_000_000);
+    round = await createRound(env);
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+
+    winnerBet = await placeBet(env, round, winner, BET_AMOUNT, { up: {} }, {
+      stakeAccount: winnerStake,
+    });
+    loserBet = await placeBet(env, round, loser, BET_AMOUNT, { down: {} }, {
+      stakeAccount: loserStake,
+    });
+
+    const bet = await env.program.account.bet.fetch(winnerBet);
+    expect(bet.feeDiscountBps).to.eq(DISCOUNT_BPS);
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.discountedStake.toString()).to.eq(BET_AMOUNT.toString());
+  });
+
+  it("fails placeBet with another wallet's stake account", async () => {
+    await expectAnchorError(
+      placeBet(env, round, loser, BET_AMOUNT, { down: {} }, {
+        stakeAccount: winnerStake,
+      }),
+      "InvalidStakeAccount"
+    );
+  });
+
+  describe("settlement", () => {
+    const fee = new anchor.BN(2 * BET_AMOUNT).muln(FEE_BPS).divn(10_000);
+    let treasuryDelta: anchor.BN;
+
+    before(async () => {
+      const treasuryBefore = await tokenBalance(env, env.treasuryTokenAccount);
+      await settleSingleRound(env, round, PRICE_FIXTURES.goldUp, [
+        writable(winnerBet),
+        writable(loserBet),
+      ]);
+      treasuryDelta = (
+        await tokenBalance(env, env.treasuryTokenAccount)
+      ).sub(treasuryBefore);
+    });
+
+    it("settleSingleRound keeps the fee on discounted stake in the vault as the round's fee rebate", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      expect(r.totalFeeCollected.toString()).to.eq(fee.toString());
+      expect(r.feeRebate.toString()).to.eq(feeRebate.toString());
+      expect(treasuryDelta.toString()).to.eq(fee.sub(feeRebate).toString());
+      expect((await tokenBalance(env, round.vaultPda)).toString()).to.eq(
+        r.totalRewardPool.add(feeRebate).toString()
+      );
+    });
+
+    it("claimReward pays a winning bet its reward plus its fee rebate", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      const before = await tokenBalance(env, winner.tokenAccount);
+
+      await claimReward(env, round, winnerBet, winner);
+
+      // the only winner takes the whole reward pool
+      const after = await tokenBalance(env, winner.tokenAccount);
+      expect(after.sub(before).toString()).to.eq(
+        r.totalRewardPool.add(rebatePerBet).toString()
+      );
+    });
+
+    it("claimReward pays a losing bet only its fee rebate", async () => {
+      const before = await tokenBalance(env, loser.tokenAccount);
+
+      await claimReward(env, round, loserBet, loser);
+
+      const after = await tokenBalance(env, loser.tokenAccount);
+      expect(after.sub(before).toString()).to.eq(rebatePerBet.toString());
+      expect((await tokenBalance(env, round.vaultPda)).toString()).to.eq("0");
+    });
+  });
+});
@@ -81,7 +81,10 @@ describe("startRoundSingleRound", () => {
       .createRound(
         { singleAsset: {} },
         new anchor.BN(start),
-        new anchor.BN(end)
+        new anchor.BN(end),
+        { mean: {} },
+        false,
+        false
       )
       .accounts({
         signer: admin.publicKey,
@@ -0,0 +1,139 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  claimReward,
+  claimRewardsBatch,
+  claimTriple,
+  createRound,
+  placeBet,
+  settleSingleRound,
+  startSingleRound,
+  sweepUnclaimedRewards,
+  waitForChainTime,
+  writable,
+} from "./helpers/round";
+import { airdropMany } from "./helpers/env";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
+
+describe("sweepUnclaimedRewards", () => {
+  const CLAIM_WINDOW_SECS = 15;
+
+  let env: TestEnv;
+  let upBettor: Bettor;
+  let downBettor: Bettor;
+  // noWindowRound is created before the claim window is configured
+  let noWindowRound: RoundAccounts;
+  let windowRound: RoundAccounts;
+  let upBet: PublicKey;
+
+  before(async () => {
+    env = await setupEnv();
+    upBettor = await newBettor(env);
+    downBettor = await newBettor(env);
+
+    noWindowRound = await createRound(env);
+    await updateConfig(env, {
+      newClaimWindowSecs: new anchor.BN(CLAIM_WINDOW_SECS),
+    });
+    windowRound = await createRound(env);
+
+    const bets = new Map<RoundAccounts, PublicKey[]>();
+    for (const round of [noWindowRound, windowRound]) {
+      await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+      bets.set(round, [
+        await placeBet(env, round, upBettor, 10_000_000, { up: {} }),
+        await placeBet(env, round, downBettor, 10_000_000, { down: {} }),
+      ]);
+    }
+    upBet = bets.get(windowRound)[0];
+
+    // the window round is settled last so its claim window is still open below
+    for (const round of [noWindowRound, windowRound]) {
+      await settleSingleRound(
+        env,
+        round,
+        PRICE_FIXTURES.goldUp,
+        bets.get(round).map(writable)
+      );
+    }
+  });
+
+  it("fails while the claim window is still open", async () => {
+    await expectAnchorError(
+      sweepUnclaimedRewards(env, windowRound),
+      "ClaimWindowNotExpired"
+    );
+  });
+
+  it("fails when the round has no claim window", async () => {
+    const r = await env.program.account.round.fetch(noWindowRound.roundPda);
+    expect(r.claimWindowSecs.toString()).to.eq("0");
+
+    await expectAnchorError(
+      sweepUnclaimedRewards(env, noWindowRound),
+      "ClaimWindowNotExpired"
+    );
+  });
+
+  it("fails when the signer is neither admin nor keeper", async () => {
+    const stranger = Keypair.generate();
+    await airdropMany(env.provider.connection, [stranger.publicKey]);
+    await expectAnchorError(
+      sweepUnclaimedRewards(env, windowRound, stranger),
+      "Unauthorized"
+    );
+  });
+
+  it("claim_reward fails with ClaimExpired once the claim window expired", async () => {
+    const r = await env.program.account.round.fetch(windowRound.roundPda);
+    expect(r.claimWindowSecs.toNumber()).to.eq(CLAIM_WINDOW_SECS);
+    await waitForChainTime(env, r.settledAt.toNumber() + CLAIM_WINDOW_SECS);
+
+    await expectAnchorError(
+      claimReward(env, windowRound, upBet, upBettor),
+      "ClaimExpired"
+    );
+  });
+
+  it("happy path moves the vault balance to the treasury and closes the round", async () => {
+    const before = await env.program.account.round.fetch(windowRound.roundPda);
+
+    // nobody claimed: the whole reward pool is still in the vault
+    const unclaimed = await tokenBalance(env, windowRound.vaultPda);
+    expect(unclaimed.toString()).to.eq(before.totalRewardPool.toString());
+    const treasuryBefore = await tokenBalance(env, env.treasuryTokenAccount);
+
+    await sweepUnclaimedRewards(env, windowRound);
+
+    const treasuryAfter = await tokenBalance(env, env.treasuryTokenAccount);
+    expect(treasuryAfter.sub(treasuryBefore).toString()).to.eq(
+      unclaimed.toString()
+    );
+    expect(
+      await env.provider.connection.getAccountInfo(windowRound.vaultPda)
+    ).to.be.null;
+
+    const r = await env.program.account.round.fetch(windowRound.roundPda);
+    expect(r.status).to.deep.equal({ closed: {} });
+    expect(r.unclaimedSwept.toString()).to.eq(unclaimed.toString());
+  });
+
+  it("claims fail with ClaimExpired after the sweep", async () => {
+    // claim_reward can no longer load the closed vault; the batch claim checks the round first
+    await expectAnchorError(
+      claimRewardsBatch(env, upBettor, claimTriple(windowRound, upBet)),
+      "ClaimExpired"
+    );
+  });
+});
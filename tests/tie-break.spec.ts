@@ -0,0 +1,245 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+} from "./helpers/config";
+import {
+  GroupRoundAccounts,
+  claimReward,
+  createGroupRound,
+  endGroupRound,
+  placeBet,
+  settleGroupRound,
+  startGroupRound,
+  writable,
+} from "./helpers/round";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
+
+describe("tie break rule", () => {
+  const BET_AMOUNT = 10_000_000;
+  const { solStart, solUp, solDown } = PRICE_FIXTURES;
+
+  let env: TestEnv;
+  let bettors: Bettor[];
+
+  // Plays a Group Battle round with one Up bet per group; each group holds one SOL asset
+  async function playRound(endPrices: PublicKey[]) {
+    const startPrices = endPrices.map(() => [solStart]);
+    const round = await createGroupRound(env, startPrices, {
+      durationSecs: 30,
+    });
+    await startGroupRound(env, round, startPrices);
+
+    const bets: PublicKey[] = [];
+    for (let g = 0; g < endPrices.length; g++) {
+      bets.push(
+        await placeBet(env, round, bettors[g], BET_AMOUNT, { up: {} }, {
+          groupAsset: round.groupAssetPdas[g],
+        })
+      );
+    }
+
+    await endGroupRound(
+      env,
+      round,
+      endPrices.map((price) => [price])
+    );
+    await settleGroupRound(env, round, bets.map(writable));
+    return { round, bets };
+  }
+
+  before(async () => {
+    env = await setupEnv();
+    bettors = [
+      await newBettor(env),
+      await newBettor(env),
+      await newBettor(env),
+    ];
+    await updateConfig(env, { newTieBreakRule: { lowestId: {} } });
+  });
+
+  describe("LowestId with every group tied", () => {
+    let round: GroupRoundAccounts;
+    let bets: PublicKey[];
+
+    before(async () => {
+      ({ round, bets } = await playRound([solUp, solUp]));
+    });
+
+    it("keeps every group as winner and settles the round as a full draw", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      expect(r.tieBreakRule).to.deep.equal({ lowestId: {} });
+      expect(r.winnerGroupIds.map((id) => id.toNumber())).to.deep.equal([
+        1, 2,
+      ]);
+      expect(r.totalFeeCollected.toString()).to.eq("0");
+      expect(r.winnersWeight.toString()).to.eq("0");
+
+      for (const bet of bets) {
+        expect(
+          (await env.program.account.bet.fetch(bet)).status
+        ).to.deep.equal({ draw: {} });
+      }
+    });
+
+    it("claimReward refunds every stake in full", async () => {
+      for (let g = 0; g < bets.length; g++) {
+        const before = await tokenBalance(env, bettors[g].tokenAccount);
+        await claimReward(env, round, bets[g], bettors[g]);
+        const after = await tokenBalance(env, bettors[g].tokenAccount);
+        expect(after.sub(before).toString()).to.eq(BET_AMOUNT.toString());
+      }
+    });
+  });
+
+  describe("LowestId with a partial tie", () => {
+    let round: GroupRoundAccounts;
+    let bets: PublicKey[];
+
+    before(async () => {
+      ({ round, bets } = await playRound([solUp, solUp, solDown]));
+    });
+
+    it("keeps only the lowest tied group id as winner", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      expect(r.winnerGroupIds.map((id) => id.toNumber())).to.deep.equal([1]);
+
+      const statuses = await Promise.all(
+        bets.map(
+          async (bet) => (await env.program.account.bet.fetch(bet)).status
+        )
+      );
+      expect(statuses).to.deep.equal([{ won: {} }, { lost: {} }, { lost: {} }]);
+    });
+
+    it("claimReward pays the lowest id group the reward pool", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      const before = await tokenBalance(env, bettors[0].tokenAccount);
+      await claimReward(env, round, bets[0], bettors[0]);
+      const after = await tokenBalance(env, bettors[0].tokenAccount);
+      expect(after.sub(before).toString()).to.eq(
+        r.totalRewardPool.toString()
+      );
+    });
+
+    it("fails claimReward on the tied group dropped by the rule", async () => {
+      await expectAnchorError(
+        claimReward(env, round, bets[1], bettors[1]),
+        "BetNotWonOrDraw"
+      );
+    });
+  });
+
+  describe("every group tied past MAX_WINNER_GROUP_IDS", () => {
+    // eleven groups of one unchanged SOL asset
+    const GROUPS = 11;
+    let round: GroupRoundAccounts;
+    let bets: PublicKey[];
+
+    before(async () => {
+      const prices = Array.from({ length: GROUPS }, () => [solStart]);
+      round = await createGroupRound(env, prices, { durationSecs: 60 });
+      await startGroupRound(env, round, prices);
+
+      // bets on the first and the last group
+      bets = [];
+      for (const [i, g] of [0, GROUPS - 1].entries()) {
+        bets.push(
+          await placeBet(env, round, bettors[i], BET_AMOUNT, { up: {} }, {
+            groupAsset: round.groupAssetPdas[g],
+          })
+        );
+      }
+
+      await endGroupRound(env, round, prices);
+      await settleGroupRound(env, round, bets.map(writable));
+    });
+
+    it("keeps the same capped ids as winners and losers and settles a full draw", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      expect(r.capturedEndGroups.toNumber()).to.eq(GROUPS);
+      expect(r.winnerGroupIds).to.have.length(10);
+      expect(r.loserGroupIds.map((id) => id.toNumber())).to.deep.equal(
+        r.winnerGroupIds.map((id) => id.toNumber())
+      );
+      expect(r.totalFeeCollected.toString()).to.eq("0");
+
+      for (const bet of bets) {
+        expect(
+          (await env.program.account.bet.fetch(bet)).status
+        ).to.deep.equal({ draw: {} });
+      }
+    });
+
+    it("claimReward refunds every stake in full", async () => {
+      for (let i = 0; i < bets.length; i++) {
+        const before = await tokenBalance(env, bettors[i].tokenAccount);
+        await claimReward(env, round, bets[i], bettors[i]);
+        const after = await tokenBalance(env, bettors[i].tokenAccount);
+        expect(after.sub(before).toString()).to.eq(BET_AMOUNT.toString());
+      }
+    });
+  });
+
+  describe("Split with more than MAX_WINNER_ASSETS tied assets", () => {
+    // two groups of six unchanged SOL assets: twelve assets tie
+    const ASSETS_PER_GROUP = 6;
+    let round: GroupRoundAccounts;
+    let bets: PublicKey[];
+
+    before(async () => {
+      await updateConfig(env, { newTieBreakRule: { split: {} } });
+      const prices = [0, 1].map(() => Array(ASSETS_PER_GROUP).fill(solStart));
+      round = await createGroupRound(env, prices, {
+        marketType: { assetBattle: {} },
+        durationSecs: 45,
+      });
+      await startGroupRound(env, round, prices);
+
+      bets = [];
+      for (let g = 0; g < 2; g++) {
+        bets.push(
+          await placeBet(env, round, bettors[g], BET_AMOUNT, { up: {} }, {
+            asset: round.assetPdas[g][0],
+          })
+        );
+      }
+
+      await endGroupRound(env, round, prices);
+      await settleGroupRound(env, round, bets.map(writable));
+    });
+
+    it("ranks every asset and settles the round as a full draw", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      expect(r.rankedAssets.toNumber()).to.eq(2 * ASSETS_PER_GROUP);
+      expect(r.maxAssetGrowthRateE8.toString()).to.eq(
+        r.minAssetGrowthRateE8.toString()
+      );
+      // only the first MAX_WINNER_ASSETS winners are kept
+      expect(r.winnerAssets).to.have.length(10);
+      expect(r.totalFeeCollected.toString()).to.eq("0");
+
+      for (const bet of bets) {
+        expect(
+          (await env.program.account.bet.fetch(bet)).status
+        ).to.deep.equal({ draw: {} });
+      }
+    });
+
+    it("claimReward refunds every stake in full", async () => {
+      for (let g = 0; g < bets.length; g++) {
+        const before = await tokenBalance(env, bettors[g].tokenAccount);
+        await claimReward(env, round, bets[g], bettors[g]);
+        const after = await tokenBalance(env, bettors[g].tokenAccount);
+        expect(after.sub(before).toString()).to.eq(BET_AMOUNT.toString());
+      }
+    });
+  });
+});
@@ -1,8 +1,191 @@
-import { getProviderAndProgram } from "./helpers/env";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import { TestEnv, setupEnv, updateConfig } from "./helpers/config";
+import { airdropMany } from "./helpers/env";
+import { expectAnchorError } from "./helpers/errors";
 
 describe("updateConfig", () => {
-  getProviderAndProgram();
+  let env: TestEnv;
 
-  it("happy path");
-  it("fails unauthorized");
+  before(async () => {
+    env = await setupEnv();
+  });
+
+  it("happy path", async () => {
+    const before = await env.program.account.config.fetch(env.configPda);
+
+    await updateConfig(env, {
+      newMinBetAmount: new anchor.BN(20_000_000),
+      newMaxBetAmount: new anchor.BN(500_000_000),
+      newMaxUserStakePerRound: new anchor.BN(1_000_000_000),
+      newMaxTotalPool: new anchor.BN(5_000_000_000),
+      newTieBreakRule: { lowestId: {} },
+      newDrawBandBps: 10,
+      newClaimWindowSecs: new anchor.BN(3_600),
+      newFeeSchedule: {
+        treasuryBps: 9_000,
+        keeperPoolBps: 0,
+        burnBps: 1_000,
+      },
+      newFeeDiscountTiers: [
+        { minStake: new anchor.BN(100_000_000), discountBps: 1_000 },
+        { minStake: new anchor.BN(1_000_000_000), discountBps: 2_500 },
+      ],
+      newLossRebateBps: 500,
+    });
+
+    const cfg = await env.program.account.config.fetch(env.configPda);
+    expect(cfg.minBetAmount.toString()).to.eq("20000000");
+    expect(cfg.maxBetAmount.toString()).to.eq("500000000");
+    expect(cfg.maxUserStakePerRound.toString()).to.eq("1000000000");
+    expect(cfg.maxTotalPool.toString()).to.eq("5000000000");
+    expect(cfg.tieBreakRule).to.deep.equal({ lowestId: {} });
+    expect(cfg.drawBandBps).to.eq(10);
+    expect(cfg.claimWindowSecs.toString()).to.eq("3600");
+    expect(cfg.feeSchedule).to.deep.equal({
+      treasuryBps: 9_000,
+      keeperPoolBps: 0,
+      burnBps: 1_000,
+    });
+    expect(cfg.feeDiscountTiers.map((t) => t.discountBps)).to.deep.equal([
+      1_000, 2_500,
+    ]);
+    expect(cfg.lossRebateBps).to.eq(500);
+    // knobs left out of the params are unchanged
+    expect(cfg.feeSingleAssetBps).to.eq(before.feeSingleAssetBps);
+    expect(cfg.treasury.toBase58()).to.eq(before.treasury.toBase58());
+    expect(cfg.version).to.eq(before.version + 1);
+  });
+
+  it("fails unauthorized", async () => {
+    const stranger = Keypair.generate();
+    await airdropMany(env.provider.connection, [stranger.publicKey]);
+    await expectAnchorError(
+      updateConfig(env, { newDrawBandBps: 20 }, stranger),
+      "Unauthorized"
+    );
+  });
+
+  it("fails max bet amount below min bet amount", async () => {
+    // min is 20 GRT after the happy path
+    await expectAnchorError(
+      updateConfig(env, { newMaxBetAmount: new anchor.BN(10_000_000) }),
+      "InvalidNewMaxBetAmount"
+    );
+  });
+
+  it("fails min bet amount raised above the current max bet amount", async () => {
+    // max is 500 GRT after the happy path; the pair is checked with the resulting values
+    await expectAnchorError(
+      updateConfig(env, { newMinBetAmount: new anchor.BN(600_000_000) }),
+      "InvalidNewMaxBetAmount"
+    );
+  });
+
+  it("fails max user stake per round below min bet amount", async () => {
+    await expectAnchorError(
+      updateConfig(env, {
+        newMaxUserStakePerRound: new anchor.BN(10_000_000),
+      }),
+      "InvalidNewMaxUserStakePerRound"
+    );
+  });
+
+  it("fails max total pool of 0", async () => {
+    await expectAnchorError(
+      updateConfig(env, { newMaxTotalPool: new anchor.BN(0) }),
+      "InvalidNewMaxTotalPool"
+    );
+  });
+
+  it("fails max total pool below the max bet amount", async () => {
+    // max bet is 500 GRT after the happy path
+    await expectAnchorError(
+      updateConfig(env, { newMaxTotalPool: new anchor.BN(400_000_000) }),
+      "InvalidNewMaxTotalPool"
+    );
+  });
+
+  it("fails max bet amount raised above the current max total pool", async () => {
+    // max total pool is 5000 GRT after the happy path
+    await expectAnchorError(
+      updateConfig(env, { newMaxBetAmount: new anchor.BN(6_000_000_000) }),
+      "InvalidNewMaxTotalPool"
+    );
+  });
+
+  it("fails negative claim window", async () => {
+    await expectAnchorError(
+      updateConfig(env, { newClaimWindowSecs: new anchor.BN(-1) }),
+      "InvalidNewClaimWindowSecs"
+    );
+  });
+
+  it("fails jackpot fee bps above 10000", async () => {
+    await expectAnchorError(
+      updateConfig(env, { newJackpotFeeBps: 10_001 }),
+      "InvalidNewJackpotFeeBps"
+    );
+  });
+
+  it("fails fee schedule not totaling 10000 bps", async () => {
+    await expectAnchorError(
+      updateConfig(env, {
+        newFeeSchedule: {
+          treasuryBps: 7_000,
+          keeperPoolBps: 2_000,
+          burnBps: 0,
+        },
+      }),
+      "InvalidNewFeeSchedule"
+    );
+  });
+
+  it("fails a keeper pool leg before the keeper reward pool is set", async () => {
+    await expectAnchorError(
+      updateConfig(env, {
+        newFeeSchedule: {
+          treasuryBps: 7_000,
+          keeperPoolBps: 2_000,
+          burnBps: 1_000,
+        },
+      }),
+      "KeeperRewardPoolNotSet"
+    );
+  });
+
+  it("fails referral fee bps pushing jackpot and referral slices above 10000", async () => {
+    await updateConfig(env, { newJackpotFeeBps: 6_000 });
+    await expectAnchorError(
+      updateConfig(env, { newReferralFeeBps: 4_001 }),
+      "InvalidNewReferralFeeBps"
+    );
+  });
+
+  it("fails fee discount tiers not ascending by min stake", async () => {
+    await expectAnchorError(
+      updateConfig(env, {
+        newFeeDiscountTiers: [
+          { minStake: new anchor.BN(1_000_000_000), discountBps: 2_500 },
+          { minStake: new anchor.BN(100_000_000), discountBps: 1_000 },
+        ],
+      }),
+      "InvalidNewFeeDiscountTiers"
+    );
+  });
+
+  it("fails negative unstake cooldown", async () => {
+    await expectAnchorError(
+      updateConfig(env, { newUnstakeCooldownSecs: new anchor.BN(-1) }),
+      "InvalidNewUnstakeCooldownSecs"
+    );
+  });
+
+  it("fails loss rebate bps above 10000", async () => {
+    await expectAnchorError(
+      updateConfig(env, { newLossRebateBps: 10_001 }),
+      "InvalidNewLossRebateBps"
+    );
+  });
 });
@@ -0,0 +1,375 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  initializePromotions,
+  mintVoucher,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  cashOutBet,
+  claimReward,
+  createRound,
+  placeBet,
+  placeBetWithVoucher,
+  settleSingleRound,
+  startSingleRound,
+  waitForChainTime,
+  withdrawBet,
+  writable,
+} from "./helpers/round";
+import { deriveUserRoundPositionPda } from "./helpers/pda";
+import { mintAmount } from "./helpers/token";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError, expectFailure } from "./helpers/errors";
+
+describe("vouchers", () => {
+  const VOUCHER_AMOUNT = 10_000_000;
+  const PROMO_FUNDING = 100_000_000;
+  const LOSS_REBATE_BPS = 1_000;
+
+  let env: TestEnv;
+  let promoVault: PublicKey;
+  // promoBettor only bets with vouchers, bettor with their own GRT
+  let promoBettor: Bettor;
+  let bettor: Bettor;
+  let voucher: PublicKey;
+  // promoBettor's Up bet wins, its Down bet and bettor's Down bet lose
+  let round: RoundAccounts;
+  let bets: { promoWinner: PublicKey; promoLoser: PublicKey; loser: PublicKey };
+
+  async function chainTime(): Promise<number> {
+    const connection = env.provider.connection;
+    return (await connection.getBlockTime(await connection.getSlot()))!;
+  }
+
+  // A voucher for promoBettor that stays valid for the whole spec
+  async function newVoucher(user: PublicKey = promoBettor.user.publicKey) {
+    return mintVoucher(env, user, VOUCHER_AMOUNT, (await chainTime()) + 3_600);
+  }
+
+  before(async () => {
+    env = await setupEnv();
+    promoBettor = await newBettor(env, 0);
+    bettor = await newBettor(env);
+    await updateConfig(env, {
+      newLossRebateBps: LOSS_REBATE_BPS,
+      newCashOutHaircutBps: 1_000,
+    });
+  });
+
+  it("initializePromotions creates the promo vault and enables promotions", async () => {
+    promoVault = await initializePromotions(env);
+
+    const cfg = await env.program.account.config.fetch(env.configPda);
+    expect(cfg.promotionsEnabled).to.be.true;
+
+    const vault = await getAccount(env.provider.connection, promoVault);
+    expect(vault.mint.toBase58()).to.eq(env.tokenMint.toBase58());
+    expect(vault.owner.toBase58()).to.eq(env.configPda.toBase58());
+    expect(vault.amount.toString()).to.eq("0");
+
+    await mintAmount(
+      env.provider.connection,
+      env.admin,
+      env.tokenMint,
+      promoVault,
+      PROMO_FUNDING
+    );
+  });
+
+  it("fails initializePromotions twice", async () => {
+    // the promo vault PDA already exists, so the system program rejects the init
+    await expectFailure(initializePromotions(env));
+  });
+
+  it("mintVoucher creates a voucher for the user and bumps the voucher counter", async () => {
+    const before = await env.program.account.config.fetch(env.configPda);
+    const expiresAt = (await chainTime()) + 3_600;
+
+    voucher = await mintVoucher(
+      env,
+      promoBettor.user.publicKey,
+      VOUCHER_AMOUNT,
+      expiresAt
+    );
+
+    const after = await env.program.account.config.fetch(env.configPda);
+    expect(after.currentVoucherCounter.toString()).to.eq(
+      before.currentVoucherCounter.addn(1).toString()
+    );
+    const v = await env.program.account.voucher.fetch(voucher);
+    expect(v.id.toString()).to.eq(after.currentVoucherCounter.toString());
+    expect(v.user.toBase58()).to.eq(promoBettor.user.publicKey.toBase58());
+    expect(v.amount.toString()).to.eq(VOUCHER_AMOUNT.toString());
+    expect(v.expiresAt.toNumber()).to.eq(expiresAt);
+    expect(v.redeemed).to.be.false;
+  });
+
+  it("fails mintVoucher by a non-admin", async () => {
+    await expectAnchorError(
+      mintVoucher(
+        env,
+        bettor.user.publicKey,
+        VOUCHER_AMOUNT,
+        (await chainTime()) + 3_600,
+        bettor.user
+      ),
+      "Unauthorized"
+    );
+  });
+
+  it("fails mintVoucher with an expiry in the past", async () => {
+    await expectAnchorError(
+      mintVoucher(
+        env,
+        promoBettor.user.publicKey,
+        VOUCHER_AMOUNT,
+        (await chainTime()) - 1
+      ),
+      "InvalidVoucherExpiry"
+    );
+  });
+
+  it("placeBetWithVoucher stakes the voucher amount from the promo vault as a promotional bet", async () => {
+    // long enough for the failures below to land before the cutoff
+    round = await createRound(env, { durationSecs: 30 });
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    const promoBefore = await tokenBalance(env, promoVault);
+
+    const promoWinner = await placeBetWithVoucher(
+      env,
+      round,
+      promoBettor,
+      voucher,
+      { up: {} }
+    );
+
+    const bet = await env.program.account.bet.fetch(promoWinner);
+    expect(bet.isPromotional).to.be.true;
+    expect(bet.amount.toString()).to.eq(VOUCHER_AMOUNT.toString());
+    expect(bet.bettor.toBase58()).to.eq(promoBettor.user.publicKey.toBase58());
+    const v = await env.program.account.voucher.fetch(voucher);
+    expect(v.redeemed).to.be.true;
+
+    // the stake comes from the promo vault, not from the bettor
+    const promoAfter = await tokenBalance(env, promoVault);
+    expect(promoBefore.sub(promoAfter).toString()).to.eq(
+      VOUCHER_AMOUNT.toString()
+    );
+    expect((await tokenBalance(env, round.vaultPda)).toString()).to.eq(
+      VOUCHER_AMOUNT.toString()
+    );
+    const promoBettorBalance = await tokenBalance(
+      env,
+      promoBettor.tokenAccount
+    );
+    expect(promoBettorBalance.toString()).to.eq("0");
+
+    bets = {
+      promoWinner,
+      promoLoser: await placeBetWithVoucher(
+        env,
+        round,
+        promoBettor,
+        await newVoucher(),
+        { down: {} }
+      ),
+      loser: await placeBet(env, round, bettor, VOUCHER_AMOUNT, { down: {} }),
+    };
+  });
+
+  it("fails placeBetWithVoucher with another user's voucher", async () => {
+    const bettorVoucher = await newVoucher(bettor.user.publicKey);
+    await expectAnchorError(
+      placeBetWithVoucher(env, round, promoBettor, bettorVoucher, { up: {} }),
+      "InvalidVoucher"
+    );
+  });
+
+  it("fails placeBetWithVoucher with a redeemed voucher", async () => {
+    await expectAnchorError(
+      placeBetWithVoucher(env, round, promoBettor, voucher, { up: {} }),
+      "VoucherAlreadyRedeemed"
+    );
+  });
+
+  it("fails placeBetWithVoucher after the voucher expired", async () => {
+    const expiresAt = (await chainTime()) + 2;
+    const expiring = await mintVoucher(
+      env,
+      promoBettor.user.publicKey,
+      VOUCHER_AMOUNT,
+      expiresAt
+    );
+    await waitForChainTime(env, expiresAt);
+
+    await expectAnchorError(
+      placeBetWithVoucher(env, round, promoBettor, expiring, { up: {} }),
+      "VoucherExpired"
+    );
+  });
+
+  it("fails increaseBet and cashOutBet on a promotional bet", async () => {
+    await expectAnchorError(
+      env.program.methods
+        .increaseBet(new anchor.BN(VOUCHER_AMOUNT))
+        .accounts({
+          signer: promoBettor.user.publicKey,
+          config: env.configPda,
+          round: round.roundPda,
+          bet: bets.promoWinner,
+          groupAsset: null,
+          userRoundPosition: deriveUserRoundPositionPda(
+            env.program.programId,
+            round.roundPda,
+            promoBettor.user.publicKey
+          ),
+          vault: round.vaultPda,
+          tokenAccount: promoBettor.tokenAccount,
+          mint: env.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([promoBettor.user])
+        .rpc(),
+      "PromotionalBetNotAllowed"
+    );
+
+    // cash-out only opens between the cutoff and the end time
+    const r = await env.program.account.round.fetch(round.roundPda);
+    await waitForChainTime(env, r.betCutoffTime.toNumber());
+    await expectAnchorError(
+      cashOutBet(
+        env,
+        round,
+        bets.promoWinner,
+        promoBettor,
+        PRICE_FIXTURES.goldUp
+      ),
+      "PromotionalBetNotAllowed"
+    );
+  });
+
+  it("withdrawBet returns a promotional stake to the promo vault", async () => {
+    const withdrawRound = await createRound(env);
+    await startSingleRound(env, withdrawRound, PRICE_FIXTURES.goldStart);
+    const betPda = await placeBetWithVoucher(
+      env,
+      withdrawRound,
+      promoBettor,
+      await newVoucher(),
+      { up: {} }
+    );
+    const promoBefore = await tokenBalance(env, promoVault);
+    const treasuryBefore = await tokenBalance(env, env.treasuryTokenAccount);
+
+    await withdrawBet(env, withdrawRound, betPda, promoBettor, null, {
+      promoVault,
+    });
+
+    // the whole stake goes back, with no early exit fee and nothing to the bettor
+    const promoAfter = await tokenBalance(env, promoVault);
+    expect(promoAfter.sub(promoBefore).toString()).to.eq(
+      VOUCHER_AMOUNT.toString()
+    );
+    const treasuryAfter = await tokenBalance(env, env.treasuryTokenAccount);
+    expect(treasuryAfter.sub(treasuryBefore).toString()).to.eq("0");
+    const promoBettorBalance = await tokenBalance(
+      env,
+      promoBettor.tokenAccount
+    );
+    expect(promoBettorBalance.toString()).to.eq("0");
+    expect((await tokenBalance(env, withdrawRound.vaultPda)).toString()).to.eq(
+      "0"
+    );
+  });
+
+  // the settlement runs after the failures above: root tests run before nested hooks
+  describe("settlement", () => {
+    before(async () => {
+      await settleSingleRound(env, round, PRICE_FIXTURES.goldUp, [
+        writable(bets.promoWinner),
+        writable(bets.promoLoser),
+        writable(bets.loser),
+      ]);
+    });
+
+    it("settleSingleRound gives losing promotional bets no loss rebate", async () => {
+      const lossRebate = new anchor.BN(VOUCHER_AMOUNT)
+        .muln(LOSS_REBATE_BPS)
+        .divn(10_000);
+
+      const promoLoser = await env.program.account.bet.fetch(bets.promoLoser);
+      expect(promoLoser.status).to.deep.equal({ lost: {} });
+      expect(promoLoser.lossRebate.toString()).to.eq("0");
+      const loser = await env.program.account.bet.fetch(bets.loser);
+      expect(loser.lossRebate.toString()).to.eq(lossRebate.toString());
+      const r = await env.program.account.round.fetch(round.roundPda);
+      expect(r.lossRebate.toString()).to.eq(lossRebate.toString());
+
+      await expectAnchorError(
+        claimReward(env, round, bets.promoLoser, promoBettor, promoVault),
+        "BetNotWonOrDraw"
+      );
+    });
+
+    it("claimReward pays a promotional win only the profit and returns the stake to the promo vault", async () => {
+      const r = await env.program.account.round.fetch(round.roundPda);
+      const promoBefore = await tokenBalance(env, promoVault);
+      const before = await tokenBalance(env, promoBettor.tokenAccount);
+
+      await claimReward(env, round, bets.promoWinner, promoBettor, promoVault);
+
+      // the only winner takes the whole reward pool, less the voucher stake
+      const after = await tokenBalance(env, promoBettor.tokenAccount);
+      expect(after.sub(before).toString()).to.eq(
+        r.totalRewardPool.subn(VOUCHER_AMOUNT).toString()
+      );
+      const promoAfter = await tokenBalance(env, promoVault);
+      expect(promoAfter.sub(promoBefore).toString()).to.eq(
+        VOUCHER_AMOUNT.toString()
+      );
+    });
+
+    it("claimReward returns a promotional draw's refund to the promo vault", async () => {
+      const drawRound = await createRound(env);
+      await startSingleRound(env, drawRound, PRICE_FIXTURES.goldStart);
+      const promoBet = await placeBetWithVoucher(
+        env,
+        drawRound,
+        promoBettor,
+        await newVoucher(),
+        { up: {} }
+      );
+      const betPda = await placeBet(env, drawRound, bettor, VOUCHER_AMOUNT, {
+        down: {},
+      });
+      // settled at the start price: a full draw refunds every stake
+      await settleSingleRound(env, drawRound, PRICE_FIXTURES.goldStart, [
+        writable(promoBet),
+        writable(betPda),
+      ]);
+      const promoBefore = await tokenBalance(env, promoVault);
+      const before = await tokenBalance(env, promoBettor.tokenAccount);
+
+      await claimReward(env, drawRound, promoBet, promoBettor, promoVault);
+
+      const bet = await env.program.account.bet.fetch(promoBet);
+      expect(bet.status).to.deep.equal({ draw: {} });
+      const promoAfter = await tokenBalance(env, promoVault);
+      expect(promoAfter.sub(promoBefore).toString()).to.eq(
+        VOUCHER_AMOUNT.toString()
+      );
+      const after = await tokenBalance(env, promoBettor.tokenAccount);
+      expect(after.sub(before).toString()).to.eq("0");
+    });
+  });
+});
@@ -1,8 +1,181 @@
-import { getProviderAndProgram } from "./helpers/env";
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Bettor,
+  TestEnv,
+  newBettor,
+  setupEnv,
+  tokenBalance,
+  updateConfig,
+} from "./helpers/config";
+import {
+  RoundAccounts,
+  createRound,
+  placeBet,
+  startSingleRound,
+  waitForChainTime,
+  withdrawBet,
+} from "./helpers/round";
+import { deriveUserRoundPositionPda } from "./helpers/pda";
+import { PRICE_FIXTURES } from "./helpers/pyth";
+import { expectAnchorError } from "./helpers/errors";
 
 describe("withdrawBet", () => {
-  getProviderAndProgram();
+  const EARLY_EXIT_FEE_BPS = 500;
 
-  it("happy path before cutoff");
-  it("fails after cutoff");
+  let env: TestEnv;
+  let round: RoundAccounts;
+  let partialBettor: Bettor;
+  let minBettor: Bettor;
+  let fullBettor: Bettor;
+  let partialBet: PublicKey;
+  let minBet: PublicKey;
+  let fullBet: PublicKey;
+
+  before(async () => {
+    env = await setupEnv();
+    partialBettor = await newBettor(env);
+    minBettor = await newBettor(env);
+    fullBettor = await newBettor(env);
+    await updateConfig(env, { newEarlyExitFeeBps: EARLY_EXIT_FEE_BPS });
+
+    round = await createRound(env, { durationSecs: 40 });
+    await startSingleRound(env, round, PRICE_FIXTURES.goldStart);
+    partialBet = await placeBet(env, round, partialBettor, 30_000_000, {
+      up: {},
+    });
+    minBet = await placeBet(env, round, minBettor, 15_000_000, { up: {} });
+    // placed last: a full withdrawal frees the highest bet id
+    fullBet = await placeBet(env, round, fullBettor, 10_000_000, {
+      down: {},
+    });
+  });
+
+  it("happy path before cutoff", async () => {
+    const roundBefore = await env.program.account.round.fetch(round.roundPda);
+    const bettorBefore = await tokenBalance(env, fullBettor.tokenAccount);
+    const treasuryBefore = await tokenBalance(env, env.treasuryTokenAccount);
+
+    await withdrawBet(env, round, fullBet, fullBettor, null);
+
+    // a full withdrawal pays the early-exit fee like a partial one
+    const fee = new anchor.BN(10_000_000).muln(EARLY_EXIT_FEE_BPS).divn(10_000);
+    const bettorAfter = await tokenBalance(env, fullBettor.tokenAccount);
+    expect(bettorAfter.sub(bettorBefore).toString()).to.eq(
+      new anchor.BN(10_000_000).sub(fee).toString()
+    );
+    const treasuryAfter = await tokenBalance(env, env.treasuryTokenAccount);
+    expect(treasuryAfter.sub(treasuryBefore).toString()).to.eq(fee.toString());
+
+    expect(await env.provider.connection.getAccountInfo(fullBet)).to.be.null;
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.totalBets.toNumber()).to.eq(roundBefore.totalBets.toNumber() - 1);
+    expect(r.totalPool.toString()).to.eq(
+      roundBefore.totalPool.subn(10_000_000).toString()
+    );
+
+    const position = await env.program.account.userRoundPosition.fetch(
+      deriveUserRoundPositionPda(
+        env.program.programId,
+        round.roundPda,
+        fullBettor.user.publicKey
+      )
+    );
+    expect(position.betIds).to.be.empty;
+    expect(position.totalStake.toString()).to.eq("0");
+    expect(position.totalWeight.toString()).to.eq("0");
+  });
+
+  it("partial withdrawal keeps the bet with proportional weight", async () => {
+    const betBefore = await env.program.account.bet.fetch(partialBet);
+    const roundBefore = await env.program.account.round.fetch(round.roundPda);
+
+    await withdrawBet(env, round, partialBet, partialBettor, 10_000_000);
+
+    const bet = await env.program.account.bet.fetch(partialBet);
+    expect(bet.status).to.deep.equal({ pending: {} });
+    expect(bet.amount.toString()).to.eq("20000000");
+    const expectedWeight = betBefore.weight.muln(20).divn(30);
+    expect(bet.weight.toString()).to.eq(expectedWeight.toString());
+
+    const r = await env.program.account.round.fetch(round.roundPda);
+    expect(r.totalPool.toString()).to.eq(
+      roundBefore.totalPool.subn(10_000_000).toString()
+    );
+    expect(r.totalBets.toString()).to.eq(roundBefore.totalBets.toString());
+
+    const position = await env.program.account.userRoundPosition.fetch(
+      deriveUserRoundPositionPda(
+        env.program.programId,
+        round.roundPda,
+        partialBettor.user.publicKey
+      )
+    );
+    expect(position.totalStake.toString()).to.eq("20000000");
+    expect(position.totalWeight.toString()).to.eq(expectedWeight.toString());
+  });
+
+  it("charges early exit fee to treasury", async () => {
+    const bettorBefore = await tokenBalance(env, partialBettor.tokenAccount);
+    const treasuryBefore = await tokenBalance(env, env.treasuryTokenAccount);
+
+    const amount = new anchor.BN(5_000_000);
+    await withdrawBet(env, round, partialBet, partialBettor, amount.toNumber());
+
+    const fee = amount.muln(EARLY_EXIT_FEE_BPS).divn(10_000);
+    const bettorAfter = await tokenBalance(env, partialBettor.tokenAccount);
+    expect(bettorAfter.sub(bettorBefore).toString()).to.eq(
+      amount.sub(fee).toString()
+    );
+    const treasuryAfter = await tokenBalance(env, env.treasuryTokenAccount);
+    expect(treasuryAfter.sub(treasuryBefore).toString()).to.eq(fee.toString());
+  });
+
+  it("fails partial withdrawal leaving less than min bet amount", async () => {
+    // 15 GRT - 10 GRT leaves 5 GRT, below the 10 GRT minimum
+    await expectAnchorError(
+      withdrawBet(env, round, minBet, minBettor, 10_000_000),
+      "BetBelowMinimum"
+    );
+  });
+
+  it("fails withdrawing more than the bet amount", async () => {
+    await expectAnchorError(
+      withdrawBet(env, round, minBet, minBettor, 15_000_001),
+      "InvalidBetAmount"
+    );
+  });
+
+  it("fails an early-exit fee withdrawal without the treasury token account", async () => {
+    await expectAnchorError(
+      withdrawBet(env, round, minBet, minBettor, 5_000_000, {
+        treasuryTokenAccount: null,
+      }),
+      "MissingTreasuryTokenAccount"
+    );
+  });
+
+  it("withdraws without the treasury token account when no fee is due", async () => {
+    await updateConfig(env, { newEarlyExitFeeBps: 0 });
+    const bettorBefore = await tokenBalance(env, minBettor.tokenAccount);
+
+    await withdrawBet(env, round, minBet, minBettor, 5_000_000, {
+      treasuryTokenAccount: null,
+    });
+
+    const bettorAfter = await tokenBalance(env, minBettor.tokenAccount);
+    expect(bettorAfter.sub(bettorBefore).toString()).to.eq("5000000");
+    await updateConfig(env, { newEarlyExitFeeBps: EARLY_EXIT_FEE_BPS });
+  });
+
+  it("fails after cutoff", async () => {
+    const r = await env.program.account.round.fetch(round.roundPda);
+    await waitForChainTime(env, r.betCutoffTime.toNumber());
+
+    await expectAnchorError(
+      withdrawBet(env, round, minBet, minBettor, null),
+      "RoundEnded"
+    );
+  });
 });